@@ -1,9 +1,13 @@
 //! ACBL member data fetching and parsing
 
+use crate::http::ClientConfig;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// ACBL member masterpoint information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MemberInfo {
     pub name: String,
     pub location: String,
@@ -22,8 +26,37 @@ pub struct ClubGameResult {
     pub event_type: Option<String>,
     pub tables: Option<u32>,
     pub sections: Vec<SectionResult>,
+    /// PBN link as written on the page, possibly relative
     pub pbn_url: Option<String>,
+    /// BWS link as written on the page, possibly relative
     pub bws_url: Option<String>,
+    /// Page the results were fetched from, for resolving relative links
+    pub page_url: Option<String>,
+}
+
+impl ClubGameResult {
+    /// Absolute URL of the PBN hand records, if the page links to them
+    pub fn pbn_download_url(&self) -> Option<String> {
+        resolve_url(self.page_url.as_deref(), self.pbn_url.as_deref()?)
+    }
+
+    /// Absolute URL of the BWS results file, if the page links to it
+    pub fn bws_download_url(&self) -> Option<String> {
+        resolve_url(self.page_url.as_deref(), self.bws_url.as_deref()?)
+    }
+}
+
+/// Resolve a link against the page it appeared on
+///
+/// Absolute links are returned as they are. Relative ones need `base` and
+/// give `None` without it.
+pub fn resolve_url(base: Option<&str>, href: &str) -> Option<String> {
+    let href = href.trim();
+    if let Ok(url) = url::Url::parse(href) {
+        return Some(url.into());
+    }
+    let base = url::Url::parse(base?).ok()?;
+    base.join(href).ok().map(String::from)
 }
 
 /// Section results (NS or EW)
@@ -53,16 +86,47 @@ pub struct PairResult {
 }
 
 /// Create an HTTP client with browser-like headers
-fn create_browser_client() -> Result<reqwest::blocking::Client, String> {
-    reqwest::blocking::Client::builder()
-        .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+fn create_browser_client(config: &ClientConfig) -> Result<reqwest::blocking::Client, String> {
+    config.blocking_client().map_err(|e| e.to_string())
 }
 
 /// Fetch a URL with browser-like headers
 pub fn fetch_with_browser_headers(url: &str) -> Result<String, String> {
-    let client = create_browser_client()?;
+    fetch_with_client_config(url, &ClientConfig::default())
+}
+
+/// Fetch a URL with browser-like headers through a configured client
+pub fn fetch_with_client_config(url: &str, config: &ClientConfig) -> Result<String, String> {
+    fetch_page(url, config).map_err(|failure| failure.message)
+}
+
+/// A failed fetch, and whether trying again might succeed
+#[derive(Debug)]
+struct FetchFailure {
+    message: String,
+    /// Transport errors, 5xx and 429 are worth retrying; 404 and the like aren't
+    retryable: bool,
+}
+
+impl FetchFailure {
+    fn permanent(message: String) -> Self {
+        Self {
+            message,
+            retryable: false,
+        }
+    }
+
+    fn transient(message: String) -> Self {
+        Self {
+            message,
+            retryable: true,
+        }
+    }
+}
+
+/// [`fetch_with_client_config`], keeping whether the failure is worth retrying
+fn fetch_page(url: &str, config: &ClientConfig) -> Result<String, FetchFailure> {
+    let client = create_browser_client(config).map_err(FetchFailure::permanent)?;
 
     let response = client.get(url)
         .header("Accept", "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,image/apng,*/*;q=0.8")
@@ -76,26 +140,144 @@ pub fn fetch_with_browser_headers(url: &str) -> Result<String, String> {
         .header("Sec-Fetch-User", "?1")
         .header("Cache-Control", "max-age=0")
         .send()
-        .map_err(|e| format!("Failed to fetch URL: {}", e))?;
+        .map_err(|e| FetchFailure::transient(format!("Failed to fetch URL: {}", e)))?;
 
     let status = response.status();
     if !status.is_success() {
-        return Err(format!(
+        let message = format!(
             "HTTP error: {} {}",
             status.as_u16(),
             status.canonical_reason().unwrap_or("Unknown")
-        ));
+        );
+        return Err(
+            if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                FetchFailure::transient(message)
+            } else {
+                FetchFailure::permanent(message)
+            },
+        );
     }
 
     response
         .text()
-        .map_err(|e| format!("Failed to read response: {}", e))
+        .map_err(|e| FetchFailure::transient(format!("Failed to read response: {}", e)))
+}
+
+/// Caching and retry settings for ACBL page fetches
+#[derive(Debug, Clone)]
+pub struct FetchOptions {
+    /// Directory for cached pages; `None` disables caching
+    pub cache_dir: Option<PathBuf>,
+    /// How long a cached page stays fresh
+    pub ttl: Duration,
+    /// Retries after the first failed attempt
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled on each further retry
+    pub backoff: Duration,
+    /// Timeout, proxy and user agent for the HTTP client
+    pub client: ClientConfig,
+}
+
+impl Default for FetchOptions {
+    fn default() -> Self {
+        Self {
+            cache_dir: None,
+            ttl: Duration::from_secs(24 * 60 * 60),
+            max_retries: 3,
+            backoff: Duration::from_millis(500),
+            client: ClientConfig::default(),
+        }
+    }
+}
+
+/// Fetch a URL with browser-like headers, using the cache and retry settings
+pub fn fetch_with_options(url: &str, options: &FetchOptions) -> Result<String, String> {
+    fetch_cached(url, options, |url| fetch_page(url, &options.client))
+}
+
+/// Serve `url` from the cache when fresh, otherwise call `fetch` and store
+/// the result
+///
+/// Only retryable failures (see [`FetchFailure`]) are retried, with backoff.
+fn fetch_cached<F>(url: &str, options: &FetchOptions, mut fetch: F) -> Result<String, String>
+where
+    F: FnMut(&str) -> Result<String, FetchFailure>,
+{
+    let cache_path = options.cache_dir.as_ref().map(|dir| cache_file(dir, url));
+
+    if let Some(ref path) = cache_path {
+        if let Some(body) = read_fresh_cache(path, options.ttl) {
+            log::debug!("ACBL cache hit for {}", url);
+            return Ok(body);
+        }
+    }
+
+    let mut attempt = 0;
+    let body = loop {
+        match fetch(url) {
+            Ok(body) => break body,
+            Err(e) if e.retryable && attempt < options.max_retries => {
+                let delay = options.backoff * 2u32.saturating_pow(attempt);
+                log::warn!(
+                    "Fetching {} failed ({}); retrying in {:?}",
+                    url,
+                    e.message,
+                    delay
+                );
+                std::thread::sleep(delay);
+                attempt += 1;
+            }
+            Err(e) => return Err(e.message),
+        }
+    };
+
+    if let Some(ref path) = cache_path {
+        // A failed cache write only costs a refetch next time
+        if let Err(e) = std::fs::create_dir_all(path.parent().unwrap_or(Path::new(".")))
+            .and_then(|_| std::fs::write(path, &body))
+        {
+            log::warn!("Failed to cache {}: {}", url, e);
+        }
+    }
+
+    Ok(body)
+}
+
+/// Cache file for a URL: a hash of the URL, so any URL maps to a valid filename
+///
+/// The hash is 64-bit FNV-1a, which unlike `DefaultHasher` is fixed across
+/// Rust releases, so the cache survives a toolchain upgrade.
+fn cache_file(dir: &Path, url: &str) -> PathBuf {
+    let hash = url.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    dir.join(format!("{:016x}.html", hash))
+}
+
+/// Read a cached page if it was written within `ttl`
+fn read_fresh_cache(path: &Path, ttl: Duration) -> Option<String> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    let age = modified.elapsed().unwrap_or_default();
+    if age > ttl {
+        return None;
+    }
+    std::fs::read_to_string(path).ok()
 }
 
 /// Fetch and parse ACBL Live for Clubs game results
 pub fn fetch_club_game_results(url: &str) -> Result<ClubGameResult, String> {
-    let html = fetch_with_browser_headers(url)?;
-    parse_club_game_html(&html)
+    fetch_club_game_results_with_options(url, &FetchOptions::default())
+}
+
+/// Fetch and parse ACBL Live for Clubs game results with caching and retries
+pub fn fetch_club_game_results_with_options(
+    url: &str,
+    options: &FetchOptions,
+) -> Result<ClubGameResult, String> {
+    let html = fetch_with_options(url, options)?;
+    let mut game = parse_club_game_html(&html)?;
+    game.page_url = Some(url.to_string());
+    Ok(game)
 }
 
 /// Parse ACBL Live for Clubs HTML
@@ -108,16 +290,19 @@ fn parse_club_game_html(html: &str) -> Result<ClubGameResult, String> {
     let club_name =
         extract_text_by_selector(&document, "h1, .club-name, [class*='club']").unwrap_or_default();
 
-    let event_name = extract_text_by_selector(&document, "h2, .event-name, [class*='event']")
-        .unwrap_or_default();
+    let event_name = extract_text_by_selector(&document, EVENT_NAME_SELECTOR).unwrap_or_default();
 
     // Look for date, MP limits, tables in the page
     let page_text = document.root_element().text().collect::<String>();
 
     let date = extract_date_from_text(&page_text).unwrap_or_default();
     let mp_limits = extract_mp_limits_from_text(&page_text).unwrap_or_default();
-    let tables = extract_tables_from_text(&page_text);
-    let event_type = extract_event_type_from_text(&page_text);
+
+    // Tables and event type also appear in navigation and boilerplate, so
+    // only look next to the event name
+    let header_text = event_header_text(&document).unwrap_or(page_text);
+    let tables = extract_tables_from_text(&header_text);
+    let event_type = extract_event_type_from_text(&header_text);
 
     // Extract PBN and BWS URLs
     let pbn_url = extract_file_url(&document, "pbn");
@@ -136,9 +321,28 @@ fn parse_club_game_html(html: &str) -> Result<ClubGameResult, String> {
         sections,
         pbn_url,
         bws_url,
+        page_url: None,
     })
 }
 
+/// Selector for the element holding the event name
+const EVENT_NAME_SELECTOR: &str = "h2, .event-name, [class*='event']";
+
+/// Text of the event header: the event name element and its siblings
+///
+/// Text nodes are joined by newlines so adjacent values don't run together.
+fn event_header_text(document: &scraper::Html) -> Option<String> {
+    use scraper::{ElementRef, Selector};
+
+    let selector = Selector::parse(EVENT_NAME_SELECTOR).ok()?;
+    let element = document.select(&selector).next()?;
+    let region = element
+        .parent()
+        .and_then(ElementRef::wrap)
+        .unwrap_or(element);
+    Some(region.text().collect::<Vec<_>>().join("\n"))
+}
+
 fn extract_text_by_selector(document: &scraper::Html, selector_str: &str) -> Option<String> {
     use scraper::Selector;
 
@@ -170,55 +374,124 @@ fn extract_mp_limits_from_text(text: &str) -> Option<String> {
     None
 }
 
+/// Number of tables from "Tables: 11"
+///
+/// Returns `None` if the text gives different table counts.
 fn extract_tables_from_text(text: &str) -> Option<u32> {
-    // Look for "Tables: 11" pattern
-    if let Some(idx) = text.find("Tables:") {
-        let start = idx + "Tables:".len();
-        let remaining = &text[start..].trim();
-        let num_str: String = remaining
-            .chars()
-            .take_while(|c| c.is_ascii_digit())
-            .collect();
-        return num_str.parse().ok();
-    }
-    None
+    let tables_regex = regex::Regex::new(r"\bTables:\s*(\d+)\b").ok()?;
+    let mut counts = tables_regex
+        .captures_iter(text)
+        .filter_map(|c| c[1].parse::<u32>().ok());
+    let first = counts.next()?;
+    counts.all(|n| n == first).then_some(first)
 }
 
+/// ACBL event types, most specific first, with the name to report when it
+/// differs from the matched text
+const EVENT_TYPES: [(&str, Option<&str>); 10] = [
+    (r"\bUpgraded Club Championship\b", None),
+    (r"\bUnit Championship\b", None),
+    (r"\bClub Championship\b", None),
+    (r"\bSectional Tournament at Clubs\b", Some("STaC")),
+    (r"\bSTaC\b", None),
+    (r"\bNAP(?: District \d+)?\b", None),
+    (r"\bGNT\b", None),
+    (r"\bCharity\b", None),
+    (r"\bSectional\b", None),
+    (r"\bRegional\b", None),
+];
+
+/// Event type named in the text, e.g. "Club Championship" or "NAP District 21"
+///
+/// A match inside a longer one ("Club Championship" within "Upgraded Club
+/// Championship") is ignored. Returns `None` if different event types are
+/// named, rather than guessing.
 fn extract_event_type_from_text(text: &str) -> Option<String> {
-    // Look for common event types
-    let event_types = [
-        "Unit Championship",
-        "Club Championship",
-        "Upgraded Club Championship",
-        "Charity",
-        "STaC",
-        "NAP",
-        "GNT",
-    ];
-    for event_type in event_types {
-        if text.contains(event_type) {
-            return Some(event_type.to_string());
+    let mut spans: Vec<std::ops::Range<usize>> = Vec::new();
+    let mut found: Vec<String> = Vec::new();
+    for (pattern, label) in EVENT_TYPES {
+        let event_regex = regex::Regex::new(pattern).ok()?;
+        for m in event_regex.find_iter(text) {
+            if spans
+                .iter()
+                .any(|s| s.start <= m.start() && m.end() <= s.end)
+            {
+                continue;
+            }
+            spans.push(m.range());
+            let name = label.unwrap_or(m.as_str()).to_string();
+            if !found.contains(&name) {
+                found.push(name);
+            }
         }
     }
-    None
+    match found.len() {
+        1 => found.pop(),
+        _ => None,
+    }
 }
 
+/// Link to a downloadable `file_type` ("pbn" or "bws") file, as written
+///
+/// Links whose path ends in the extension are preferred, then links whose
+/// text names the type, then any other link mentioning it. The first link
+/// wins among equals.
 fn extract_file_url(document: &scraper::Html, file_type: &str) -> Option<String> {
     use scraper::Selector;
 
+    let extension = format!(".{}", file_type);
+    let mut best: Option<(u8, &str)> = None;
     if let Ok(selector) = Selector::parse("a") {
         for link in document.select(&selector) {
-            if let Some(href) = link.value().attr("href") {
-                let text = link.text().collect::<String>().to_lowercase();
-                let href_lower = href.to_lowercase();
-
-                if text.contains(file_type) || href_lower.contains(file_type) {
-                    return Some(href.to_string());
-                }
+            let Some(href) = link.value().attr("href") else {
+                continue;
+            };
+            let text = link.text().collect::<String>().to_lowercase();
+            let href_lower = href.to_lowercase();
+            let path = href_lower.split(['?', '#']).next().unwrap_or("");
+            let text_names_type = text
+                .split(|c: char| !c.is_alphanumeric())
+                .any(|word| word == file_type);
+
+            let preference = if path.ends_with(&extension) {
+                0
+            } else if text_names_type {
+                1
+            } else if href_lower.contains(file_type) {
+                2
+            } else {
+                continue;
+            };
+            if !matches!(best, Some((found, _)) if found <= preference) {
+                best = Some((preference, href));
             }
         }
     }
-    None
+    best.map(|(_, href)| href.to_string())
+}
+
+/// Download a file, such as a game's PBN or BWS, through a configured client
+pub fn download_file(url: &str, config: &ClientConfig) -> Result<Vec<u8>, String> {
+    let client = create_browser_client(config)?;
+
+    let response = client
+        .get(url)
+        .send()
+        .map_err(|e| format!("Failed to fetch URL: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!(
+            "HTTP error: {} {}",
+            status.as_u16(),
+            status.canonical_reason().unwrap_or("Unknown")
+        ));
+    }
+
+    response
+        .bytes()
+        .map(|bytes| bytes.to_vec())
+        .map_err(|e| format!("Failed to read response: {}", e))
 }
 
 fn parse_section_results(document: &scraper::Html) -> Result<Vec<SectionResult>, String> {
@@ -235,6 +508,9 @@ fn parse_section_results(document: &scraper::Html) -> Result<Vec<SectionResult>,
 
     let cell_selector = Selector::parse("td").map_err(|e| format!("Invalid selector: {:?}", e))?;
 
+    let header_selector =
+        Selector::parse("th, td").map_err(|e| format!("Invalid selector: {:?}", e))?;
+
     let mut current_section = "A".to_string();
     let mut current_direction = "NS".to_string();
 
@@ -256,8 +532,25 @@ fn parse_section_results(document: &scraper::Html) -> Result<Vec<SectionResult>,
         // Check if this is a table with results
         if tag == "table" {
             let mut pairs = Vec::new();
+            let mut header: Option<Vec<String>> = None;
+            let mut columns: Option<RecapColumns> = None;
 
             for row in element.select(&row_selector) {
+                // Header rows name the columns; a following row of bare strat
+                // letters labels the Overall/Section sub-columns
+                let labels = header_labels(&row, &header_selector);
+                if is_strat_subheader(&labels) {
+                    if let Some(ref main) = header {
+                        columns = RecapColumns::from_header(main, Some(&labels));
+                    }
+                    continue;
+                }
+                if let Some(cols) = RecapColumns::from_header(&labels, None) {
+                    header = Some(labels);
+                    columns = Some(cols);
+                    continue;
+                }
+
                 let cells: Vec<String> = row
                     .select(&cell_selector)
                     .map(|cell| cell.text().collect::<String>().trim().to_string())
@@ -265,7 +558,11 @@ fn parse_section_results(document: &scraper::Html) -> Result<Vec<SectionResult>,
 
                 // Look for rows that look like pair results
                 // Typical format: Pair#, Names, Strat, Overall places, Section places, Score, %, MPs
-                if let Some(pair_result) = parse_pair_result_row(&cells) {
+                let pair_result = match columns {
+                    Some(ref cols) => cols.parse_row(&cells),
+                    None => parse_pair_result_row(&cells),
+                };
+                if let Some(pair_result) = pair_result {
                     pairs.push(pair_result);
                 }
             }
@@ -298,6 +595,172 @@ fn extract_section_letter(text: &str) -> Option<String> {
     None
 }
 
+/// Column positions in a recap table, found from its header row
+#[derive(Debug, Clone, Default, PartialEq)]
+struct RecapColumns {
+    pair: usize,
+    names: usize,
+    strat: Option<usize>,
+    /// Overall placing columns for strats A, B, C
+    overall: [Option<usize>; 3],
+    /// Section placing columns for strats A, B, C
+    section: [Option<usize>; 3],
+    score: Option<usize>,
+    percentage: Option<usize>,
+    masterpoints: Option<usize>,
+}
+
+impl RecapColumns {
+    /// Map a header row (with colspans expanded) to column positions
+    ///
+    /// Returns `None` unless the row has both a Pair and a Names column.
+    /// Overall/Section columns take their strat from a trailing letter in the
+    /// label ("Overall B"), else from the matching cell of `subheader`, else
+    /// from their order within the group.
+    fn from_header(labels: &[String], subheader: Option<&[String]>) -> Option<Self> {
+        let mut pair = None;
+        let mut names = None;
+        let mut columns = RecapColumns::default();
+        let mut overall = Vec::new();
+        let mut section = Vec::new();
+
+        for (i, label) in labels.iter().enumerate() {
+            let lower = label.to_lowercase();
+            if lower.starts_with("pair") && pair.is_none() {
+                pair = Some(i);
+            } else if lower.starts_with("name") || lower.starts_with("player") {
+                names = Some(i);
+            } else if lower.starts_with("strat") {
+                columns.strat = Some(i);
+            } else if lower.starts_with("overall") || lower.starts_with("ovr") {
+                overall.push(i);
+            } else if lower.starts_with("sect") {
+                section.push(i);
+            } else if lower.starts_with("score") {
+                columns.score = Some(i);
+            } else if lower == "%" || lower.starts_with("pct") || lower.starts_with("percent") {
+                columns.percentage = Some(i);
+            } else if lower == "mp" || lower == "mps" || lower.starts_with("masterpoint") {
+                columns.masterpoints = Some(i);
+            }
+        }
+
+        columns.pair = pair?;
+        columns.names = names?;
+
+        // Sub-header letters line up with the grouped columns in order
+        let mut grouped: Vec<usize> = overall.iter().chain(section.iter()).copied().collect();
+        grouped.sort_unstable();
+        let sub_letter = |col: usize| {
+            let sub = subheader?;
+            let pos = grouped.iter().position(|&g| g == col)?;
+            // The sub-header may cover only the grouped columns or the whole row
+            let cell = if sub.len() == labels.len() {
+                sub.get(col)
+            } else {
+                sub.get(pos)
+            };
+            cell.and_then(|c| strat_index(c))
+        };
+
+        for (group, slots) in [
+            (&overall, &mut columns.overall),
+            (&section, &mut columns.section),
+        ] {
+            for (pos, &col) in group.iter().enumerate() {
+                let strat = labels[col]
+                    .split_whitespace()
+                    .last()
+                    .and_then(strat_index)
+                    .filter(|_| labels[col].split_whitespace().count() > 1)
+                    .or_else(|| sub_letter(col))
+                    .unwrap_or(pos);
+                if strat < 3 {
+                    slots[strat] = Some(col);
+                }
+            }
+        }
+
+        Some(columns)
+    }
+
+    /// Parse a data row using the header's column positions
+    fn parse_row(&self, cells: &[String]) -> Option<PairResult> {
+        let cell = |col: Option<usize>| col.and_then(|c| cells.get(c)).map(String::as_str);
+        let placing = |col: Option<usize>| cell(col).and_then(parse_placing);
+        let number =
+            |col: Option<usize>| cell(col).and_then(|c| c.replace(',', "").parse::<f64>().ok());
+
+        let pair_number: u32 = cells.get(self.pair)?.parse().ok()?;
+        let (player1, player2) = split_pair_names(cells.get(self.names)?);
+
+        Some(PairResult {
+            pair_number,
+            player1,
+            player2,
+            strat: cell(self.strat).unwrap_or_default().to_string(),
+            overall_a: placing(self.overall[0]),
+            overall_b: placing(self.overall[1]),
+            overall_c: placing(self.overall[2]),
+            section_a: placing(self.section[0]),
+            section_b: placing(self.section[1]),
+            section_c: placing(self.section[2]),
+            score: number(self.score).unwrap_or(0.0),
+            percentage: number(self.percentage).unwrap_or(0.0),
+            masterpoints: cell(self.masterpoints)
+                .filter(|c| !c.is_empty())
+                .map(str::to_string),
+        })
+    }
+}
+
+/// Collect a row's cell texts, repeating each cell across its colspan
+fn header_labels(row: &scraper::ElementRef, cell_selector: &scraper::Selector) -> Vec<String> {
+    let mut labels = Vec::new();
+    for cell in row.select(cell_selector) {
+        let text = cell.text().collect::<String>().trim().to_string();
+        let span = cell
+            .value()
+            .attr("colspan")
+            .and_then(|s| s.parse::<usize>().ok())
+            .unwrap_or(1)
+            .max(1);
+        labels.extend(std::iter::repeat_n(text, span));
+    }
+    labels
+}
+
+/// A row consisting only of strat letters (and blanks), e.g. "A B C A B C"
+fn is_strat_subheader(labels: &[String]) -> bool {
+    labels.iter().any(|l| !l.is_empty())
+        && labels
+            .iter()
+            .all(|l| l.is_empty() || strat_index(l).is_some())
+}
+
+/// Index of a strat letter: A=0, B=1, C=2
+fn strat_index(s: &str) -> Option<usize> {
+    match s.trim() {
+        "A" | "a" => Some(0),
+        "B" | "b" => Some(1),
+        "C" | "c" => Some(2),
+        _ => None,
+    }
+}
+
+/// Parse a placing cell; ties such as "2/3" take the higher place
+fn parse_placing(cell: &str) -> Option<u32> {
+    cell.split(['/', '-']).next()?.trim().parse().ok()
+}
+
+/// Split "Player1 - Player2" into its two names
+fn split_pair_names(names: &str) -> (String, String) {
+    match names.split_once(" - ") {
+        Some((a, b)) => (a.trim().to_string(), b.trim().to_string()),
+        None => (names.to_string(), String::new()),
+    }
+}
+
 fn parse_pair_result_row(cells: &[String]) -> Option<PairResult> {
     // Need at least pair number, names, and some results
     if cells.len() < 5 {
@@ -379,14 +842,15 @@ fn parse_pair_result_row(cells: &[String]) -> Option<PairResult> {
 /// Fetch and parse ACBL member data from a District 21 style URL
 /// Returns a HashMap keyed by ACBL member number (as string)
 pub fn fetch_member_masterpoints(url: &str) -> Result<HashMap<String, MemberInfo>, String> {
-    // Fetch the page
-    let response =
-        reqwest::blocking::get(url).map_err(|e| format!("Failed to fetch URL: {}", e))?;
-
-    let body = response
-        .text()
-        .map_err(|e| format!("Failed to read response: {}", e))?;
+    fetch_member_masterpoints_with_options(url, &FetchOptions::default())
+}
 
+/// Fetch and parse ACBL member data with caching and retries
+pub fn fetch_member_masterpoints_with_options(
+    url: &str,
+    options: &FetchOptions,
+) -> Result<HashMap<String, MemberInfo>, String> {
+    let body = fetch_with_options(url, options)?;
     parse_member_html(&body)
 }
 
@@ -559,44 +1023,546 @@ fn parse_member_html_alternate(html: &str) -> Result<HashMap<String, MemberInfo>
     Ok(members)
 }
 
+/// A member entry in a JSON masterpoints file: MemberInfo plus the ACBL number
+#[derive(Debug, Deserialize)]
+struct MemberRecord {
+    #[serde(default, alias = "member_number", alias = "Member#")]
+    acbl_number: Option<String>,
+    #[serde(flatten)]
+    info: MemberInfo,
+}
+
+/// Load ACBL member data from a local file
+///
+/// Accepts a `.json` array of member objects (the `MemberInfo` fields plus an
+/// optional `acbl_number`) or a CSV with columns Member#, Name, Location,
+/// Rank, Points, Unit. Returns the same map as `fetch_member_masterpoints`.
+pub fn load_member_masterpoints_from_file(
+    path: &Path,
+) -> Result<HashMap<String, MemberInfo>, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    // A byte order mark would otherwise become part of the first CSV header
+    // and break JSON parsing
+    let content = content.trim_start_matches('\u{feff}');
+
+    let is_json = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("json"));
+
+    let records = if is_json {
+        serde_json::from_str::<Vec<MemberRecord>>(content)
+            .map_err(|e| format!("Invalid masterpoints JSON: {}", e))?
+    } else {
+        parse_member_csv(content)?
+    };
+
+    let mut members = HashMap::new();
+    for record in records {
+        if let Some(num) = record.acbl_number {
+            let num = num.trim().trim_start_matches('0');
+            if !num.is_empty() {
+                members.insert(num.to_string(), record.info.clone());
+            }
+        }
+        members.insert(record.info.name.to_lowercase(), record.info);
+    }
+
+    Ok(members)
+}
+
+/// Parse member rows from a CSV with a Member#, Name, Location, Rank, Points, Unit header
+fn parse_member_csv(content: &str) -> Result<Vec<MemberRecord>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("Invalid masterpoints CSV: {}", e))?
+        .clone();
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|h| h.eq_ignore_ascii_case(name))
+            .ok_or_else(|| format!("Masterpoints CSV is missing the {} column", name))
+    };
+    let number_col = column("Member#")?;
+    let name_col = column("Name")?;
+    let location_col = column("Location")?;
+    let rank_col = column("Rank")?;
+    let points_col = column("Points")?;
+    let unit_col = column("Unit")?;
+
+    let mut records = Vec::new();
+    for row in reader.records() {
+        let row = row.map_err(|e| format!("Invalid masterpoints CSV: {}", e))?;
+        let field = |i: usize| row.get(i).unwrap_or("").to_string();
+
+        records.push(MemberRecord {
+            acbl_number: Some(field(number_col)),
+            info: MemberInfo {
+                name: field(name_col),
+                location: field(location_col),
+                rank: field(rank_col),
+                points: field(points_col)
+                    .replace(",", "")
+                    .parse::<f64>()
+                    .unwrap_or(0.0),
+                unit: field(unit_col),
+            },
+        });
+    }
+
+    Ok(records)
+}
+
 /// Look up a member by ACBL number or name
+///
+/// Names match when they agree after [`normalize_name`], so "Smith, John"
+/// finds "John Smith". There is no partial matching: a name that matches no
+/// member gives `None`.
 pub fn lookup_member<'a>(
     members: &'a HashMap<String, MemberInfo>,
     acbl_number: &str,
     name: Option<&str>,
 ) -> Option<&'a MemberInfo> {
-    // Try ACBL number first (strip leading zeros for matching)
-    let normalized_num = acbl_number.trim_start_matches('0');
-    if let Some(info) = members.get(normalized_num) {
+    if let Some(info) = lookup_member_number(members, acbl_number) {
         return Some(info);
     }
-    if let Some(info) = members.get(acbl_number) {
+
+    let name = name?;
+    if let Some(info) = members.get(&name.to_lowercase()) {
         return Some(info);
     }
+    let target = normalize_name(name);
+    if target.is_empty() {
+        return None;
+    }
+    members
+        .values()
+        .find(|info| normalize_name(&info.name) == target)
+}
 
-    // Fall back to name matching
-    if let Some(name) = name {
-        let name_lower = name.to_lowercase();
-        if let Some(info) = members.get(&name_lower) {
-            return Some(info);
-        }
+/// Look up a member by ACBL number, or the closest name
+///
+/// Falls back from an exact [`lookup_member`] match to the member whose
+/// normalized name is most similar (Jaro-Winkler), provided the similarity
+/// is at least `threshold` (0.0 to 1.0; 0.9 is a reasonable start). Ties
+/// go to the lowest member number.
+#[cfg(feature = "fuzzy")]
+pub fn lookup_member_fuzzy<'a>(
+    members: &'a HashMap<String, MemberInfo>,
+    acbl_number: &str,
+    name: Option<&str>,
+    threshold: f64,
+) -> Option<&'a MemberInfo> {
+    if let Some(info) = lookup_member(members, acbl_number, name) {
+        return Some(info);
+    }
 
-        // Try partial name match (last name)
-        let last_name = name.split_whitespace().last().unwrap_or("").to_lowercase();
-        for (key, info) in members {
-            if key.contains(&last_name) && !last_name.is_empty() {
-                return Some(info);
-            }
-        }
+    let target = normalize_name(name?);
+    if target.is_empty() {
+        return None;
     }
 
-    None
+    // Members are keyed by number and again by name; score each member once,
+    // through the numbered entry when there is one
+    let numbered: std::collections::HashSet<&str> = members
+        .iter()
+        .filter(|(key, _)| is_member_number(key))
+        .map(|(_, info)| info.name.as_str())
+        .collect();
+
+    // Equal scores go to the lowest member number (then name key), so the
+    // result doesn't depend on HashMap order
+    let order = |key: &String| (!is_member_number(key), key.len(), key.clone());
+    members
+        .iter()
+        .filter(|(key, info)| is_member_number(key) || !numbered.contains(info.name.as_str()))
+        .map(|(key, info)| {
+            (
+                strsim::jaro_winkler(&target, &normalize_name(&info.name)),
+                key,
+                info,
+            )
+        })
+        .filter(|(score, _, _)| *score >= threshold)
+        .max_by(|a, b| {
+            a.0.total_cmp(&b.0)
+                .then_with(|| order(b.1).cmp(&order(a.1)))
+        })
+        .map(|(_, _, info)| info)
+}
+
+/// Whether a member map key is an ACBL number rather than a name
+#[cfg(feature = "fuzzy")]
+fn is_member_number(key: &str) -> bool {
+    !key.is_empty() && key.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Member by ACBL number, with or without leading zeros
+fn lookup_member_number<'a>(
+    members: &'a HashMap<String, MemberInfo>,
+    acbl_number: &str,
+) -> Option<&'a MemberInfo> {
+    let normalized_num = acbl_number.trim_start_matches('0');
+    if normalized_num.is_empty() {
+        return None;
+    }
+    members
+        .get(normalized_num)
+        .or_else(|| members.get(acbl_number))
+}
+
+/// Normalize a player name for comparison
+///
+/// Lowercases, drops punctuation other than hyphens and apostrophes,
+/// collapses whitespace, and turns "Last, First" into "first last".
+pub fn normalize_name(name: &str) -> String {
+    let ordered = match name.split_once(',') {
+        Some((last, first)) => format!("{} {}", first, last),
+        None => name.to_string(),
+    };
+    ordered
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '\'' {
+                c
+            } else {
+                ' '
+            }
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn test_fetch_options(cache_dir: Option<PathBuf>) -> FetchOptions {
+        FetchOptions {
+            cache_dir,
+            backoff: Duration::from_millis(1),
+            ..FetchOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_fetch_cached_hit_skips_fetch() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = test_fetch_options(Some(dir.path().to_path_buf()));
+        let calls = std::cell::Cell::new(0);
+        let fetch = |_: &str| {
+            calls.set(calls.get() + 1);
+            Ok("<html>page</html>".to_string())
+        };
+
+        let first = fetch_cached("https://example.com/a", &options, fetch).unwrap();
+        let second = fetch_cached("https://example.com/a", &options, fetch).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(calls.get(), 1);
+
+        // A different URL is a miss
+        fetch_cached("https://example.com/b", &options, fetch).unwrap();
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn test_fetch_cached_expired() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = FetchOptions {
+            ttl: Duration::ZERO,
+            ..test_fetch_options(Some(dir.path().to_path_buf()))
+        };
+        let mut calls = 0;
+        let mut fetch = |_: &str| {
+            calls += 1;
+            Ok(format!("body {}", calls))
+        };
+
+        fetch_cached("https://example.com/a", &options, &mut fetch).unwrap();
+        std::thread::sleep(Duration::from_millis(10));
+        let second = fetch_cached("https://example.com/a", &options, &mut fetch).unwrap();
+        assert_eq!(second, "body 2");
+    }
+
+    #[test]
+    fn test_fetch_cached_retries() {
+        let options = test_fetch_options(None);
+        let mut calls = 0;
+        let body = fetch_cached("https://example.com/a", &options, |_| {
+            calls += 1;
+            if calls < 3 {
+                Err(FetchFailure::transient("HTTP error: 503".to_string()))
+            } else {
+                Ok("ok".to_string())
+            }
+        })
+        .unwrap();
+        assert_eq!(body, "ok");
+        assert_eq!(calls, 3);
+
+        let mut calls = 0;
+        let err = fetch_cached("https://example.com/a", &options, |_| {
+            calls += 1;
+            Err(FetchFailure::transient("HTTP error: 503".to_string()))
+        })
+        .unwrap_err();
+        assert_eq!(err, "HTTP error: 503");
+        assert_eq!(calls, 4);
+    }
+
+    #[test]
+    fn test_fetch_cached_does_not_retry_permanent_failures() {
+        let options = test_fetch_options(None);
+        let mut calls = 0;
+        let err = fetch_cached("https://example.com/a", &options, |_| {
+            calls += 1;
+            Err(FetchFailure::permanent(
+                "HTTP error: 404 Not Found".to_string(),
+            ))
+        })
+        .unwrap_err();
+        assert_eq!(err, "HTTP error: 404 Not Found");
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_cache_file_is_stable() {
+        // Pinned so cached pages stay valid across toolchain upgrades
+        assert_eq!(
+            cache_file(Path::new("cache"), ""),
+            Path::new("cache").join("cbf29ce484222325.html")
+        );
+        assert_ne!(
+            cache_file(Path::new("cache"), "https://example.com/a"),
+            cache_file(Path::new("cache"), "https://example.com/b")
+        );
+    }
+
+    #[test]
+    fn test_parse_recap_placings() {
+        let html = include_str!("../../tests/fixtures/input/acbl-recap.html");
+        let document = scraper::Html::parse_document(html);
+        let sections = parse_section_results(&document).unwrap();
+
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].section, "A");
+        assert_eq!(sections[0].direction, "NS");
+
+        let pairs = &sections[0].pairs;
+        assert_eq!(pairs.len(), 3);
+
+        let first = &pairs[0];
+        assert_eq!(first.pair_number, 3);
+        assert_eq!(first.player1, "Jane Smith");
+        assert_eq!(first.player2, "Bob Jones");
+        assert_eq!(first.strat, "A");
+        assert_eq!(first.overall_a, Some(1));
+        assert_eq!(first.overall_b, None);
+        assert_eq!(first.section_a, Some(1));
+        assert_eq!(first.score, 98.5);
+        assert_eq!(first.percentage, 63.14);
+        assert_eq!(first.masterpoints, Some("1.75 Black".to_string()));
+
+        // Strat C pair placed in all three strats, tied for 2nd/3rd in A
+        let third = &pairs[2];
+        assert_eq!(third.strat, "C");
+        assert_eq!(third.overall_a, Some(2));
+        assert_eq!(third.overall_b, Some(1));
+        assert_eq!(third.overall_c, Some(1));
+        assert_eq!(third.section_c, Some(1));
+        assert_eq!(third.masterpoints, None);
+    }
+
+    #[test]
+    fn test_parse_club_game_header() {
+        let html = include_str!("../../tests/fixtures/input/acbl-club-game.html");
+        let game = parse_club_game_html(html).unwrap();
+
+        assert_eq!(game.event_name, "Tuesday Afternoon Open Pairs");
+        assert_eq!(game.date, "01/19/2026");
+        // The navigation's "Tables: 40" and footer championship are ignored
+        assert_eq!(game.tables, Some(11));
+        assert_eq!(
+            game.event_type.as_deref(),
+            Some("Upgraded Club Championship")
+        );
+    }
+
+    #[test]
+    fn test_club_game_download_urls() {
+        let html = include_str!("../../tests/fixtures/input/acbl-club-game.html");
+        let mut game = parse_club_game_html(html).unwrap();
+        assert_eq!(game.pbn_url.as_deref(), Some("/files/260119.pbn"));
+
+        // A relative link can't be used until the page URL is known
+        assert_eq!(game.pbn_download_url(), None);
+        game.page_url = Some("https://my.acbl.org/club-results/details/123".to_string());
+        assert_eq!(
+            game.pbn_download_url().as_deref(),
+            Some("https://my.acbl.org/files/260119.pbn")
+        );
+        assert_eq!(game.bws_download_url(), None);
+    }
+
+    #[test]
+    fn test_resolve_url() {
+        let base = Some("https://example.com/club/results/game.html");
+        assert_eq!(
+            resolve_url(base, "/download/123.pbn").as_deref(),
+            Some("https://example.com/download/123.pbn")
+        );
+        assert_eq!(
+            resolve_url(base, "files/123.bws").as_deref(),
+            Some("https://example.com/club/results/files/123.bws")
+        );
+        assert_eq!(
+            resolve_url(None, "https://cdn.example.com/123.pbn").as_deref(),
+            Some("https://cdn.example.com/123.pbn")
+        );
+        assert_eq!(resolve_url(None, "/download/123.pbn"), None);
+    }
+
+    #[test]
+    fn test_extract_file_url_prefers_extension() {
+        let html = scraper::Html::parse_document(
+            r#"<a href="/help/pbn-format">What is PBN?</a>
+               <a href="/download?id=7">BWS file</a>
+               <a href="/download/7.pbn?v=2">Hand records</a>"#,
+        );
+        assert_eq!(
+            extract_file_url(&html, "pbn").as_deref(),
+            Some("/download/7.pbn?v=2")
+        );
+        assert_eq!(
+            extract_file_url(&html, "bws").as_deref(),
+            Some("/download?id=7")
+        );
+        assert_eq!(extract_file_url(&html, "lin"), None);
+    }
+
+    #[test]
+    fn test_extract_event_type() {
+        assert_eq!(
+            extract_event_type_from_text("NAP District 21 qualifier").as_deref(),
+            Some("NAP District 21")
+        );
+        assert_eq!(
+            extract_event_type_from_text("Sectional Tournament at Clubs (STaC)").as_deref(),
+            Some("STaC")
+        );
+        assert_eq!(
+            extract_event_type_from_text("Regional Open Pairs").as_deref(),
+            Some("Regional")
+        );
+        // Ambiguous, or only part of a word
+        assert_eq!(extract_event_type_from_text("Sectional and Regional"), None);
+        assert_eq!(extract_event_type_from_text("SNAPshot"), None);
+    }
+
+    #[test]
+    fn test_extract_tables() {
+        assert_eq!(extract_tables_from_text("Tables: 11\nBoards: 24"), Some(11));
+        assert_eq!(
+            extract_tables_from_text("Tables: 11 ... Tables: 11"),
+            Some(11)
+        );
+        assert_eq!(extract_tables_from_text("Tables: 11 ... Tables: 9"), None);
+        assert_eq!(extract_tables_from_text("Boards: 24"), None);
+    }
+
+    #[test]
+    fn test_recap_columns_single_row_header() {
+        let labels: Vec<String> = [
+            "Pair",
+            "Names",
+            "Strat",
+            "Overall A",
+            "Overall B",
+            "Sect A",
+            "Sect B",
+            "Score",
+            "%",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let cols = RecapColumns::from_header(&labels, None).unwrap();
+        assert_eq!(cols.overall, [Some(3), Some(4), None]);
+        assert_eq!(cols.section, [Some(5), Some(6), None]);
+        assert_eq!(cols.percentage, Some(8));
+        assert_eq!(cols.masterpoints, None);
+    }
+
+    #[test]
+    fn test_load_member_masterpoints_csv() {
+        let path = std::path::Path::new(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/input/masterpoints.csv"
+        ));
+        let members = load_member_masterpoints_from_file(path).unwrap();
+
+        let info = lookup_member(&members, "0123456", None).unwrap();
+        assert_eq!(info.name, "Jane Smith");
+        assert_eq!(info.rank, "Gold Life Master");
+        assert_eq!(info.points, 2512.37);
+        assert_eq!(info.unit, "503");
+
+        let info = lookup_member(&members, "", Some("Bob Jones")).unwrap();
+        assert_eq!(info.location, "Oakland CA");
+        assert_eq!(info.points, 48.5);
+    }
+
+    #[test]
+    fn test_load_member_masterpoints_csv_with_bom() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("members.csv");
+        std::fs::write(
+            &path,
+            "\u{feff}Member#,Name,Location,Rank,Points,Unit\r\n\
+             0123456,Jane Smith,Berkeley CA,Gold Life Master,2512.37,503\r\n",
+        )
+        .unwrap();
+
+        let members = load_member_masterpoints_from_file(&path).unwrap();
+        let info = lookup_member(&members, "0123456", None).unwrap();
+        assert_eq!(info.name, "Jane Smith");
+        assert_eq!(info.unit, "503");
+    }
+
+    #[test]
+    fn test_load_member_masterpoints_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("members.json");
+        std::fs::write(
+            &path,
+            r#"[{"acbl_number": "7654321", "name": "Ann Lee", "location": "Davis CA",
+                 "rank": "Club Master", "points": 35.2, "unit": "503"}]"#,
+        )
+        .unwrap();
+
+        let members = load_member_masterpoints_from_file(&path).unwrap();
+        assert_eq!(members["7654321"].name, "Ann Lee");
+        assert_eq!(members["ann lee"].rank, "Club Master");
+    }
+
+    #[test]
+    fn test_load_member_masterpoints_missing_column() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("members.csv");
+        std::fs::write(&path, "Member#,Name\n1234567,Someone\n").unwrap();
+
+        let err = load_member_masterpoints_from_file(&path).unwrap_err();
+        assert!(err.contains("Location"));
+    }
+
     #[test]
     fn test_extract_number_from_url() {
         assert_eq!(
@@ -608,4 +1574,66 @@ mod tests {
             Some("9876543".to_string())
         );
     }
+
+    fn member(name: &str) -> MemberInfo {
+        MemberInfo {
+            name: name.to_string(),
+            location: String::new(),
+            rank: String::new(),
+            points: 0.0,
+            unit: String::new(),
+        }
+    }
+
+    fn members(names: &[&str]) -> HashMap<String, MemberInfo> {
+        names
+            .iter()
+            .map(|n| (n.to_lowercase(), member(n)))
+            .collect()
+    }
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name("Smith, John"), "john smith");
+        assert_eq!(normalize_name("  John   SMITH "), "john smith");
+        assert_eq!(normalize_name("O'Brien, Mary-Ann J."), "mary-ann j o'brien");
+    }
+
+    #[test]
+    fn test_lookup_member_by_name() {
+        let members = members(&["John Smith", "Bob Johnson"]);
+        let info = lookup_member(&members, "", Some("Smith, John")).unwrap();
+        assert_eq!(info.name, "John Smith");
+
+        // No substring fallback onto a different surname
+        let members = self::members(&["Bob Johnson", "Tom Smithson"]);
+        assert!(lookup_member(&members, "", Some("John Smith")).is_none());
+        assert!(lookup_member(&members, "", Some("Smith, John")).is_none());
+    }
+
+    #[cfg(feature = "fuzzy")]
+    #[test]
+    fn test_lookup_member_fuzzy() {
+        let members = members(&["Jon Smith", "Bob Johnson"]);
+        let info = lookup_member_fuzzy(&members, "", Some("Smith, John"), 0.9).unwrap();
+        assert_eq!(info.name, "Jon Smith");
+
+        let members = self::members(&["Bob Johnson"]);
+        assert!(lookup_member_fuzzy(&members, "", Some("Smith, John"), 0.9).is_none());
+    }
+
+    #[cfg(feature = "fuzzy")]
+    #[test]
+    fn test_lookup_member_fuzzy_ties_go_to_lowest_number() {
+        // Two members with the same name score identically
+        let mut members = HashMap::new();
+        for number in ["2345", "123"] {
+            let mut info = member("Jon Smith");
+            info.location = number.to_string();
+            members.insert(number.to_string(), info.clone());
+            members.insert("jon smith".to_string(), info);
+        }
+        let info = lookup_member_fuzzy(&members, "", Some("John Smith"), 0.9).unwrap();
+        assert_eq!(info.location, "123");
+    }
 }
@@ -0,0 +1,36 @@
+//! Text helpers for calls
+//!
+//! `Call` comes from bridge-types, whose PBN parser only accepts the PBN
+//! spellings. The BWS and PBN readers both see other spellings in the wild,
+//! so they share [`parse_call`] instead of each keeping their own.
+
+use crate::Call;
+
+/// Parse a call as written by PBN files or entered on a Bridgemate
+///
+/// Case-insensitive; accepts "PASS"/"P", "X", "XX" and bids such as "1C" or
+/// "3NT". Returns `None` for anything else.
+pub fn parse_call(s: &str) -> Option<Call> {
+    let s = s.trim().to_uppercase();
+    match s.as_str() {
+        "PASS" | "P" => Some(Call::Pass),
+        "X" => Some(Call::Double),
+        "XX" => Some(Call::Redouble),
+        _ => Call::from_pbn(&s),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_call() {
+        assert_eq!(parse_call("pass"), Some(Call::Pass));
+        assert_eq!(parse_call("x"), Some(Call::Double));
+        assert_eq!(parse_call("XX"), Some(Call::Redouble));
+        assert_eq!(parse_call(" 4s "), Call::from_pbn("4S"));
+        assert!(parse_call("4S").is_some());
+        assert_eq!(parse_call("??"), None);
+    }
+}
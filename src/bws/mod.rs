@@ -1,5 +1,7 @@
+pub mod ranking;
 pub mod reader;
 pub mod tables;
 
+pub use ranking::{rank_overall, OverallRank};
 pub use reader::{read_bws, BwsData};
 pub use tables::*;
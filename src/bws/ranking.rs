@@ -0,0 +1,200 @@
+//! Pair matchpoint totals and placings across sections
+
+use super::BwsData;
+use std::collections::{BTreeMap, HashMap};
+
+/// Pair matchpoint summary
+#[derive(Debug, Default, Clone)]
+pub struct PairMatchpoints {
+    pub boards_played: u32,
+    pub total_mp_pct: f64, // Sum of matchpoint percentages, including sit-out credit
+    pub sit_out_boards: u32,
+}
+
+/// Per-pair matchpoint totals keyed by (section, pair_number, is_ns)
+pub type PairMatchpointTotals = HashMap<(i32, i32, bool), PairMatchpoints>;
+
+/// Average matchpoint percentage per board, if any boards were played
+///
+/// Boards sat out count towards the average at their credited percentage.
+pub fn average_mp_pct(totals: &PairMatchpoints) -> Option<f64> {
+    let boards = totals.boards_played + totals.sit_out_boards;
+    (totals.boards_played > 0).then(|| totals.total_mp_pct / f64::from(boards))
+}
+
+/// A pair's placing across all sections
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverallRank {
+    pub section: i32,
+    pub pair: i32,
+    /// `Some(true)` for NS, `Some(false)` for EW, `None` in a one-winner field
+    pub direction: Option<bool>,
+    pub avg_mp_pct: f64,
+    pub rank: u32,
+}
+
+/// Rank pairs across sections
+///
+/// Two-winner sections (Mitchell, the default) pool their NS pairs into one
+/// field and their EW pairs into another. One-winner sections (`winners` of
+/// 1, e.g. a Howell) pool into a single field, with each pair's NS and EW
+/// boards combined. Pairs are compared by average MP%, which is already
+/// normalized for section size. Averages equal to two decimals share a place,
+/// and the next lower average gets the next place. Results are ordered NS
+/// field, EW field, one-winner field, best first.
+pub fn rank_overall(data: &BwsData, pair_totals: &PairMatchpointTotals) -> Vec<OverallRank> {
+    let one_winner = |section: i32| {
+        data.sections
+            .iter()
+            .any(|s| s.id == section && s.winners == Some(1))
+    };
+
+    let mut fields: BTreeMap<(i32, i32, Option<bool>), PairMatchpoints> = BTreeMap::new();
+    for (&(section, pair, is_ns), totals) in pair_totals {
+        let direction = if one_winner(section) {
+            None
+        } else {
+            Some(is_ns)
+        };
+        let entry = fields.entry((section, pair, direction)).or_default();
+        entry.boards_played += totals.boards_played;
+        entry.sit_out_boards += totals.sit_out_boards;
+        entry.total_mp_pct += totals.total_mp_pct;
+    }
+
+    let field_order = |direction: Option<bool>| match direction {
+        Some(true) => 0,
+        Some(false) => 1,
+        None => 2,
+    };
+    let ranks = dense_rank_pairs(&fields, |key| field_order(key.2), average_mp_pct);
+    let mut ranked: Vec<OverallRank> = fields
+        .iter()
+        .filter_map(|(&(section, pair, direction), totals)| {
+            Some(OverallRank {
+                section,
+                pair,
+                direction,
+                avg_mp_pct: average_mp_pct(totals)?,
+                rank: *ranks.get(&(section, pair, direction))?,
+            })
+        })
+        .collect();
+    ranked.sort_by(|a, b| {
+        field_order(a.direction)
+            .cmp(&field_order(b.direction))
+            .then(a.rank.cmp(&b.rank))
+            .then(a.section.cmp(&b.section))
+            .then(a.pair.cmp(&b.pair))
+    });
+    ranked
+}
+
+/// Dense rank of each pair by `score` within the field `group` puts it in,
+/// best first
+///
+/// Scores equal to two decimals (as displayed) share a rank, and the next
+/// lower score gets the next rank. Pairs without a score are not ranked.
+pub(crate) fn dense_rank_pairs<'a, K, T, G, F>(
+    totals: impl IntoIterator<Item = (&'a K, &'a T)>,
+    group: impl Fn(&K) -> G,
+    score: F,
+) -> HashMap<K, u32>
+where
+    K: Copy + Eq + std::hash::Hash + 'a,
+    T: 'a,
+    G: Ord,
+    F: Fn(&T) -> Option<f64>,
+{
+    let mut groups: BTreeMap<G, Vec<(K, i64)>> = BTreeMap::new();
+    for (key, pair) in totals {
+        if let Some(score) = score(pair) {
+            let hundredths = (score * 100.0).round() as i64;
+            groups
+                .entry(group(key))
+                .or_default()
+                .push((*key, hundredths));
+        }
+    }
+
+    let mut ranks = HashMap::new();
+    for pairs in groups.values_mut() {
+        pairs.sort_by(|a, b| b.1.cmp(&a.1));
+        let mut rank = 0;
+        let mut previous = None;
+        for (key, hundredths) in pairs.iter() {
+            if previous != Some(*hundredths) {
+                rank += 1;
+                previous = Some(*hundredths);
+            }
+            ranks.insert(*key, rank);
+        }
+    }
+    ranks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bws::SectionRow;
+
+    #[test]
+    fn test_rank_overall_two_sections() {
+        let section = |id: i32, letter: &str, winners: i32| SectionRow {
+            id,
+            letter: letter.to_string(),
+            tables: 2,
+            missing_pair: 0,
+            ew_move_before_play: None,
+            session: Some(1),
+            scoring_type: Some(0),
+            winners: Some(winners),
+        };
+        let data = BwsData {
+            sections: vec![section(1, "A", 2), section(2, "B", 2)],
+            ..Default::default()
+        };
+        let totals = |boards: u32, pct: f64| PairMatchpoints {
+            boards_played: boards,
+            total_mp_pct: pct,
+            ..Default::default()
+        };
+        let mut pair_totals = PairMatchpointTotals::new();
+        pair_totals.insert((1, 1, true), totals(4, 220.0)); // A1 NS 55%
+        pair_totals.insert((1, 2, true), totals(4, 180.0)); // A2 NS 45%
+        pair_totals.insert((2, 1, true), totals(2, 120.0)); // B1 NS 60%
+        pair_totals.insert((2, 2, true), totals(2, 110.0)); // B2 NS 55%
+        pair_totals.insert((1, 1, false), totals(4, 200.0)); // A1 EW 50%
+
+        let ranked = rank_overall(&data, &pair_totals);
+        let placings: Vec<(i32, i32, Option<bool>, u32)> = ranked
+            .iter()
+            .map(|r| (r.section, r.pair, r.direction, r.rank))
+            .collect();
+        assert_eq!(
+            placings,
+            vec![
+                (2, 1, Some(true), 1),
+                (1, 1, Some(true), 2),
+                (2, 2, Some(true), 2),
+                (1, 2, Some(true), 3),
+                (1, 1, Some(false), 1),
+            ]
+        );
+
+        // One-winner sections pool a pair's NS and EW boards into one field
+        let data = BwsData {
+            sections: vec![section(1, "A", 1), section(2, "B", 1)],
+            ..Default::default()
+        };
+        let ranked = rank_overall(&data, &pair_totals);
+        assert_eq!(ranked.len(), 4);
+        assert!(ranked.iter().all(|r| r.direction.is_none()));
+        let a1 = ranked
+            .iter()
+            .find(|r| r.section == 1 && r.pair == 1)
+            .unwrap();
+        assert_eq!(a1.avg_mp_pct, 52.5);
+        assert_eq!(a1.rank, 3);
+    }
+}
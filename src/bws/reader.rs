@@ -1,8 +1,12 @@
 use super::tables::*;
+use crate::auction::parse_call;
 use crate::error::{BridgeError, Result};
 use crate::{
-    dealer_from_board_number, Board, Card, Deal, Direction, Hand, Rank, Suit, Vulnerability,
+    dealer_from_board_number, Board, Call, Card, Deal, Direction, Hand, Rank, Suit, Vulnerability,
 };
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::BTreeSet;
 use std::path::Path;
 use std::process::Command;
 
@@ -16,10 +20,71 @@ pub struct BwsData {
     pub round_data: Vec<RoundDataRow>,
     pub received_data: Vec<ReceivedDataRow>,
     pub hand_records: Vec<HandRecordRow>,
+    pub biddings: Vec<BiddingRow>,
     pub boards: Vec<Board>,
+    /// Problems found while reading, such as rows that failed to parse
+    pub warnings: Vec<String>,
+}
+
+/// Overview of a BWS file's contents, from [`BwsData::summary`]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BwsSummary {
+    pub sections: Vec<SectionSummary>,
+    pub players: usize,
+    pub results: usize,
+    pub auctions: usize,
+    /// Distinct board numbers with results, ascending
+    pub boards: Vec<u32>,
+    /// Number of boards with hand records; 0 when the deals are in a
+    /// separate PBN file
+    pub hand_records: usize,
+    pub warnings: Vec<String>,
+}
+
+/// One section of a [`BwsSummary`]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SectionSummary {
+    pub letter: String,
+    pub tables: i32,
+    /// "Matchpoints", "IMPs" or "Unknown"; `None` if not recorded
+    pub scoring: Option<&'static str>,
+}
+
+/// Name of a section's ScoringType code
+pub fn scoring_type_name(code: i32) -> &'static str {
+    match code {
+        0 => "Matchpoints",
+        1 => "IMPs",
+        _ => "Unknown",
+    }
 }
 
 impl BwsData {
+    /// Counts and board numbers describing the file, as shown by `info`
+    pub fn summary(&self) -> BwsSummary {
+        BwsSummary {
+            sections: self
+                .sections
+                .iter()
+                .map(|s| SectionSummary {
+                    letter: s.letter.trim().to_string(),
+                    tables: s.tables,
+                    scoring: s.scoring_type.map(scoring_type_name),
+                })
+                .collect(),
+            players: self.player_names.len(),
+            results: self.received_data.len(),
+            auctions: self.auction_count(),
+            boards: get_board_numbers(self),
+            hand_records: if self.has_hand_records() {
+                self.boards.len()
+            } else {
+                0
+            },
+            warnings: self.warnings.clone(),
+        }
+    }
+
     pub fn has_hand_records(&self) -> bool {
         !self.hand_records.is_empty()
     }
@@ -28,6 +93,90 @@ impl BwsData {
         !self.received_data.is_empty()
     }
 
+    pub fn has_biddings(&self) -> bool {
+        !self.biddings.is_empty()
+    }
+
+    /// Number of distinct auctions (section, table, board) in the bidding data
+    pub fn auction_count(&self) -> usize {
+        self.biddings
+            .iter()
+            .map(|b| (b.section, b.table, b.board))
+            .collect::<BTreeSet<_>>()
+            .len()
+    }
+
+    /// Get the auction recorded at a table for a board, in bidding order
+    ///
+    /// Erased calls and calls that cannot be parsed are skipped. Returns an
+    /// empty auction if the file has no bidding data for that table.
+    pub fn auction_for(&self, section: i32, table: i32, board: i32) -> Vec<Call> {
+        let mut rows: Vec<&BiddingRow> = self
+            .biddings
+            .iter()
+            .filter(|b| b.section == section && b.table == table && b.board == board)
+            .filter(|b| b.erased.unwrap_or(0) == 0)
+            .collect();
+        rows.sort_by_key(|b| b.counter);
+        rows.iter().filter_map(|b| parse_call(&b.call)).collect()
+    }
+
+    /// Check each result against its board's hand record
+    ///
+    /// The opening lead is made by the player on declarer's left, so a
+    /// recorded lead held by anyone else means the board was fouled or the
+    /// result was misentered. Unreadable leads are reported too. Results
+    /// without a lead, a declarer or a hand record are skipped.
+    pub fn validate_results_against_hands(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+        for result in &self.received_data {
+            let Some(lead) = result.lead_card.as_deref().filter(|l| !l.trim().is_empty()) else {
+                continue;
+            };
+            let Some(board) = u32::try_from(result.board)
+                .ok()
+                .and_then(|n| self.boards.iter().find(|b| b.number == Some(n)))
+            else {
+                continue;
+            };
+            let Some((_, declarer)) =
+                crate::scoring::contract_from_bws_fields(&result.contract, &result.ns_ew)
+            else {
+                continue;
+            };
+
+            let label = format!(
+                "Board {}, section {} table {}",
+                result.board, result.section, result.table
+            );
+            let Some(card) = crate::cards::parse_card(lead) else {
+                issues.push(format!(
+                    "{}: unreadable lead card \"{}\"",
+                    label,
+                    lead.trim()
+                ));
+                continue;
+            };
+
+            let leader = declarer.next();
+            if board.deal.hand(leader).has_card(card) {
+                continue;
+            }
+            let holder = Direction::ALL
+                .into_iter()
+                .find(|&d| board.deal.hand(d).has_card(card));
+            let card = crate::cards::card_to_pbn(card);
+            issues.push(match holder {
+                Some(holder) => format!(
+                    "{}: lead {} is held by {}, not {} (left of declarer {})",
+                    label, card, holder, leader, declarer
+                ),
+                None => format!("{}: lead {} is not in the hand record", label, card),
+            });
+        }
+        issues
+    }
+
     /// Get player name for a given section, table, and direction
     pub fn get_player_at(&self, section: i32, table: i32, direction: &str) -> Option<&str> {
         self.player_numbers
@@ -36,6 +185,95 @@ impl BwsData {
             .and_then(|p| p.name.as_deref())
     }
 
+    /// Get the name of a pair's player in a direction
+    ///
+    /// PlayerNumbers records where players sat in the first round, so the
+    /// pair is followed back to its starting table (see [`Self::starting_table`]).
+    pub fn get_pair_player(&self, section: i32, pair: i32, direction: &str) -> Option<&str> {
+        let is_ns = direction == "N" || direction == "S";
+        self.get_player_at(
+            section,
+            self.starting_table(section, pair, is_ns),
+            direction,
+        )
+    }
+
+    /// Table at which a pair sat in the first round
+    ///
+    /// Taken from RoundData, or from the results if RoundData is empty. In a
+    /// Mitchell the EW pairs move, so EW pair 3 may start at table 1. Falls
+    /// back to table = pair number when the pair can't be found.
+    pub fn starting_table(&self, section: i32, pair: i32, is_ns: bool) -> i32 {
+        self.seatings(section)
+            .into_iter()
+            .filter(|s| if is_ns { s.2 == pair } else { s.3 == pair })
+            .min_by_key(|s| s.0)
+            .map_or(pair, |s| s.1)
+    }
+
+    /// Pair seated NS (or EW) at a table in the first round
+    ///
+    /// The inverse of [`Self::starting_table`], falling back to pair number =
+    /// table number.
+    pub fn pair_at_start(&self, section: i32, table: i32, is_ns: bool) -> i32 {
+        self.seatings(section)
+            .into_iter()
+            .filter(|s| s.1 == table)
+            .min_by_key(|s| s.0)
+            .map_or(table, |s| if is_ns { s.2 } else { s.3 })
+    }
+
+    /// (round, table, NS pair, EW pair) for each table and round of a section
+    fn seatings(&self, section: i32) -> Vec<(i32, i32, i32, i32)> {
+        let rounds: Vec<_> = self
+            .round_data
+            .iter()
+            .filter(|r| r.section == section)
+            .map(|r| (r.round, r.table, r.ns_pair, r.ew_pair))
+            .collect();
+        if !rounds.is_empty() {
+            return rounds;
+        }
+        self.received_data
+            .iter()
+            .filter(|r| r.section == section)
+            .map(|r| (r.round, r.table, r.pair_ns, r.pair_ew))
+            .collect()
+    }
+
+    /// Get the ACBL number of the player seated at a table, from PlayerNames.strID
+    ///
+    /// `PlayerNumbers.Number` is whatever was keyed in at the table, which is
+    /// often a club-local id rather than the ACBL number. The seat is joined
+    /// to PlayerNames first by numeric id, then by name (ignoring case).
+    /// Returns `None` if no PlayerNames row matches or its strID is blank.
+    pub fn acbl_number_for(&self, section: i32, table: i32, direction: &str) -> Option<&str> {
+        let seat = self
+            .player_numbers
+            .iter()
+            .find(|p| p.section == section && p.table == table && p.direction == direction)?;
+
+        let by_id = seat
+            .number
+            .trim()
+            .parse::<i32>()
+            .ok()
+            .and_then(|id| self.player_names.iter().find(|p| p.id == id));
+        let by_name = || {
+            let name = seat.name.as_deref()?.trim();
+            self.player_names
+                .iter()
+                .find(|p| !name.is_empty() && p.name.trim().eq_ignore_ascii_case(name))
+        };
+
+        let str_id = by_id.or_else(by_name)?.str_id.trim();
+        if str_id.is_empty() {
+            None
+        } else {
+            Some(str_id)
+        }
+    }
+
     /// Get pair of player names (North-South or East-West) for a table
     pub fn get_pair_names(
         &self,
@@ -88,8 +326,16 @@ pub fn list_tables(path: &Path) -> Result<Vec<String>> {
 }
 
 /// Export a table as CSV
+///
+/// Dates are exported as ISO dates and binary columns are stripped. The
+/// output is decoded as UTF-8, falling back to Windows-1252, which is what
+/// Access databases written on Windows usually contain.
 fn export_table(path: &Path, table: &str) -> Result<String> {
-    let output = Command::new("mdb-export").arg(path).arg(table).output()?;
+    let output = Command::new("mdb-export")
+        .args(["-D", "%Y-%m-%d", "-b", "strip"])
+        .arg(path)
+        .arg(table)
+        .output()?;
 
     if !output.status.success() {
         return Err(BridgeError::Bws(format!(
@@ -99,78 +345,119 @@ fn export_table(path: &Path, table: &str) -> Result<String> {
         )));
     }
 
-    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    Ok(decode_export(output.stdout))
+}
+
+/// Decode mdb-export output as UTF-8, or as Windows-1252 if it isn't valid UTF-8
+fn decode_export(bytes: Vec<u8>) -> String {
+    match String::from_utf8(bytes) {
+        Ok(s) => s,
+        Err(e) => e
+            .into_bytes()
+            .iter()
+            .map(|&b| windows_1252_char(b))
+            .collect(),
+    }
+}
+
+/// Map a Windows-1252 byte to its character
+fn windows_1252_char(byte: u8) -> char {
+    const HIGH: [char; 32] = [
+        '\u{20AC}', '\u{FFFD}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}',
+        '\u{2021}', '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{FFFD}',
+        '\u{017D}', '\u{FFFD}', '\u{FFFD}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}',
+        '\u{2022}', '\u{2013}', '\u{2014}', '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}',
+        '\u{0153}', '\u{FFFD}', '\u{017E}', '\u{0178}',
+    ];
+    match byte {
+        0x80..=0x9F => HIGH[usize::from(byte - 0x80)],
+        _ => char::from(byte),
+    }
+}
+
+/// Deserialize exported CSV rows, recording rows that fail to parse
+///
+/// Malformed rows are skipped rather than failing the whole file; each one
+/// adds a warning naming the table and record.
+fn parse_table_rows<T: DeserializeOwned>(
+    table: &str,
+    csv: &str,
+    warnings: &mut Vec<String>,
+) -> Vec<T> {
+    let mut reader = csv::Reader::from_reader(csv.as_bytes());
+    let mut rows = Vec::new();
+    for (index, row) in reader.deserialize().enumerate() {
+        match row {
+            Ok(row) => rows.push(row),
+            Err(e) => warnings.push(format!("{} row {} skipped: {}", table, index + 1, e)),
+        }
+    }
+    rows
 }
 
 /// Read and parse a BWS file
+///
+/// Tables missing from the file are left empty. Rows that can't be parsed
+/// are skipped and reported in [`BwsData::warnings`].
 pub fn read_bws(path: &Path) -> Result<BwsData> {
     check_mdbtools()?;
 
     let tables = list_tables(path)?;
     let mut data = BwsData::default();
+    let mut warnings = Vec::new();
 
-    // Read Section table
-    if tables.contains(&"Section".to_string()) {
-        let csv = export_table(path, "Section")?;
-        let mut reader = csv::Reader::from_reader(csv.as_bytes());
-        for row in reader.deserialize().flatten() {
-            data.sections.push(row);
+    let read = |table: &str| -> Result<Option<String>> {
+        if tables.iter().any(|t| t == table) {
+            export_table(path, table).map(Some)
+        } else {
+            Ok(None)
         }
+    };
+
+    // Section table
+    if let Some(csv) = read("Section")? {
+        data.sections = parse_table_rows("Section", &csv, &mut warnings);
     }
 
-    // Read Session table (event name and date)
-    if tables.contains(&"Session".to_string()) {
-        let csv = export_table(path, "Session")?;
-        let mut reader = csv::Reader::from_reader(csv.as_bytes());
-        for row in reader.deserialize().flatten() {
-            data.sessions.push(row);
-        }
+    // Session table (event name and date)
+    if let Some(csv) = read("Session")? {
+        data.sessions = parse_table_rows("Session", &csv, &mut warnings);
     }
 
-    // Read PlayerNames table
-    if tables.contains(&"PlayerNames".to_string()) {
-        let csv = export_table(path, "PlayerNames")?;
-        let mut reader = csv::Reader::from_reader(csv.as_bytes());
-        for row in reader.deserialize().flatten() {
-            data.player_names.push(row);
-        }
+    // PlayerNames table
+    if let Some(csv) = read("PlayerNames")? {
+        data.player_names = parse_table_rows("PlayerNames", &csv, &mut warnings);
     }
 
-    // Read ReceivedData table
-    if tables.contains(&"ReceivedData".to_string()) {
-        let csv = export_table(path, "ReceivedData")?;
-        let mut reader = csv::Reader::from_reader(csv.as_bytes());
-        for row in reader.deserialize().flatten() {
-            data.received_data.push(row);
-        }
+    // ReceivedData table
+    if let Some(csv) = read("ReceivedData")? {
+        data.received_data = parse_table_rows("ReceivedData", &csv, &mut warnings);
     }
 
-    // Read PlayerNumbers table (links section/table/direction to players)
-    if tables.contains(&"PlayerNumbers".to_string()) {
-        let csv = export_table(path, "PlayerNumbers")?;
-        let mut reader = csv::Reader::from_reader(csv.as_bytes());
-        for row in reader.deserialize().flatten() {
-            data.player_numbers.push(row);
-        }
+    // PlayerNumbers table (links section/table/direction to players)
+    if let Some(csv) = read("PlayerNumbers")? {
+        data.player_numbers = parse_table_rows("PlayerNumbers", &csv, &mut warnings);
     }
 
-    // Read RoundData table (pair-to-table assignments per round)
-    if tables.contains(&"RoundData".to_string()) {
-        let csv = export_table(path, "RoundData")?;
-        let mut reader = csv::Reader::from_reader(csv.as_bytes());
-        for row in reader.deserialize().flatten() {
-            data.round_data.push(row);
-        }
+    // RoundData table (pair-to-table assignments per round)
+    if let Some(csv) = read("RoundData")? {
+        data.round_data = parse_table_rows("RoundData", &csv, &mut warnings);
     }
 
-    // Read HandRecord table if available
-    if tables.contains(&"HandRecord".to_string()) {
-        let csv = export_table(path, "HandRecord")?;
-        let mut reader = csv::Reader::from_reader(csv.as_bytes());
-        for row in reader.deserialize().flatten() {
-            data.hand_records.push(row);
-        }
+    // HandRecord table if available
+    if let Some(csv) = read("HandRecord")? {
+        data.hand_records = parse_table_rows("HandRecord", &csv, &mut warnings);
+    }
+
+    // BiddingData table if available (not all scorers record auctions)
+    if let Some(csv) = read("BiddingData")? {
+        data.biddings = parse_table_rows("BiddingData", &csv, &mut warnings);
+    }
+
+    for warning in &warnings {
+        log::warn!("{}", warning);
     }
+    data.warnings = warnings;
 
     // Convert hand records to boards if available
     data.boards = hand_records_to_boards(&data.hand_records);
@@ -251,19 +538,24 @@ fn parse_hand_from_bws(holdings: &[(Suit, Option<&str>)]) -> Hand {
 
 /// Parse a holding string and add cards to the hand
 fn add_cards_from_holding(hand: &mut Hand, suit: Suit, s: Option<&str>) {
-    let s = match s {
-        Some(s) if !s.is_empty() => s,
-        _ => return,
-    };
-
-    // Try parsing as PBN-style string first (AKQJT9876...)
-    for c in s.chars() {
-        if let Some(rank) = Rank::from_char(c) {
-            hand.add_card(Card::new(suit, rank));
-        }
+    for rank in parse_bws_holding(s.unwrap_or("")) {
+        hand.add_card(Card::new(suit, rank));
     }
 }
 
+/// Parse the ranks of a BWS suit holding
+///
+/// Accepts PBN-style concatenated ranks ("AKT98") as well as space- or
+/// comma-separated tokens ("A K Q", "10, 9, 8"), in any case. "10" is the
+/// ten; unrecognized characters are skipped.
+pub fn parse_bws_holding(s: &str) -> Vec<Rank> {
+    s.to_uppercase()
+        .replace("10", "T")
+        .chars()
+        .filter_map(Rank::from_char)
+        .collect()
+}
+
 /// Get unique board numbers from received data
 pub fn get_board_numbers(data: &BwsData) -> Vec<u32> {
     let mut boards: Vec<u32> = data.received_data.iter().map(|r| r.board as u32).collect();
@@ -290,4 +582,289 @@ mod tests {
         let result = check_mdbtools();
         assert!(result.is_ok(), "mdbtools should be installed");
     }
+
+    fn player_data() -> BwsData {
+        let seat = |table: i32, direction: &str, number: &str, name: &str| PlayerNumberRow {
+            section: 1,
+            table,
+            direction: direction.to_string(),
+            number: number.to_string(),
+            name: Some(name.to_string()),
+        };
+        let player = |id: i32, name: &str, str_id: &str| PlayerNameRow {
+            id,
+            name: name.to_string(),
+            str_id: str_id.to_string(),
+        };
+        BwsData {
+            player_numbers: vec![
+                seat(1, "N", "7", "Alice Smith"),
+                seat(1, "S", "", "bob jones"),
+                seat(1, "E", "99", "Carol White"),
+                seat(1, "W", "8", "Dan Brown"),
+            ],
+            player_names: vec![
+                player(7, "Alice Smith", "1234567"),
+                player(12, "Bob Jones", " 7654321 "),
+                player(8, "Dan Brown", ""),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_acbl_number_for() {
+        let data = player_data();
+        // Seat number is a local id matching PlayerNames.ID
+        assert_eq!(data.acbl_number_for(1, 1, "N"), Some("1234567"));
+        // No number keyed in; joined by name
+        assert_eq!(data.acbl_number_for(1, 1, "S"), Some("7654321"));
+        // Unknown player, blank strID, empty seat
+        assert_eq!(data.acbl_number_for(1, 1, "E"), None);
+        assert_eq!(data.acbl_number_for(1, 1, "W"), None);
+        assert_eq!(data.acbl_number_for(1, 2, "N"), None);
+    }
+
+    #[test]
+    fn test_parse_rows_with_quirks() {
+        // Remarks with an embedded comma and newline, a Windows-1252 name,
+        // and a row with a non-numeric board
+        let bytes = b"ID,Section,Table,Round,Board,PairNS,PairEW,Declarer,NS/EW,Contract,Result,LeadCard,Remarks\n\
+1,1,1,1,1,1,1,1,N,4S,=,SA,\"Director called, adjusted\nlater\"\n\
+2,1,1,1,x,1,1,1,N,4S,=,SA,\n"
+            .to_vec();
+        let csv = decode_export(bytes);
+        let mut warnings = Vec::new();
+        let rows: Vec<ReceivedDataRow> = parse_table_rows("ReceivedData", &csv, &mut warnings);
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(
+            rows[0].remarks.as_deref(),
+            Some("Director called, adjusted\nlater")
+        );
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("ReceivedData row 2 skipped"));
+
+        let names = decode_export(b"ID,Name,strID\n7,Jos\xe9 M\xfcller,1234567\n".to_vec());
+        let rows: Vec<PlayerNameRow> = parse_table_rows("PlayerNames", &names, &mut warnings);
+        assert_eq!(rows[0].name, "Jos\u{e9} M\u{fc}ller");
+    }
+
+    #[test]
+    fn test_decode_export() {
+        assert_eq!(decode_export("Café".as_bytes().to_vec()), "Café");
+        assert_eq!(decode_export(vec![0x93, b'x', 0x94]), "\u{201C}x\u{201D}");
+    }
+
+    /// HandRecord as exported by mdb-export from a Bridgemate BWS
+    const HAND_RECORD_CSV: &str = "\
+Section,Board,NorthSpades,NorthHearts,NorthDiamonds,NorthClubs,EastSpades,EastHearts,EastDiamonds,EastClubs,SouthSpades,SouthHearts,SouthDiamonds,SouthClubs,WestSpades,WestHearts,WestDiamonds,WestClubs
+1,6,AKQJ,AKQ,AKQ,AKQ,T987,JT9,JT9,JT9,6543,876,876,876,2,5432,5432,5432
+1,2,AKQJ,AKQ,AKQ,AKQ,T987,JT9,JT9,JT9,6543,876,876,876,2,5432,5432,5432
+";
+
+    #[test]
+    fn test_hand_records_use_board_rotation() {
+        let mut warnings = Vec::new();
+        let data = BwsData {
+            hand_records: parse_table_rows("HandRecord", HAND_RECORD_CSV, &mut warnings),
+            ..Default::default()
+        };
+        assert!(warnings.is_empty());
+        assert_eq!(data.hand_records.len(), 2);
+
+        let boards = hand_records_to_boards(&data.hand_records);
+        assert_eq!(boards[0].number, Some(2));
+        assert_eq!(boards[0].dealer, Some(Direction::East));
+        assert_eq!(boards[0].vulnerable, Vulnerability::from_board_number(2));
+        assert_eq!(boards[1].number, Some(6));
+        assert_eq!(boards[1].dealer, Some(Direction::East));
+        assert_eq!(boards[1].vulnerable, Vulnerability::from_board_number(6));
+    }
+
+    /// BiddingData as exported by mdb-export
+    const BIDDING_CSV: &str = "\
+ID,Section,Table,Round,Board,Counter,Direction,Bid,DateLog,TimeLog,Erased
+1,1,1,1,1,1,N,1NT,,,0
+2,1,1,1,1,2,E,PASS,,,0
+4,1,1,1,1,4,W,PASS,,,0
+3,1,1,1,1,3,S,3NT,,,0
+5,1,1,1,1,5,N,PASS,,,0
+6,1,1,1,1,6,E,x,,,1
+7,1,1,1,1,6,E,PASS,,,0
+8,1,2,1,1,1,N,PASS,,,0
+";
+
+    fn bidding_data() -> BwsData {
+        let mut reader = csv::Reader::from_reader(BIDDING_CSV.as_bytes());
+        BwsData {
+            biddings: reader.deserialize().flatten().collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_auction_for() {
+        let data = bidding_data();
+        assert_eq!(data.biddings.len(), 8);
+        assert_eq!(data.auction_count(), 2);
+
+        let auction = data.auction_for(1, 1, 1);
+        assert_eq!(auction.len(), 6);
+        assert_eq!(auction[0], Call::from_pbn("1NT").unwrap());
+        assert_eq!(auction[2], Call::from_pbn("3NT").unwrap());
+        assert!(auction[3..].iter().all(|c| *c == Call::Pass));
+
+        assert!(data.auction_for(1, 3, 1).is_empty());
+    }
+
+    #[test]
+    fn test_mitchell_pair_tracking() {
+        // Two-table Mitchell: EW pair 3 starts at table 1 and moves to table 2
+        let round = |round: i32, table: i32, ns_pair: i32, ew_pair: i32| RoundDataRow {
+            section: 1,
+            table,
+            round,
+            ns_pair,
+            ew_pair,
+            low_board: round * 2 - 1,
+            high_board: round * 2,
+        };
+        let mut data = player_data();
+        data.round_data = vec![round(1, 1, 1, 3), round(1, 2, 2, 4), round(2, 2, 2, 3)];
+
+        assert_eq!(data.starting_table(1, 3, false), 1);
+        assert_eq!(data.starting_table(1, 4, false), 2);
+        assert_eq!(data.starting_table(1, 2, true), 2);
+        assert_eq!(data.pair_at_start(1, 1, false), 3);
+        assert_eq!(data.pair_at_start(1, 1, true), 1);
+        assert_eq!(data.get_pair_player(1, 3, "E"), Some("Carol White"));
+        assert_eq!(data.get_pair_player(1, 1, "E"), None);
+
+        // Without RoundData the seating comes from the results
+        data.round_data.clear();
+        data.received_data = vec![ReceivedDataRow {
+            id: 1,
+            section: 1,
+            table: 2,
+            round: 2,
+            board: 3,
+            pair_ns: 2,
+            pair_ew: 3,
+            declarer: 0,
+            ns_ew: "N".to_string(),
+            contract: "4S".to_string(),
+            result: "=".to_string(),
+            lead_card: None,
+            remarks: None,
+        }];
+        assert_eq!(data.starting_table(1, 3, false), 2);
+        assert_eq!(data.starting_table(1, 5, false), 5);
+    }
+
+    #[test]
+    fn test_validate_results_against_hands() {
+        let deal =
+            Deal::from_pbn("N:K843.T542.J6.863 AQJ7.K.Q75.AT942 962.AJ7.KT82.J75 T5.Q9863.A943.KQ")
+                .unwrap();
+        let row = |table: i32, ns_ew: &str, lead: Option<&str>| ReceivedDataRow {
+            id: table,
+            section: 1,
+            table,
+            round: 1,
+            board: 1,
+            pair_ns: table,
+            pair_ew: table,
+            declarer: 0,
+            ns_ew: ns_ew.to_string(),
+            contract: "4S".to_string(),
+            result: "=".to_string(),
+            lead_card: lead.map(String::from),
+            remarks: None,
+        };
+        let data = BwsData {
+            boards: vec![Board::new().with_number(1).with_deal(deal)],
+            received_data: vec![
+                // East leads against North; West against South
+                row(1, "N", Some("HK")),
+                row(2, "S", Some("S10")),
+                row(3, "N", None),
+                // North can't lead against their own contract
+                row(4, "N", Some("SK")),
+                row(5, "N", Some("X9")),
+            ],
+            ..Default::default()
+        };
+
+        let issues = data.validate_results_against_hands();
+        assert_eq!(issues.len(), 2);
+        assert!(issues[0].starts_with("Board 1, section 1 table 4: lead SK is held by"));
+        assert!(issues[1].contains("table 5: unreadable lead card \"X9\""));
+
+        // No hand records, nothing to check against
+        let data = BwsData {
+            boards: Vec::new(),
+            ..data
+        };
+        assert!(data.validate_results_against_hands().is_empty());
+    }
+
+    #[test]
+    fn test_parse_bws_holding() {
+        let expected = vec![Rank::Ten, Rank::Nine, Rank::Eight, Rank::Two];
+        assert_eq!(parse_bws_holding("10 9 8 2"), expected);
+        assert_eq!(parse_bws_holding("10,9,8,2"), expected);
+        assert_eq!(parse_bws_holding("T982"), expected);
+        assert_eq!(
+            parse_bws_holding("a k 10"),
+            vec![Rank::Ace, Rank::King, Rank::Ten]
+        );
+        assert!(parse_bws_holding("").is_empty());
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut data = player_data();
+        data.sections = vec![SectionRow {
+            id: 1,
+            letter: "A ".to_string(),
+            tables: 2,
+            missing_pair: 0,
+            ew_move_before_play: None,
+            session: Some(1),
+            scoring_type: Some(1),
+            winners: Some(2),
+        }];
+        let result = |board: i32| ReceivedDataRow {
+            id: board,
+            section: 1,
+            table: 1,
+            round: 1,
+            board,
+            pair_ns: 1,
+            pair_ew: 3,
+            declarer: 0,
+            ns_ew: "N".to_string(),
+            contract: "4S".to_string(),
+            result: "=".to_string(),
+            lead_card: None,
+            remarks: None,
+        };
+        data.received_data = vec![result(2), result(1), result(2)];
+
+        let summary = data.summary();
+        assert_eq!(
+            summary.sections,
+            vec![SectionSummary {
+                letter: "A".to_string(),
+                tables: 2,
+                scoring: Some("IMPs"),
+            }]
+        );
+        assert_eq!(summary.players, data.player_names.len());
+        assert_eq!(summary.results, 3);
+        assert_eq!(summary.boards, vec![1, 2]);
+        assert_eq!(summary.hand_records, 0);
+        assert_eq!(summary.auctions, 0);
+    }
 }
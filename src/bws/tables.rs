@@ -109,3 +109,20 @@ pub struct RoundDataRow {
     pub low_board: i32,
     pub high_board: i32,
 }
+
+/// A single call from the BiddingData table (if available)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct BiddingRow {
+    pub section: i32,
+    pub table: i32,
+    pub round: i32,
+    pub board: i32,
+    /// Position of the call within the auction, starting at 1
+    pub counter: i32,
+    pub direction: String,
+    #[serde(rename = "Bid")]
+    pub call: String,
+    /// Non-zero when the call was corrected at the table
+    pub erased: Option<i32>,
+}
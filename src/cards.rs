@@ -0,0 +1,152 @@
+//! Iteration, index and text helpers for cards
+//!
+//! `Card`, `Rank` and `Suit` come from bridge-types; these free functions
+//! give the rest of the crate one canonical deck order and card index
+//! instead of repeating nested suit/rank loops, and one way to write and
+//! read a single card.
+
+use crate::{Card, Rank, Suit};
+
+/// All 52 cards in canonical order: suits in `Suit::ALL` order, and within
+/// each suit ranks in `Rank::ALL` order
+pub fn deck() -> impl Iterator<Item = Card> {
+    Suit::ALL
+        .into_iter()
+        .flat_map(|suit| Rank::ALL.into_iter().map(move |rank| Card::new(suit, rank)))
+}
+
+/// Position of a rank in `Rank::ALL` (0..13)
+pub fn rank_index(rank: Rank) -> usize {
+    Rank::ALL.iter().position(|&r| r == rank).unwrap_or(0)
+}
+
+/// Rank at a position in `Rank::ALL`
+pub fn rank_from_index(index: usize) -> Option<Rank> {
+    Rank::ALL.get(index).copied()
+}
+
+/// Position of a card in [`deck`] order (0..52)
+pub fn card_index(card: Card) -> usize {
+    let suit = Suit::ALL.iter().position(|&s| s == card.suit).unwrap_or(0);
+    suit * Rank::ALL.len() + rank_index(card.rank)
+}
+
+/// Card at a position in [`deck`] order
+pub fn card_from_index(index: usize) -> Option<Card> {
+    let suit = Suit::ALL.get(index / Rank::ALL.len())?;
+    let rank = rank_from_index(index % Rank::ALL.len())?;
+    Some(Card::new(*suit, rank))
+}
+
+/// A card in PBN form, suit letter then rank: "SA", "HT"
+pub fn card_to_pbn(card: Card) -> String {
+    format!("{}{}", card.suit.to_char(), card.rank.to_char())
+}
+
+/// A card with a suit symbol then rank: "♠A", "♥T"
+pub fn card_to_unicode(card: Card) -> String {
+    format!("{}{}", suit_symbol(card.suit), card.rank.to_char())
+}
+
+/// Unicode symbol for a suit
+pub fn suit_symbol(suit: Suit) -> char {
+    match suit {
+        Suit::Spades => '♠',
+        Suit::Hearts => '♥',
+        Suit::Diamonds => '♦',
+        Suit::Clubs => '♣',
+    }
+}
+
+/// Parse a card written as suit then rank
+///
+/// The suit may be a letter (either case) or a symbol, filled or outline
+/// ("SA", "sa", "♠A", "♤A"), and the ten may be "T" or "10".
+pub fn parse_card(s: &str) -> Option<Card> {
+    let mut chars = s.trim().chars();
+    let suit = match chars.next()? {
+        '♠' | '♤' => Suit::Spades,
+        '♥' | '♡' => Suit::Hearts,
+        '♦' | '♢' => Suit::Diamonds,
+        '♣' | '♧' => Suit::Clubs,
+        c => Suit::from_char(c.to_ascii_uppercase())?,
+    };
+    let rank = match chars.as_str() {
+        "10" => Rank::Ten,
+        rank => {
+            let mut rank = rank.chars();
+            let c = rank.next()?;
+            if rank.next().is_some() {
+                return None;
+            }
+            Rank::from_char(c.to_ascii_uppercase())?
+        }
+    };
+    Some(Card::new(suit, rank))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_deck_has_52_unique_cards() {
+        let cards: Vec<Card> = deck().collect();
+        assert_eq!(cards.len(), 52);
+
+        let unique: HashSet<(usize, usize)> = cards
+            .iter()
+            .map(|c| (card_index(*c), rank_index(c.rank)))
+            .collect();
+        assert_eq!(unique.len(), 52);
+    }
+
+    #[test]
+    fn test_index_round_trip() {
+        for (i, card) in deck().enumerate() {
+            assert_eq!(card_index(card), i);
+            assert_eq!(card_from_index(i), Some(card));
+        }
+        assert_eq!(card_from_index(52), None);
+
+        for rank in Rank::ALL {
+            assert_eq!(rank_from_index(rank_index(rank)), Some(rank));
+        }
+        assert_eq!(rank_from_index(13), None);
+    }
+
+    #[test]
+    fn test_card_text_round_trip() {
+        for card in deck() {
+            assert_eq!(parse_card(&card_to_pbn(card)), Some(card));
+            assert_eq!(parse_card(&card_to_unicode(card)), Some(card));
+        }
+    }
+
+    #[test]
+    fn test_card_text_forms() {
+        let cases = [
+            (Suit::Spades, "SA", "♠A"),
+            (Suit::Hearts, "HA", "♥A"),
+            (Suit::Diamonds, "DA", "♦A"),
+            (Suit::Clubs, "CA", "♣A"),
+        ];
+        for (suit, pbn, unicode) in cases {
+            let card = Card::new(suit, Rank::Ace);
+            assert_eq!(card_to_pbn(card), pbn);
+            assert_eq!(card_to_unicode(card), unicode);
+        }
+
+        let ten = Card::new(Suit::Hearts, Rank::Ten);
+        assert_eq!(card_to_pbn(ten), "HT");
+        assert_eq!(card_to_unicode(ten), "♥T");
+        for text in ["HT", "ht", "H10", "♥T", "♥10", "♡10"] {
+            assert_eq!(parse_card(text), Some(ten), "{}", text);
+        }
+
+        assert_eq!(parse_card("S"), None);
+        assert_eq!(parse_card("SAK"), None);
+        assert_eq!(parse_card("XA"), None);
+    }
+}
@@ -0,0 +1,553 @@
+//! Helpers for rotating, comparing and renumbering deals
+//!
+//! `Deal` and `Board` come from bridge-types; these free functions relabel
+//! seats, compare deals, and keep a board's number, dealer and vulnerability
+//! consistent.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::holding::high_card_points;
+use crate::{
+    dealer_from_board_number, Board, BridgeError, Card, Deal, Direction, Hand, Rank, Result, Suit,
+    Vulnerability,
+};
+
+/// Rotate a deal clockwise by `by` quarter-turns
+///
+/// With `by == 1`, North's cards move to East, East's to South, and so on.
+/// Rotating by 4 (or any multiple) returns the same deal.
+pub fn rotate_deal(deal: &Deal, by: u8) -> Deal {
+    let mut rotated = Deal::new();
+    for dir in Direction::ALL {
+        rotated.set_hand(crate::seat::rotate(dir, by), deal.hand(dir).clone());
+    }
+    rotated
+}
+
+/// A stable key identifying the cards held together, whoever holds them
+///
+/// The deal is rotated so the hand holding the spade ace sits North, then
+/// written as PBN starting from North. Two deals get equal keys when they
+/// are the same four hands, even if dealt from different seats. A deal
+/// without the spade ace (e.g. an empty hand record) is keyed unrotated.
+pub fn canonical_key(deal: &Deal) -> String {
+    let spade_ace = Card::new(Suit::Spades, Rank::Ace);
+    let by = Direction::ALL
+        .into_iter()
+        .find(|&d| deal.hand(d).has_card(spade_ace))
+        .map_or(0, |seat| (4 - crate::seat::seat_index(seat)) % 4);
+    rotate_deal(deal, by as u8).to_pbn(Direction::North)
+}
+
+/// Whether two boards hold the same physical deal (see [`canonical_key`])
+pub fn is_same_deal(a: &Board, b: &Board) -> bool {
+    canonical_key(&a.deal) == canonical_key(&b.deal)
+}
+
+/// High-card points (A=4, K=3, Q=2, J=1) held by each partnership, as (NS, EW)
+pub fn partnership_hcp(deal: &Deal) -> (u8, u8) {
+    let hcp = |dir: Direction| high_card_points(deal.hand(dir));
+    (
+        hcp(Direction::North) + hcp(Direction::South),
+        hcp(Direction::East) + hcp(Direction::West),
+    )
+}
+
+/// Longest combined suit for the partnership of `seat`, and its length
+///
+/// Equal lengths go to the higher-ranking suit.
+pub fn best_fit(deal: &Deal, seat: Direction) -> (Suit, usize) {
+    let partner = crate::seat::partner(seat);
+    [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs]
+        .into_iter()
+        .map(|suit| {
+            let len = deal.hand(seat).suit_length(suit) + deal.hand(partner).suit_length(suit);
+            (suit, usize::from(len))
+        })
+        .fold(
+            (Suit::Spades, 0),
+            |best, fit| {
+                if fit.1 > best.1 {
+                    fit
+                } else {
+                    best
+                }
+            },
+        )
+}
+
+/// Give a board a new number, updating dealer and vulnerability to match
+///
+/// Dealer and vulnerability follow the standard rotation for `number`.
+pub fn renumber_board(board: &mut Board, number: u32) {
+    board.number = Some(number);
+    board.dealer = Some(dealer_from_board_number(number));
+    board.vulnerable = Vulnerability::from_board_number(number);
+}
+
+/// Set a board's dealer, or with `None` follow the board-number rotation
+///
+/// An unnumbered board keeps its dealer when rotating.
+pub fn set_dealer(board: &mut Board, dealer: Option<Direction>) {
+    if let Some(dealer) = dealer.or_else(|| board.number.map(dealer_from_board_number)) {
+        board.dealer = Some(dealer);
+    }
+}
+
+/// Set a board's vulnerability, or with `None` follow the board-number
+/// rotation
+///
+/// An unnumbered board keeps its vulnerability when rotating.
+pub fn set_vulnerability(board: &mut Board, vulnerable: Option<Vulnerability>) {
+    if let Some(vulnerable) =
+        vulnerable.or_else(|| board.number.map(Vulnerability::from_board_number))
+    {
+        board.vulnerable = vulnerable;
+    }
+}
+
+/// Deal the cards deterministically from a seed string
+///
+/// The same seed gives the same deal on every run and platform, so a short
+/// string is enough to share or reproduce a practice set. The algorithm is
+/// fixed and must not change:
+///
+/// 1. Hash the seed's UTF-8 bytes with 64-bit FNV-1a (offset basis
+///    `0xcbf29ce484222325`, prime `0x100000001b3`).
+/// 2. Use the hash as the state of a SplitMix64 generator.
+/// 3. Number the cards 0..52 as ♠A..♠2, ♥A..♥2, ♦A..♦2, ♣A..♣2 and shuffle
+///    them with Fisher-Yates: for `i` from 51 down to 1, swap card `i` with
+///    card `next() % (i + 1)`.
+/// 4. Deal the first 13 shuffled cards to North, the next 13 to East, then
+///    South, then West.
+pub fn deal_from_seed(seed: &str) -> Deal {
+    const SUITS: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+    const RANKS: [Rank; 13] = [
+        Rank::Ace,
+        Rank::King,
+        Rank::Queen,
+        Rank::Jack,
+        Rank::Ten,
+        Rank::Nine,
+        Rank::Eight,
+        Rank::Seven,
+        Rank::Six,
+        Rank::Five,
+        Rank::Four,
+        Rank::Three,
+        Rank::Two,
+    ];
+
+    let mut state = seed.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    let mut next = || {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    };
+
+    let mut cards: Vec<Card> = SUITS
+        .iter()
+        .flat_map(|&suit| RANKS.iter().map(move |&rank| Card::new(suit, rank)))
+        .collect();
+    for i in (1..cards.len()).rev() {
+        let j = (next() % (i as u64 + 1)) as usize;
+        cards.swap(i, j);
+    }
+
+    let mut deal = Deal::new();
+    for (dir, hand_cards) in Direction::ALL.into_iter().zip(cards.chunks(13)) {
+        let mut hand = Hand::new();
+        for &card in hand_cards {
+            hand.add_card(card);
+        }
+        deal.set_hand(dir, hand);
+    }
+    deal
+}
+
+/// Remove a card from a hand, returning whether it was held
+pub fn remove_card(hand: &mut Hand, card: Card) -> bool {
+    if !hand.has_card(card) {
+        return false;
+    }
+    let mut rest = Hand::new();
+    for &c in hand.cards().iter().filter(|&&c| c != card) {
+        rest.add_card(c);
+    }
+    *hand = rest;
+    true
+}
+
+/// Play a card from a seat's hand, removing it from the deal
+///
+/// Fails without changing the deal if the seat doesn't hold the card. Suit
+/// following isn't checked here; see [`crate::validate::validate_play`].
+pub fn play_card(deal: &mut Deal, direction: Direction, card: Card) -> Result<()> {
+    let mut hand = deal.hand(direction).clone();
+    if !remove_card(&mut hand, card) {
+        return Err(BridgeError::InvalidDeal(format!(
+            "{} does not hold {}",
+            direction_name(direction),
+            crate::cards::card_to_pbn(card)
+        )));
+    }
+    deal.set_hand(direction, hand);
+    Ok(())
+}
+
+/// A difference between two sets of boards, found by [`diff_boards`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoardDiff {
+    /// The board number appears only in the first set
+    OnlyInLeft(u32),
+    /// The board number appears only in the second set
+    OnlyInRight(u32),
+    /// One seat holds different cards in a suit
+    Holding {
+        board: u32,
+        direction: Direction,
+        suit: Suit,
+        /// Holding in the first set, e.g. "SAKQ"
+        left: String,
+        /// Holding in the second set
+        right: String,
+    },
+}
+
+impl fmt::Display for BoardDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoardDiff::OnlyInLeft(board) => write!(f, "Board {}: missing from other", board),
+            BoardDiff::OnlyInRight(board) => write!(f, "Board {}: only in other", board),
+            BoardDiff::Holding {
+                board,
+                direction,
+                left,
+                right,
+                ..
+            } => write!(
+                f,
+                "Board {} {}: has {}, other has {}",
+                board,
+                direction_name(*direction),
+                left,
+                right
+            ),
+        }
+    }
+}
+
+/// Compare two sets of boards, matching them by board number
+///
+/// For each board number in both sets, every seat's holding is compared
+/// suit by suit. Boards without a number are ignored; if a number appears
+/// more than once in a set, the first board is used. Differences are
+/// returned in board number order.
+pub fn diff_boards(a: &[Board], b: &[Board]) -> Vec<BoardDiff> {
+    let left = boards_by_number(a);
+    let right = boards_by_number(b);
+    let mut diffs = Vec::new();
+
+    for (&number, left_board) in &left {
+        let Some(right_board) = right.get(&number) else {
+            diffs.push(BoardDiff::OnlyInLeft(number));
+            continue;
+        };
+        for direction in Direction::ALL {
+            let left_hand = left_board.deal.hand(direction);
+            let right_hand = right_board.deal.hand(direction);
+            for suit in [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs] {
+                let left_holding = holding(left_hand, suit);
+                let right_holding = holding(right_hand, suit);
+                if left_holding != right_holding {
+                    diffs.push(BoardDiff::Holding {
+                        board: number,
+                        direction,
+                        suit,
+                        left: left_holding,
+                        right: right_holding,
+                    });
+                }
+            }
+        }
+    }
+    for &number in right.keys() {
+        if !left.contains_key(&number) {
+            diffs.push(BoardDiff::OnlyInRight(number));
+        }
+    }
+
+    diffs.sort_by_key(|d| match d {
+        BoardDiff::OnlyInLeft(n) | BoardDiff::OnlyInRight(n) => *n,
+        BoardDiff::Holding { board, .. } => *board,
+    });
+    diffs
+}
+
+/// Boards keyed by number, keeping the first of any duplicates
+fn boards_by_number(boards: &[Board]) -> BTreeMap<u32, &Board> {
+    let mut by_number = BTreeMap::new();
+    for board in boards {
+        if let Some(number) = board.number {
+            by_number.entry(number).or_insert(board);
+        }
+    }
+    by_number
+}
+
+/// A suit holding as the suit letter and ranks highest first, e.g. "SAKQ"
+/// (just "S" for a void)
+fn holding(hand: &Hand, suit: Suit) -> String {
+    let mut ranks: Vec<Rank> = hand
+        .cards()
+        .iter()
+        .filter(|c| c.suit == suit)
+        .map(|c| c.rank)
+        .collect();
+    ranks.sort_by(|a, b| b.cmp(a));
+    std::iter::once(suit.to_char())
+        .chain(ranks.iter().map(|r| r.to_char()))
+        .collect()
+}
+
+fn direction_name(direction: Direction) -> &'static str {
+    match direction {
+        Direction::North => "North",
+        Direction::East => "East",
+        Direction::South => "South",
+        Direction::West => "West",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEAL: &str = "N:K843.T542.J6.863 AQJ7.K.Q75.AT942 962.AJ7.KT82.J75 T5.Q9863.A943.KQ";
+
+    #[test]
+    fn test_rotate_by_four_is_identity() {
+        let deal = Deal::from_pbn(DEAL).unwrap();
+        let rotated = rotate_deal(&deal, 4);
+        assert_eq!(
+            rotated.to_pbn(Direction::North),
+            deal.to_pbn(Direction::North)
+        );
+
+        let rotated = (0..4).fold(deal.clone(), |d, _| rotate_deal(&d, 1));
+        assert_eq!(
+            rotated.to_pbn(Direction::North),
+            deal.to_pbn(Direction::North)
+        );
+    }
+
+    #[test]
+    fn test_rotate_moves_hcp_with_seats() {
+        let board = Board::new().with_deal(Deal::from_pbn(DEAL).unwrap());
+        let rotated = Board::new().with_deal(rotate_deal(&board.deal, 1));
+
+        // N, E, S, W: North's 4 HCP now sit East
+        assert_eq!(board.all_hcp(), [4, 16, 9, 11]);
+        assert_eq!(rotated.all_hcp(), [11, 4, 16, 9]);
+
+        let rotated = Board::new().with_deal(rotate_deal(&board.deal, 2));
+        assert_eq!(rotated.all_hcp(), [9, 11, 4, 16]);
+    }
+
+    #[test]
+    fn test_deal_from_seed_is_reproducible() {
+        let deal = deal_from_seed("practice-1");
+        assert_eq!(
+            deal.to_pbn(Direction::North),
+            deal_from_seed("practice-1").to_pbn(Direction::North)
+        );
+        for dir in Direction::ALL {
+            assert_eq!(deal.hand(dir).len(), 13);
+        }
+
+        // Pinned so the algorithm can't drift between versions or platforms
+        let expected =
+            Deal::from_pbn("N:AKT5.AQJ6.A9.Q98 82.T95.K873.KJT3 QJ94.K43.Q642.65 763.872.JT5.A742")
+                .unwrap();
+        assert_eq!(
+            deal.to_pbn(Direction::North),
+            expected.to_pbn(Direction::North)
+        );
+    }
+
+    #[test]
+    fn test_deal_from_seed_differs_by_seed() {
+        assert_ne!(
+            deal_from_seed("practice-1").to_pbn(Direction::North),
+            deal_from_seed("practice-2").to_pbn(Direction::North)
+        );
+    }
+
+    #[test]
+    fn test_renumber_board() {
+        let mut board = Board::new().with_number(1);
+        renumber_board(&mut board, 6);
+        assert_eq!(board.number, Some(6));
+        assert_eq!(board.dealer, Some(Direction::East));
+        assert_eq!(board.vulnerable, Vulnerability::from_board_number(6));
+    }
+
+    #[test]
+    fn test_set_dealer_and_vulnerability() {
+        let mut board = Board::new()
+            .with_number(6)
+            .with_dealer(Direction::North)
+            .with_vulnerability(Vulnerability::None);
+        set_dealer(&mut board, None);
+        set_vulnerability(&mut board, None);
+        assert_eq!(board.dealer, Some(Direction::East));
+        assert_eq!(board.vulnerable, Vulnerability::from_board_number(6));
+
+        set_dealer(&mut board, Some(Direction::West));
+        set_vulnerability(&mut board, Some(Vulnerability::None));
+        assert_eq!(board.dealer, Some(Direction::West));
+        assert_eq!(board.vulnerable, Vulnerability::None);
+
+        // Rotation needs a board number
+        let mut board = Board::new().with_vulnerability(Vulnerability::Both);
+        set_vulnerability(&mut board, None);
+        assert_eq!(board.vulnerable, Vulnerability::Both);
+    }
+
+    #[test]
+    fn test_canonical_key_ignores_first_direction() {
+        let from_north = Deal::from_pbn(DEAL).unwrap();
+        let from_east =
+            Deal::from_pbn("E:AQJ7.K.Q75.AT942 962.AJ7.KT82.J75 T5.Q9863.A943.KQ K843.T542.J6.863")
+                .unwrap();
+        assert_eq!(canonical_key(&from_north), canonical_key(&from_east));
+
+        // East holds the spade ace, so the key starts with East's hand
+        assert!(canonical_key(&from_north).starts_with("N:AQJ7.K.Q75.AT942 "));
+    }
+
+    #[test]
+    fn test_is_same_deal() {
+        let deal = Deal::from_pbn(DEAL).unwrap();
+        let board = Board::new().with_deal(deal.clone());
+        let rotated = Board::new().with_deal(rotate_deal(&deal, 3));
+        assert!(is_same_deal(&board, &rotated));
+
+        let other = Board::new().with_deal(
+            Deal::from_pbn("N:A843.T542.J6.863 KQJ7.K.Q75.AT942 962.AJ7.KT82.J75 T5.Q9863.A943.KQ")
+                .unwrap(),
+        );
+        // North and East have swapped the spade ace and king
+        assert!(!is_same_deal(&board, &other));
+        assert!(!is_same_deal(&board, &Board::new()));
+    }
+
+    #[test]
+    fn test_diff_boards_one_card() {
+        let left = vec![
+            Board::new()
+                .with_number(1)
+                .with_deal(Deal::from_pbn(DEAL).unwrap()),
+            Board::new()
+                .with_number(7)
+                .with_deal(Deal::from_pbn(DEAL).unwrap()),
+        ];
+        // Board 7: North's spade king and East's spade ace swapped
+        let right = vec![
+            Board::new()
+                .with_number(1)
+                .with_deal(Deal::from_pbn(DEAL).unwrap()),
+            Board::new().with_number(7).with_deal(
+                Deal::from_pbn(
+                    "N:A843.T542.J6.863 KQJ7.K.Q75.AT942 962.AJ7.KT82.J75 T5.Q9863.A943.KQ",
+                )
+                .unwrap(),
+            ),
+        ];
+
+        let diffs = diff_boards(&left, &right);
+        assert_eq!(diffs.len(), 2);
+        assert_eq!(
+            diffs[0].to_string(),
+            "Board 7 North: has SK843, other has SA843"
+        );
+        assert_eq!(
+            diffs[1].to_string(),
+            "Board 7 East: has SAQJ7, other has SKQJ7"
+        );
+        assert!(diff_boards(&left, &left).is_empty());
+    }
+
+    #[test]
+    fn test_diff_boards_unmatched_numbers() {
+        let left = vec![Board::new().with_number(1), Board::new().with_number(2)];
+        let right = vec![Board::new().with_number(2), Board::new().with_number(3)];
+        assert_eq!(
+            diff_boards(&left, &right),
+            vec![BoardDiff::OnlyInLeft(1), BoardDiff::OnlyInRight(3)]
+        );
+    }
+
+    #[test]
+    fn test_remove_card() {
+        let mut deal = Deal::from_pbn(DEAL).unwrap();
+        let mut hand = deal.hand(Direction::North).clone();
+        let king = Card::new(Suit::Spades, Rank::King);
+        assert!(remove_card(&mut hand, king));
+        assert!(!hand.has_card(king));
+        assert_eq!(hand.cards().len(), 12);
+        assert!(!remove_card(&mut hand, king));
+        assert_eq!(hand.cards().len(), 12);
+
+        let ace = Card::new(Suit::Spades, Rank::Ace);
+        assert!(play_card(&mut deal, Direction::North, ace).is_err());
+        assert_eq!(deal.hand(Direction::North).cards().len(), 13);
+    }
+
+    #[test]
+    fn test_play_full_trick() {
+        let mut deal = Deal::from_pbn(DEAL).unwrap();
+        let trick = [
+            (Direction::West, Card::new(Suit::Hearts, Rank::Queen)),
+            (Direction::North, Card::new(Suit::Hearts, Rank::Two)),
+            (Direction::East, Card::new(Suit::Hearts, Rank::King)),
+            (Direction::South, Card::new(Suit::Hearts, Rank::Ace)),
+        ];
+        for (seat, card) in trick {
+            play_card(&mut deal, seat, card).unwrap();
+        }
+        for dir in Direction::ALL {
+            assert_eq!(deal.hand(dir).cards().len(), 12);
+        }
+        assert!(!deal
+            .hand(Direction::South)
+            .has_card(Card::new(Suit::Hearts, Rank::Ace)));
+    }
+
+    #[test]
+    fn test_partnership_hcp() {
+        let deal = Deal::from_pbn(DEAL).unwrap();
+        // N 4, E 16, S 9, W 11
+        assert_eq!(partnership_hcp(&deal), (13, 27));
+    }
+
+    #[test]
+    fn test_best_fit() {
+        let deal = Deal::from_pbn(DEAL).unwrap();
+        // NS: spades 4+3, hearts 4+3, diamonds 2+4, clubs 3+3
+        assert_eq!(best_fit(&deal, Direction::North), (Suit::Spades, 7));
+        // EW: spades 4+2, hearts 1+5, diamonds 3+4, clubs 5+2
+        assert_eq!(best_fit(&deal, Direction::West), (Suit::Diamonds, 7));
+
+        // North and South hold nine hearts between them
+        let deal =
+            Deal::from_pbn("N:K84.AQ542.J6.863 AQJ7.K.Q75.AT942 962.JT73.K82.J75 T53.986.AT943.KQ")
+                .unwrap();
+        assert_eq!(best_fit(&deal, Direction::South), (Suit::Hearts, 9));
+    }
+}
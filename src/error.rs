@@ -29,6 +29,9 @@ pub enum BridgeError {
     #[error("LIN format error: {0}")]
     Lin(String),
 
+    #[error("HTTP client error: {0}")]
+    Http(String),
+
     #[error("URL resolution error: {0}")]
     UrlResolution(String),
 
@@ -41,8 +44,15 @@ pub enum BridgeError {
     #[error("CSV error: {0}")]
     Csv(#[from] csv::Error),
 
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
     #[error("Excel error: {0}")]
     Excel(#[from] rust_xlsxwriter::XlsxError),
+
+    #[cfg(feature = "ods")]
+    #[error("ODS error: {0}")]
+    Ods(#[from] zip::result::ZipError),
 }
 
 pub type Result<T> = std::result::Result<T, BridgeError>;
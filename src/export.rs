@@ -0,0 +1,218 @@
+//! CSV export mirroring the Excel sheets
+//!
+//! The columns match the xlsx Hand Records and combined Game Results sheets,
+//! and scores/matchpoints come from the same computations.
+
+use crate::error::Result;
+use crate::scoring::calculate_score_for_result;
+use crate::xlsx::writer::{
+    calculate_all_matchpoints, format_hand_compact, sort_results_by_board_and_score,
+    COMBINED_RESULT_HEADERS, HAND_RECORD_HEADERS,
+};
+use crate::{Board, Direction};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+
+/// Write boards to a CSV file with the Hand Records sheet columns
+pub fn write_boards_to_csv(boards: &[Board], path: &Path) -> Result<()> {
+    let file = std::fs::File::create(path)?;
+    write_boards_csv(boards, file)
+}
+
+/// Write boards as CSV to any writer
+pub fn write_boards_csv<W: Write>(boards: &[Board], writer: W) -> Result<()> {
+    let mut csv = csv::Writer::from_writer(writer);
+    csv.write_record(HAND_RECORD_HEADERS)?;
+    for record in board_records(boards) {
+        csv.write_record(&record)?;
+    }
+
+    csv.flush()?;
+    Ok(())
+}
+
+/// One row of Hand Records sheet values per board, as text
+pub(crate) fn board_records(boards: &[Board]) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    for board in boards {
+        let hcp = board.all_hcp();
+        let mut record = vec![
+            board.number.map(|n| n.to_string()).unwrap_or_default(),
+            board
+                .dealer
+                .map(|d| d.to_char().to_string())
+                .unwrap_or_default(),
+            board.vulnerable.to_pbn(),
+        ];
+        for dir in [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ] {
+            record.push(format_hand_compact(board.deal.hand(dir)));
+        }
+        record.extend(hcp.iter().map(|h| h.to_string()));
+        record.push(board.double_dummy_tricks.clone().unwrap_or_default());
+        record.push(board.optimum_score.clone().unwrap_or_default());
+        record.push(board.par_contract.clone().unwrap_or_default());
+        record.push(board.contract.clone().unwrap_or_default());
+        record.push(
+            board
+                .declarer
+                .map(|d| d.to_char().to_string())
+                .unwrap_or_default(),
+        );
+        record.push(board.result.map(|r| r.to_string()).unwrap_or_default());
+        record.push(crate::lin::board_to_handviewer_url(board).unwrap_or_default());
+
+        records.push(record);
+    }
+    records
+}
+
+/// Write combined PBN (deals) and BWS (scores) data to a CSV file
+///
+/// Rows and columns match the combined Game Results sheet: sorted by board,
+/// then NS score descending.
+pub fn write_combined_to_csv(
+    boards: &[Board],
+    bws_data: &crate::bws::BwsData,
+    path: &Path,
+) -> Result<()> {
+    let file = std::fs::File::create(path)?;
+    write_combined_csv(boards, bws_data, file)
+}
+
+/// Write combined results as CSV to any writer
+pub fn write_combined_csv<W: Write>(
+    boards: &[Board],
+    data: &crate::bws::BwsData,
+    writer: W,
+) -> Result<()> {
+    let mut csv = csv::Writer::from_writer(writer);
+    csv.write_record(COMBINED_RESULT_HEADERS)?;
+    for record in combined_records(boards, data) {
+        csv.write_record(&record)?;
+    }
+
+    csv.flush()?;
+    Ok(())
+}
+
+/// One row of combined Game Results values per result, as text
+///
+/// Sorted by board, then NS score descending.
+pub(crate) fn combined_records(boards: &[Board], data: &crate::bws::BwsData) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let board_map: HashMap<u32, &Board> = boards
+        .iter()
+        .filter_map(|b| b.number.map(|n| (n, b)))
+        .collect();
+
+    let (matchpoints, _) = calculate_all_matchpoints(data);
+    let scores: Vec<Option<i32>> = data
+        .received_data
+        .iter()
+        .map(calculate_score_for_result)
+        .collect();
+
+    for idx in sort_results_by_board_and_score(data, &scores) {
+        let result = &data.received_data[idx];
+        let name = |pair: i32, dir: &str| {
+            data.get_pair_player(result.section, pair, dir)
+                .unwrap_or_default()
+                .to_string()
+        };
+
+        let declarer_dir = match result.ns_ew.as_str() {
+            "N" => "North",
+            "S" => "South",
+            "E" => "East",
+            "W" => "West",
+            _ => &result.ns_ew,
+        };
+
+        let mut record = vec![
+            result.board.to_string(),
+            result.section.to_string(),
+            result.table.to_string(),
+            result.round.to_string(),
+            result.pair_ns.to_string(),
+            result.pair_ew.to_string(),
+            name(result.pair_ns, "N"),
+            name(result.pair_ew, "E"),
+            name(result.pair_ns, "S"),
+            name(result.pair_ew, "W"),
+            declarer_dir.to_string(),
+            result.contract.clone(),
+            result.result.clone(),
+            result.lead_card.clone().unwrap_or_default(),
+            scores[idx].map(|s| s.to_string()).unwrap_or_default(),
+            matchpoints[idx]
+                .map(|mp| format!("{:.1}", mp.ns))
+                .unwrap_or_default(),
+            matchpoints[idx]
+                .map(|mp| format!("{:.1}", mp.ew))
+                .unwrap_or_default(),
+        ];
+
+        match board_map.get(&(result.board as u32)) {
+            Some(board) => {
+                record.push(board.vulnerable.to_pbn());
+                for dir in [
+                    Direction::North,
+                    Direction::East,
+                    Direction::South,
+                    Direction::West,
+                ] {
+                    let hand = board.deal.hand(dir);
+                    if hand.is_empty() {
+                        record.push(String::new());
+                    } else {
+                        record.push(format_hand_compact(hand));
+                    }
+                }
+            }
+            None => record.extend(std::iter::repeat_n(String::new(), 5)),
+        }
+
+        records.push(record);
+    }
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Deal, Vulnerability};
+
+    #[test]
+    fn test_write_boards_csv() {
+        let deal =
+            Deal::from_pbn("N:K843.T542.J6.863 AQJ7.K.Q75.AT942 962.AJ7.KT82.J75 T5.Q9863.A943.KQ")
+                .unwrap();
+        let boards = vec![Board::new()
+            .with_number(1)
+            .with_dealer(Direction::North)
+            .with_vulnerability(Vulnerability::None)
+            .with_deal(deal)];
+
+        let mut buf = Vec::new();
+        write_boards_csv(&boards, &mut buf).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+        let mut lines = output.lines();
+
+        assert_eq!(
+            lines.next().unwrap(),
+            "Board,Dealer,Vul,North,East,South,West,N HCP,E HCP,S HCP,W HCP,DD Tricks,Optimum,Par,Contract,Declarer,Result,Url"
+        );
+        let row = lines.next().unwrap();
+        assert!(row.starts_with(
+            "1,N,None,SK843 HT542 DJ6 C863,SAQJ7 HK DQ75 CAT942,S962 HAJ7 DKT82 CJ75,ST5 HQ9863 DA943 CKQ,4,16,9,11,,,,"
+        ));
+        assert!(row.ends_with(&crate::lin::board_to_handviewer_url(&boards[0]).unwrap()));
+        assert!(lines.next().is_none());
+    }
+}
@@ -0,0 +1,135 @@
+//! Selecting a subset of boards by number
+
+use crate::error::{BridgeError, Result};
+use crate::Board;
+use std::collections::BTreeSet;
+
+/// Most boards a single `start-end` range may select
+pub const MAX_RANGE_BOARDS: u32 = 10_000;
+
+/// Parse a board selection such as `5-8,12` into the set of board numbers
+///
+/// Items are single numbers or inclusive `start-end` ranges separated by
+/// commas. Open or reversed ranges (`5-`, `8-5`) are errors, as are ranges
+/// spanning more than [`MAX_RANGE_BOARDS`] boards.
+pub fn parse_board_ranges(spec: &str) -> Result<BTreeSet<u32>> {
+    let invalid = |item: &str| BridgeError::Parse(format!("Invalid board range: '{}'", item));
+    let number = |s: &str, item: &str| s.trim().parse::<u32>().map_err(|_| invalid(item));
+
+    let mut boards = BTreeSet::new();
+    for item in spec.split(',') {
+        let item = item.trim();
+        if item.is_empty() {
+            return Err(invalid(item));
+        }
+        match item.split_once('-') {
+            Some((start, end)) => {
+                let start = number(start, item)?;
+                let end = number(end, item)?;
+                if start > end {
+                    return Err(invalid(item));
+                }
+                if end - start >= MAX_RANGE_BOARDS {
+                    return Err(BridgeError::Parse(format!(
+                        "Board range '{}' spans more than {} boards",
+                        item, MAX_RANGE_BOARDS
+                    )));
+                }
+                boards.extend(start..=end);
+            }
+            None => {
+                boards.insert(number(item, item)?);
+            }
+        }
+    }
+
+    Ok(boards)
+}
+
+/// Whether a board's number is selected; unnumbered boards never are
+pub fn is_selected(board: &Board, selected: &BTreeSet<u32>) -> bool {
+    board.number.is_some_and(|n| selected.contains(&n))
+}
+
+/// Keep only boards whose number is selected
+pub fn filter_boards(boards: &mut Vec<Board>, selected: &BTreeSet<u32>) {
+    boards.retain(|b| is_selected(b, selected));
+}
+
+/// Keep only the hand records and results of selected boards
+pub fn filter_bws(data: &mut crate::bws::BwsData, selected: &BTreeSet<u32>) {
+    filter_boards(&mut data.boards, selected);
+    data.received_data
+        .retain(|r| u32::try_from(r.board).is_ok_and(|n| selected.contains(&n)));
+    data.biddings
+        .retain(|b| u32::try_from(b.board).is_ok_and(|n| selected.contains(&n)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_board_ranges() {
+        let boards = parse_board_ranges("5-8,12").unwrap();
+        assert_eq!(boards.into_iter().collect::<Vec<_>>(), vec![5, 6, 7, 8, 12]);
+
+        let boards = parse_board_ranges(" 3 , 1-2 ,3").unwrap();
+        assert_eq!(boards.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_parse_board_ranges_malformed() {
+        for spec in ["5-", "-5", "8-5", "a", "1,,2", "", "1-2-3"] {
+            assert!(parse_board_ranges(spec).is_err(), "{:?} should fail", spec);
+        }
+    }
+
+    #[test]
+    fn test_parse_board_ranges_too_large() {
+        assert!(parse_board_ranges("1-4000000000").is_err());
+        assert!(parse_board_ranges("1-10001").is_err());
+        assert_eq!(parse_board_ranges("1-10000").unwrap().len(), 10_000);
+    }
+
+    #[test]
+    fn test_filter_boards() {
+        let mut boards: Vec<Board> = (1..=10).map(|n| Board::new().with_number(n)).collect();
+        boards.push(Board::new());
+
+        filter_boards(&mut boards, &parse_board_ranges("2-3,9").unwrap());
+        let numbers: Vec<_> = boards.iter().filter_map(|b| b.number).collect();
+        assert_eq!(numbers, vec![2, 3, 9]);
+    }
+
+    #[test]
+    fn test_filter_bws() {
+        use crate::bws::tables::ReceivedDataRow;
+
+        let row = |board: i32| ReceivedDataRow {
+            id: board,
+            section: 1,
+            table: 1,
+            round: 1,
+            board,
+            pair_ns: 1,
+            pair_ew: 1,
+            declarer: 0,
+            ns_ew: "N".to_string(),
+            contract: "PASS".to_string(),
+            result: String::new(),
+            lead_card: None,
+            remarks: None,
+        };
+        let mut data = crate::bws::BwsData {
+            received_data: (1..=4).map(row).collect(),
+            boards: (1..=4).map(|n| Board::new().with_number(n)).collect(),
+            ..Default::default()
+        };
+
+        filter_bws(&mut data, &parse_board_ranges("2,4").unwrap());
+        let results: Vec<_> = data.received_data.iter().map(|r| r.board).collect();
+        assert_eq!(results, vec![2, 4]);
+        assert_eq!(data.boards.len(), 2);
+    }
+}
@@ -0,0 +1,309 @@
+//! Suit-quality and stopper helpers for a hand's holding in one suit
+//!
+//! These are simple single-hand heuristics for bidding and analysis tools;
+//! partner's cards are not considered. [`opening_bid_hint`] builds on them
+//! to suggest a starting point, not a full bidding system.
+
+use crate::{Hand, Rank, Suit};
+
+/// Broad quality of a suit holding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SuitQuality {
+    /// No cards in the suit
+    Void,
+    /// No more than one of the top five honors and none of A, K, Q
+    Weak,
+    /// One of A, K, Q, or two of the top five honors
+    Fair,
+    /// Two of A, K, Q, or three of the top five honors
+    Good,
+    /// Every card runs without losing the lead
+    Solid,
+}
+
+/// The ranks held in a suit, highest first
+fn ranks(hand: &Hand, suit: Suit) -> Vec<Rank> {
+    let mut ranks: Vec<Rank> = hand
+        .cards()
+        .iter()
+        .filter(|c| c.suit == suit)
+        .map(|c| c.rank)
+        .collect();
+    ranks.sort_by(|a, b| b.cmp(a));
+    ranks
+}
+
+/// Number of cards held from the top of the suit down without a gap (A, AK, AKQ...)
+fn top_sequence(held: &[Rank]) -> usize {
+    let mut all = Rank::ALL.to_vec();
+    all.sort_by(|a, b| b.cmp(a));
+    all.iter().zip(held).take_while(|(a, h)| a == h).count()
+}
+
+/// Whether the suit is stopped for notrump
+///
+/// Uses the usual heuristic: A, Kx, Qxx or Jxxx (or better).
+pub fn has_stopper(hand: &Hand, suit: Suit) -> bool {
+    let held = ranks(hand, suit);
+    let len = held.len();
+    held.iter().any(|&r| match r {
+        Rank::Ace => true,
+        Rank::King => len >= 2,
+        Rank::Queen => len >= 3,
+        Rank::Jack => len >= 4,
+        _ => false,
+    })
+}
+
+/// Number of the top three honors (A, K, Q) held in the suit
+pub fn top_honors(hand: &Hand, suit: Suit) -> u8 {
+    let count = ranks(hand, suit)
+        .iter()
+        .filter(|r| matches!(r, Rank::Ace | Rank::King | Rank::Queen))
+        .count();
+    count as u8
+}
+
+/// Whether `len_needed` tricks can be cashed from the top without losing the lead
+///
+/// True when the hand holds at least `len_needed` cards and either the top
+/// `len_needed` cards of the suit, or enough top winners to draw every
+/// outstanding card even if one opponent holds them all.
+pub fn is_running(hand: &Hand, suit: Suit, len_needed: usize) -> bool {
+    let held = ranks(hand, suit);
+    let len = held.len();
+    let top = top_sequence(&held);
+    len >= len_needed && (top >= len_needed || top >= 13 - len)
+}
+
+/// Suit quality points: the suit's length plus the number of the top five
+/// honors (A, K, Q, J, T) held in it
+pub fn suit_quality_points(hand: &Hand, suit: Suit) -> u8 {
+    let held = ranks(hand, suit);
+    let honors = held
+        .iter()
+        .filter(|r| {
+            matches!(
+                r,
+                Rank::Ace | Rank::King | Rank::Queen | Rank::Jack | Rank::Ten
+            )
+        })
+        .count();
+    (held.len() + honors) as u8
+}
+
+/// Number of suits with a stopper (see [`has_stopper`])
+pub fn stoppers(hand: &Hand) -> u8 {
+    SUITS
+        .iter()
+        .filter(|&&suit| has_stopper(hand, suit))
+        .count() as u8
+}
+
+/// Whether the hand is balanced: 4-3-3-3, 4-4-3-2 or 5-3-3-2
+pub fn is_balanced(hand: &Hand) -> bool {
+    let mut lengths = SUITS.map(|suit| hand.suit_length(suit));
+    lengths.sort_unstable_by(|a, b| b.cmp(a));
+    matches!(lengths, [4, 3, 3, 3] | [4, 4, 3, 2] | [5, 3, 3, 2])
+}
+
+/// Whether the hand suits a notrump opening: balanced, with at most one
+/// unstopped suit
+pub fn is_notrump_shape(hand: &Hand) -> bool {
+    is_balanced(hand) && stoppers(hand) >= 3
+}
+
+/// A naive opening bid for the hand, as a PBN call such as `"1NT"` or `"1S"`
+///
+/// `None` means pass. The rules, in order:
+///
+/// - 22+ HCP: 2C
+/// - 20-21 HCP with notrump shape: 2NT; 15-17: 1NT
+/// - 12+ HCP, or 11 with the two longest suits making 20 with the HCP:
+///   1 of a five-card major (spades when equal), otherwise the longer minor
+///   (diamonds with 4-4, clubs with 3-3)
+/// - 5-10 HCP with six cards in spades, hearts or diamonds and at least 8
+///   [`suit_quality_points`]: a weak two; with seven, a three-level preempt
+///   (clubs included)
+pub fn opening_bid_hint(hand: &Hand) -> Option<String> {
+    let hcp = high_card_points(hand);
+    let length = |suit: Suit| usize::from(hand.suit_length(suit));
+
+    if hcp >= 22 {
+        return Some("2C".to_string());
+    }
+    if is_notrump_shape(hand) {
+        match hcp {
+            20..=21 => return Some("2NT".to_string()),
+            15..=17 => return Some("1NT".to_string()),
+            _ => {}
+        }
+    }
+
+    let mut lengths = SUITS.map(length);
+    lengths.sort_unstable_by(|a, b| b.cmp(a));
+    let rule_of_20 = hcp == 11 && usize::from(hcp) + lengths[0] + lengths[1] >= 20;
+    if hcp >= 12 || rule_of_20 {
+        let suit = if length(Suit::Spades) >= 5 && length(Suit::Spades) >= length(Suit::Hearts) {
+            Suit::Spades
+        } else if length(Suit::Hearts) >= 5 {
+            Suit::Hearts
+        } else if length(Suit::Diamonds) > length(Suit::Clubs)
+            || (length(Suit::Diamonds) == 4 && length(Suit::Clubs) == 4)
+        {
+            Suit::Diamonds
+        } else {
+            Suit::Clubs
+        };
+        return Some(format!("1{}", suit.to_char()));
+    }
+
+    if (5..=10).contains(&hcp) {
+        for suit in SUITS {
+            if suit_quality_points(hand, suit) < 8 {
+                continue;
+            }
+            match length(suit) {
+                6 if suit != Suit::Clubs => return Some(format!("2{}", suit.to_char())),
+                7 => return Some(format!("3{}", suit.to_char())),
+                _ => {}
+            }
+        }
+    }
+
+    None
+}
+
+/// Suits from spades down
+const SUITS: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+
+/// High-card points (A=4, K=3, Q=2, J=1)
+pub fn high_card_points(hand: &Hand) -> u8 {
+    hand.cards()
+        .iter()
+        .map(|c| match c.rank {
+            Rank::Ace => 4,
+            Rank::King => 3,
+            Rank::Queen => 2,
+            Rank::Jack => 1,
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Classify the suit holding (see [`SuitQuality`])
+pub fn quality(hand: &Hand, suit: Suit) -> SuitQuality {
+    let held = ranks(hand, suit);
+    if held.is_empty() {
+        return SuitQuality::Void;
+    }
+    if is_running(hand, suit, held.len()) {
+        return SuitQuality::Solid;
+    }
+
+    let top_three = usize::from(top_honors(hand, suit));
+    let top_five = held
+        .iter()
+        .filter(|r| {
+            matches!(
+                r,
+                Rank::Ace | Rank::King | Rank::Queen | Rank::Jack | Rank::Ten
+            )
+        })
+        .count();
+
+    if top_three >= 2 || top_five >= 3 {
+        SuitQuality::Good
+    } else if top_three == 1 || top_five == 2 {
+        SuitQuality::Fair
+    } else {
+        SuitQuality::Weak
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spades(holding: &str) -> Hand {
+        Hand::from_pbn(&format!("{}...", holding)).unwrap()
+    }
+
+    #[test]
+    fn test_stoppers() {
+        assert!(has_stopper(&spades("Q43"), Suit::Spades));
+        assert!(has_stopper(&spades("AQ"), Suit::Spades));
+        assert!(has_stopper(&spades("KJ9"), Suit::Spades));
+        assert!(has_stopper(&spades("J652"), Suit::Spades));
+        assert!(!has_stopper(&spades("Q4"), Suit::Spades));
+        assert!(!has_stopper(&spades("K"), Suit::Spades));
+        assert!(!has_stopper(&spades(""), Suit::Spades));
+    }
+
+    #[test]
+    fn test_top_honors() {
+        assert_eq!(top_honors(&spades("Q43"), Suit::Spades), 1);
+        assert_eq!(top_honors(&spades("AQ"), Suit::Spades), 2);
+        assert_eq!(top_honors(&spades("KJ9"), Suit::Spades), 1);
+        assert_eq!(top_honors(&spades(""), Suit::Spades), 0);
+        assert_eq!(top_honors(&spades("AKQ2"), Suit::Hearts), 0);
+    }
+
+    #[test]
+    fn test_is_running() {
+        assert!(is_running(&spades("AKQJT"), Suit::Spades, 5));
+        assert!(is_running(&spades("AKQ"), Suit::Spades, 3));
+        // Seven to the AKQ: six outstanding cards, so three rounds may not draw them
+        assert!(!is_running(&spades("AKQ5432"), Suit::Spades, 7));
+        assert!(is_running(&spades("AKQ5432"), Suit::Spades, 3));
+        // Nine to the AKQJ drops everything outstanding
+        assert!(is_running(&spades("AKQJ65432"), Suit::Spades, 9));
+        assert!(!is_running(&spades("AQ"), Suit::Spades, 2));
+        assert!(!is_running(&spades(""), Suit::Spades, 1));
+    }
+
+    #[test]
+    fn test_quality() {
+        assert_eq!(quality(&spades(""), Suit::Spades), SuitQuality::Void);
+        assert_eq!(quality(&spades("8543"), Suit::Spades), SuitQuality::Weak);
+        assert_eq!(quality(&spades("Q43"), Suit::Spades), SuitQuality::Fair);
+        assert_eq!(quality(&spades("AQ"), Suit::Spades), SuitQuality::Good);
+        assert_eq!(quality(&spades("KJ9"), Suit::Spades), SuitQuality::Fair);
+        assert_eq!(quality(&spades("AKQJT"), Suit::Spades), SuitQuality::Solid);
+    }
+
+    #[test]
+    fn test_suit_quality_points() {
+        assert_eq!(suit_quality_points(&spades("KQT932"), Suit::Spades), 9);
+        assert_eq!(suit_quality_points(&spades("865432"), Suit::Spades), 6);
+        assert_eq!(suit_quality_points(&spades(""), Suit::Spades), 0);
+    }
+
+    #[test]
+    fn test_notrump_opening_on_4333_sixteen_count() {
+        let hand = Hand::from_pbn("AQ32.KJ4.Q32.A32").unwrap();
+        assert_eq!(stoppers(&hand), 4);
+        assert!(is_balanced(&hand));
+        assert!(is_notrump_shape(&hand));
+        assert_eq!(opening_bid_hint(&hand).as_deref(), Some("1NT"));
+
+        // Same shape and strength with two suits unstopped opens a minor
+        let hand = Hand::from_pbn("AKQJ.432.432.AQ2").unwrap();
+        assert_eq!(stoppers(&hand), 2);
+        assert!(!is_notrump_shape(&hand));
+        assert_eq!(opening_bid_hint(&hand).as_deref(), Some("1C"));
+    }
+
+    #[test]
+    fn test_opening_bid_hint() {
+        let hint = |pbn: &str| opening_bid_hint(&Hand::from_pbn(pbn).unwrap());
+        assert_eq!(hint("AKJ32.K32.Q32.32").as_deref(), Some("1S"));
+        assert_eq!(hint("A2.KQ32.AJ32.432").as_deref(), Some("1D"));
+        assert_eq!(hint("KQJ932.32.432.32").as_deref(), Some("2S"));
+        assert_eq!(hint("AKQ2.AKQ2.AK2.K2").as_deref(), Some("2C"));
+        assert_eq!(hint("8532.J32.Q32.432"), None);
+        // 4-4-3-2 is balanced, 5-4-2-2 isn't
+        assert!(is_balanced(&Hand::from_pbn("AQ32.KJ43.Q3.A32").unwrap()));
+        assert!(!is_balanced(&Hand::from_pbn("AQ432.KJ43.Q3.A2").unwrap()));
+    }
+}
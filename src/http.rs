@@ -0,0 +1,142 @@
+//! Shared HTTP client settings for the ACBL and URL-shortener fetchers
+//!
+//! Behind a corporate proxy or TLS-intercepting firewall the default
+//! clients can't connect. [`ClientConfig`] collects the settings both
+//! fetchers apply when building their clients.
+
+use crate::error::{BridgeError, Result};
+use std::time::Duration;
+
+/// Browser-like user agent; some sites reject obvious non-browser clients
+pub const DEFAULT_USER_AGENT: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+
+/// HTTP client settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientConfig {
+    /// Whole-request timeout; `None` waits indefinitely
+    pub timeout: Option<Duration>,
+    /// Proxy URL for all requests, e.g. "http://proxy.example.com:8080"
+    ///
+    /// With `None`, reqwest's own environment handling applies: `HTTPS_PROXY`
+    /// and `HTTP_PROXY` are used, and hosts in `NO_PROXY` bypass them.
+    pub proxy: Option<String>,
+    /// User-Agent header sent with every request
+    pub user_agent: String,
+    /// Skip TLS certificate validation (only for intercepting proxies you trust)
+    pub accept_invalid_certs: bool,
+}
+
+impl Default for ClientConfig {
+    /// A 30 second timeout, with the proxy left to the environment
+    fn default() -> Self {
+        Self {
+            timeout: Some(Duration::from_secs(30)),
+            proxy: None,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            accept_invalid_certs: false,
+        }
+    }
+}
+
+impl ClientConfig {
+    /// A blocking client builder with these settings applied
+    pub fn blocking_builder(&self) -> Result<reqwest::blocking::ClientBuilder> {
+        let mut builder = reqwest::blocking::Client::builder()
+            .user_agent(&self.user_agent)
+            .danger_accept_invalid_certs(self.accept_invalid_certs);
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(proxy) = self.reqwest_proxy()? {
+            builder = builder.proxy(proxy);
+        }
+        Ok(builder)
+    }
+
+    /// An async client builder with these settings applied
+    pub fn async_builder(&self) -> Result<reqwest::ClientBuilder> {
+        let mut builder = reqwest::Client::builder()
+            .user_agent(&self.user_agent)
+            .danger_accept_invalid_certs(self.accept_invalid_certs);
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(proxy) = self.reqwest_proxy()? {
+            builder = builder.proxy(proxy);
+        }
+        Ok(builder)
+    }
+
+    /// A blocking client with these settings
+    pub fn blocking_client(&self) -> Result<reqwest::blocking::Client> {
+        self.blocking_builder()?
+            .build()
+            .map_err(|e| BridgeError::Http(format!("Failed to create HTTP client: {}", e)))
+    }
+
+    fn reqwest_proxy(&self) -> Result<Option<reqwest::Proxy>> {
+        self.proxy
+            .as_deref()
+            .map(|url| {
+                reqwest::Proxy::all(url)
+                    .map_err(|e| BridgeError::Http(format!("Invalid proxy {}: {}", url, e)))
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_invalid_proxy() {
+        let config = ClientConfig {
+            proxy: Some("not a url".to_string()),
+            ..ClientConfig::default()
+        };
+        assert!(matches!(
+            config.blocking_client(),
+            Err(BridgeError::Http(_))
+        ));
+    }
+
+    #[test]
+    fn test_requests_go_through_proxy() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let proxy = format!("http://{}", listener.local_addr().unwrap());
+
+        // A stand-in proxy that records the request line and answers "ok"
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request_line = String::new();
+            BufReader::new(&stream)
+                .read_line(&mut request_line)
+                .unwrap();
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok")
+                .unwrap();
+            request_line
+        });
+
+        let config = ClientConfig {
+            proxy: Some(proxy),
+            ..ClientConfig::default()
+        };
+        let client = config.blocking_client().unwrap();
+        let body = client
+            .get("http://bridge.example/hands")
+            .send()
+            .unwrap()
+            .text()
+            .unwrap();
+
+        assert_eq!(body, "ok");
+        assert!(server
+            .join()
+            .unwrap()
+            .starts_with("GET http://bridge.example/hands HTTP/1.1"));
+    }
+}
@@ -1,14 +1,28 @@
 pub mod acbl;
+pub mod auction;
 pub mod bws;
+pub mod cards;
+pub mod deal;
 pub mod error;
+pub mod export;
+pub mod filter;
+pub mod holding;
+pub mod http;
 pub mod lin;
+#[cfg(feature = "ods")]
+pub mod ods;
 pub mod pbn;
+pub mod prelude;
+pub mod scoring;
+pub mod seat;
 pub mod tinyurl;
+pub mod validate;
 pub mod xlsx;
 
 pub use error::{BridgeError, Result};
 
-// Re-export types from bridge-types
+// Re-export types from bridge-types. These root paths are the canonical
+// ones; there is no separate `model` module.
 pub use bridge_types::{
     calculate_matchpoints, dealer_from_board_number, AnnotatedCall, Auction, Board, Call, Card,
     Contract, Deal, Direction, Doubled, Hand, PlaySequence, PlayerNames, Rank, Strain, Suit, Trick,
@@ -3,7 +3,9 @@
 //! LIN is a pipe-delimited format used by Bridge Base Online to encode
 //! complete hand records including deal, auction, and cardplay in URLs.
 
+use crate::cards::{card_to_pbn, parse_card};
 use crate::error::{BridgeError, Result};
+use crate::seat::{from_lin_seat, lin_seat, LIN_SEATS};
 use crate::{Card, Deal, Direction, Hand, Rank, Suit, Vulnerability};
 
 /// A bid with optional alert and annotation
@@ -36,28 +38,34 @@ pub struct LinData {
     pub play: Vec<Card>,
     /// Claim (number of tricks), if hand was claimed
     pub claim: Option<u8>,
+    /// Room designation from the `qx` marker (e.g., "o1" open room, "c1" closed room)
+    pub room: Option<String>,
 }
 
 impl LinData {
     /// Convert this LIN data to a Board with auction, play, and player names.
+    ///
+    /// Without an explicit `board_number`, the number in the board header is
+    /// used, if any.
     pub fn to_board(&self, board_number: Option<u32>) -> crate::Board {
-        use crate::{Auction, Board, Call, PlaySequence, PlayerNames, Suit};
+        use crate::{Auction, Board, Call, PlaySequence, PlayerNames};
 
         let mut board = Board::new()
             .with_dealer(self.dealer)
             .with_vulnerability(self.vulnerability)
             .with_deal(self.deal.clone());
 
-        if let Some(num) = board_number {
+        if let Some(num) = board_number.or_else(|| self.board_number()) {
             board = board.with_number(num);
         }
 
         // Player names (pn order: S, W, N, E)
+        let name = |dir| Some(self.player_names[lin_seat(dir)].clone());
         let names = PlayerNames {
-            south: Some(self.player_names[0].clone()),
-            west: Some(self.player_names[1].clone()),
-            north: Some(self.player_names[2].clone()),
-            east: Some(self.player_names[3].clone()),
+            south: name(Direction::South),
+            west: name(Direction::West),
+            north: name(Direction::North),
+            east: name(Direction::East),
         };
         board = board.with_player_names(names);
 
@@ -82,32 +90,39 @@ impl LinData {
             board = board.with_auction(auction);
         }
 
-        // Determine declarer and contract from the auction
+        // Determine declarer and contract from the auction, cross-checked
+        // against the seat that made the opening lead
+        let lead_declarer = self.declarer_from_lead();
         if let Some(ref auction) = board.auction {
             if let Some(fc) = auction.final_contract() {
+                if let Some(from_lead) = lead_declarer.filter(|&d| d != fc.declarer) {
+                    log::warn!(
+                        "{}: auction gives declarer {} but opening lead implies {}",
+                        self.board_header.as_deref().unwrap_or("LIN board"),
+                        fc.declarer,
+                        from_lead
+                    );
+                }
                 board.declarer = Some(fc.declarer);
                 board.contract = Some(fc.to_pbn());
+            } else if self.is_passed_out() {
+                board.contract = Some("Pass".to_string());
             }
         }
+        if board.declarer.is_none() {
+            board.declarer = lead_declarer;
+        }
 
         // Play sequence (opening lead = first card)
         if !self.play.is_empty() {
             if let Some(declarer) = board.declarer {
                 let leader = declarer.next();
                 // Determine trump suit from contract
-                let trump = board.contract.as_ref().and_then(|c| {
-                    if c.contains('S') {
-                        Some(Suit::Spades)
-                    } else if c.contains('H') {
-                        Some(Suit::Hearts)
-                    } else if c.contains('D') {
-                        Some(Suit::Diamonds)
-                    } else if c.contains('C') {
-                        Some(Suit::Clubs)
-                    } else {
-                        None
-                    }
-                });
+                let trump = board
+                    .contract
+                    .as_deref()
+                    .and_then(crate::scoring::strain_from_contract)
+                    .and_then(crate::scoring::strain_trump);
                 let mut play_seq = PlaySequence::new(leader, trump);
                 for card in &self.play {
                     play_seq.play_card(*card);
@@ -124,6 +139,41 @@ impl LinData {
         board
     }
 
+    /// Whether the auction is four passes
+    fn is_passed_out(&self) -> bool {
+        self.auction.len() == 4 && self.auction.iter().all(|b| b.bid.eq_ignore_ascii_case("p"))
+    }
+
+    /// Board number from the board header
+    ///
+    /// Takes the first integer after the word "Board" (any case), so
+    /// "Board 12 (Open Room)" gives 12. URL-encoded spaces ("Board+12",
+    /// "Board%2012") are decoded first, and a bare number is accepted too.
+    pub fn board_number(&self) -> Option<u32> {
+        let header = self
+            .board_header
+            .as_deref()?
+            .replace('+', " ")
+            .replace("%20", " ");
+        let board_regex = regex::Regex::new(r"(?i)\bboard\s*#?\s*(\d+)").ok()?;
+        if let Some(caps) = board_regex.captures(&header) {
+            return caps[1].parse().ok();
+        }
+        header.trim().parse().ok()
+    }
+
+    /// Infer declarer from the opening lead
+    ///
+    /// The hand holding the first card played is on declarer's left. Returns
+    /// `None` if nothing was played or no hand holds the card.
+    pub fn declarer_from_lead(&self) -> Option<Direction> {
+        let lead = *self.play.first()?;
+        let leader = Direction::ALL
+            .into_iter()
+            .find(|&d| self.deal.hand(d).has_card(lead))?;
+        Some(crate::seat::previous(leader))
+    }
+
     /// Format the cardplay as a trick-by-trick string
     /// Output format: "D2-DA-D6-D5|S3-S2-SQ-SA|..."
     pub fn format_cardplay_by_trick(&self) -> String {
@@ -137,7 +187,7 @@ impl LinData {
             .map(|trick| {
                 trick
                     .iter()
-                    .map(|card| format!("{}{}", card.suit.to_char(), card.rank.to_char()))
+                    .map(|&card| card_to_pbn(card))
                     .collect::<Vec<_>>()
                     .join(" ")
             })
@@ -158,7 +208,8 @@ pub fn parse_lin(lin_str: &str) -> Result<LinData> {
     let mut play = Vec::new();
     let mut claim = None;
 
-    // Split by pipe and process tokens
+    // Split by pipe and process tokens, ignoring a leading byte order mark
+    let lin_str = lin_str.trim_start_matches('\u{feff}');
     let tokens: Vec<&str> = lin_str.split('|').collect();
     let mut i = 0;
 
@@ -263,6 +314,7 @@ pub fn parse_lin(lin_str: &str) -> Result<LinData> {
         auction,
         play,
         claim,
+        room: None,
     })
 }
 
@@ -275,14 +327,8 @@ fn parse_md(md_str: &str) -> Option<(Direction, Deal)> {
     }
 
     // First character is dealer: 1=S, 2=W, 3=N, 4=E (BBO convention)
-    let dealer_char = md_str.chars().next()?;
-    let dealer = match dealer_char {
-        '1' => Direction::South,
-        '2' => Direction::West,
-        '3' => Direction::North,
-        '4' => Direction::East,
-        _ => return None,
-    };
+    let dealer_digit = md_str.chars().next()?.to_digit(10)?;
+    let dealer = from_lin_seat((dealer_digit as usize).checked_sub(1)?)?;
 
     // Rest is comma-separated hands in S, W, N, E order (BBO convention)
     // The dealer digit indicates who dealt, but hands are always in fixed S,W,N,E order
@@ -296,12 +342,7 @@ fn parse_md(md_str: &str) -> Option<(Direction, Deal)> {
     let mut deal = Deal::new();
 
     // BBO uses S, W, N, E order for hands (same as player names)
-    let directions = [
-        Direction::South,
-        Direction::West,
-        Direction::North,
-        Direction::East,
-    ];
+    let directions = LIN_SEATS;
 
     for (i, hand_str) in hand_strs.iter().enumerate().take(3) {
         if let Some(hand) = parse_lin_hand(hand_str) {
@@ -347,20 +388,17 @@ fn parse_lin_hand(hand_str: &str) -> Option<Hand> {
 fn calculate_fourth_hand(deal: &Deal, fourth_dir: Direction) -> Option<Hand> {
     let mut fourth = Hand::new();
 
-    for suit in Suit::ALL {
-        for rank in Rank::ALL {
-            let card = Card::new(suit, rank);
-            let mut found = false;
-            for dir in Direction::ALL {
-                if dir != fourth_dir && deal.hand(dir).has_card(card) {
-                    found = true;
-                    break;
-                }
-            }
-            if !found {
-                fourth.add_card(card);
+    for card in crate::cards::deck() {
+        let mut found = false;
+        for dir in Direction::ALL {
+            if dir != fourth_dir && deal.hand(dir).has_card(card) {
+                found = true;
+                break;
             }
         }
+        if !found {
+            fourth.add_card(card);
+        }
     }
 
     Some(fourth)
@@ -377,18 +415,6 @@ fn parse_sv(sv: &str) -> Vulnerability {
     }
 }
 
-/// Parse a card from LIN format (e.g., "D2", "SA", "HK")
-fn parse_card(card_str: &str) -> Option<Card> {
-    let mut chars = card_str.chars();
-    let suit_char = chars.next()?;
-    let rank_char = chars.next()?;
-
-    let suit = Suit::from_char(suit_char)?;
-    let rank = Rank::from_char(rank_char)?;
-
-    Some(Card::new(suit, rank))
-}
-
 /// Parse multiple boards from a LIN file (tournament format)
 /// Each board is on a separate line, starting with "qx|"
 pub fn parse_lin_file(content: &str) -> Result<Vec<LinData>> {
@@ -413,6 +439,244 @@ pub fn parse_lin_file(content: &str) -> Result<Vec<LinData>> {
     Ok(boards)
 }
 
+/// Parse a multi-board LIN file where boards are separated by `qx|` markers
+///
+/// BBO tournament and team-match exports concatenate boards, each introduced by
+/// a `qx|o1|` (open room) or `qx|c1|` (closed room) marker. BBO omits the `pn`
+/// block and board header when they repeat, so names are carried forward from
+/// the previous board, and the header from the other room of the same board
+/// (the number in the marker, as in o12 and c12). Content without any `qx`
+/// marker is parsed as one board.
+pub fn parse_lin_multi(content: &str) -> Result<Vec<LinData>> {
+    let content = content.trim_start_matches('\u{feff}');
+    let tokens: Vec<&str> = content.split('|').map(str::trim).collect();
+
+    // Tokens before the first qx marker (e.g. a vugraph pn block) act as the
+    // initial carried-forward state.
+    let mut preamble: Vec<&str> = Vec::new();
+    let mut segments: Vec<(String, Vec<&str>)> = Vec::new();
+
+    let mut i = 0;
+    while i < tokens.len() {
+        if tokens[i] == "qx" {
+            let room = tokens.get(i + 1).copied().unwrap_or("").to_string();
+            segments.push((room, Vec::new()));
+            i += 2;
+            continue;
+        }
+        match segments.last_mut() {
+            Some((_, segment)) => segment.push(tokens[i]),
+            None => preamble.push(tokens[i]),
+        }
+        i += 1;
+    }
+
+    if segments.is_empty() {
+        return Ok(vec![parse_lin(content)?]);
+    }
+
+    let preamble_data = parse_lin(&preamble.join("|"))?;
+    let mut last_names = if preamble.contains(&"pn") {
+        Some(preamble_data.player_names)
+    } else {
+        None
+    };
+    let mut last_header = preamble_data.board_header;
+    let mut last_board = None;
+
+    let mut boards = Vec::new();
+    for (index, (room, segment)) in segments.into_iter().enumerate() {
+        let mut data = parse_lin(&segment.join("|"))?;
+
+        if segment.contains(&"pn") {
+            last_names = Some(data.player_names.clone());
+        } else if let Some(ref names) = last_names {
+            data.player_names = names.clone();
+        }
+
+        // A preamble header applies to the first board; after that a header
+        // only carries over between the rooms of one board
+        let board = room_board_number(&room);
+        let same_board = index == 0 || (board.is_some() && board == last_board);
+        if data.board_header.is_none() && same_board {
+            data.board_header = last_header.clone();
+        }
+        last_header = data.board_header.clone();
+        last_board = board;
+
+        if !room.is_empty() {
+            data.room = Some(room);
+        }
+        boards.push(data);
+    }
+
+    Ok(boards)
+}
+
+/// Board number in a `qx` room marker, e.g. 12 for "o12" or "c12"
+fn room_board_number(room: &str) -> Option<u32> {
+    room.trim_start_matches(|c: char| c.is_ascii_alphabetic())
+        .parse()
+        .ok()
+}
+
+/// Parse single- or multi-board LIN content into boards
+///
+/// Boards whose header carries no number take the number in their `qx`
+/// marker, and failing that are numbered by position, starting at 1.
+pub fn read_lin_boards(content: &str) -> Result<Vec<crate::Board>> {
+    Ok(boards_from_lin(&parse_lin_multi(content)?))
+}
+
+/// Convert parsed LIN hands into boards, one per hand and in the same order
+///
+/// Numbering is as for [`read_lin_boards`].
+pub fn boards_from_lin(hands: &[LinData]) -> Vec<crate::Board> {
+    hands
+        .iter()
+        .enumerate()
+        .map(|(i, data)| {
+            let number = data
+                .board_number()
+                .or_else(|| data.room.as_deref().and_then(room_board_number))
+                .unwrap_or(i as u32 + 1);
+            data.to_board(Some(number))
+        })
+        .collect()
+}
+
+/// Base URL of the BBO handviewer, which takes the LIN string as its `lin` parameter
+pub const HANDVIEWER_URL: &str = "https://www.bridgebase.com/tools/handviewer.html";
+
+/// Serialize LinData back into a LIN string
+///
+/// Emits `pn`, `md`, `sv`, `ah`, `mb`/`an`, `pc` and `mc` tokens so that
+/// `parse_lin(&write_lin(&data))` reproduces the same deal, auction and play.
+pub fn write_lin(data: &LinData) -> String {
+    let mut lin = String::new();
+
+    if let Some(ref room) = data.room {
+        lin.push_str(&format!("qx|{}|", room));
+    }
+    lin.push_str(&format!("pn|{}|", data.player_names.join(",")));
+    lin.push_str(&format!("md|{}|", format_md(data.dealer, &data.deal)));
+    lin.push_str(&format!("sv|{}|", format_sv(data.vulnerability)));
+
+    if let Some(ref header) = data.board_header {
+        lin.push_str(&format!("ah|{}|", header.replace(' ', "+")));
+    }
+
+    for bid in &data.auction {
+        let alert = if bid.alert { "!" } else { "" };
+        lin.push_str(&format!("mb|{}{}|", bid.bid, alert));
+        if let Some(ref annotation) = bid.annotation {
+            lin.push_str(&format!("an|{}|", annotation.replace(' ', "+")));
+        }
+    }
+
+    for card in &data.play {
+        lin.push_str(&format!("pc|{}|", card_to_pbn(*card)));
+    }
+
+    if let Some(claim) = data.claim {
+        lin.push_str(&format!("mc|{}|", claim));
+    }
+
+    lin
+}
+
+/// Build a BBO handviewer URL for LinData
+pub fn to_handviewer_url(data: &LinData) -> String {
+    let lin = write_lin(data);
+    let encoded: String = url::form_urlencoded::byte_serialize(lin.as_bytes()).collect();
+    format!("{}?lin={}", HANDVIEWER_URL, encoded)
+}
+
+/// Build a BBO handviewer URL showing a board's deal
+///
+/// Dealer and vulnerability come from the board, falling back to the board
+/// number for a missing dealer. Returns `None` unless all four hands hold 13
+/// cards.
+pub fn board_to_handviewer_url(board: &crate::Board) -> Option<String> {
+    board_lin_data(board).map(|data| to_handviewer_url(&data))
+}
+
+/// Serialize a board's deal as a LIN string
+///
+/// Carries the same deal, dealer, vulnerability and board header as
+/// [`board_to_handviewer_url`], under the same conditions.
+pub fn board_to_lin(board: &crate::Board) -> Option<String> {
+    board_lin_data(board).map(|data| write_lin(&data))
+}
+
+/// LIN data for a board's deal, if all four hands are complete
+fn board_lin_data(board: &crate::Board) -> Option<LinData> {
+    if Direction::ALL
+        .iter()
+        .any(|&dir| board.deal.hand(dir).len() != 13)
+    {
+        return None;
+    }
+
+    let dealer = board
+        .dealer
+        .or_else(|| board.number.map(crate::dealer_from_board_number))?;
+
+    Some(LinData {
+        player_names: Default::default(),
+        dealer,
+        deal: board.deal.clone(),
+        vulnerability: board.vulnerable,
+        board_header: board.number.map(|n| format!("Board {}", n)),
+        auction: Vec::new(),
+        play: Vec::new(),
+        claim: None,
+        room: None,
+    })
+}
+
+/// Format the md (make deal) field: dealer digit + hands in S, W, N, E order
+fn format_md(dealer: Direction, deal: &Deal) -> String {
+    let dealer_digit = lin_seat(dealer) + 1;
+
+    let hands: Vec<String> = LIN_SEATS
+        .iter()
+        .map(|&dir| format_lin_hand(deal.hand(dir)))
+        .collect();
+
+    format!("{}{}", dealer_digit, hands.join(","))
+}
+
+/// Format a single hand in LIN format (e.g., "S7643HAKQT43DA74C")
+fn format_lin_hand(hand: &Hand) -> String {
+    let mut out = String::new();
+
+    for suit in [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs] {
+        let mut ranks: Vec<Rank> = hand
+            .cards()
+            .iter()
+            .filter(|c| c.suit == suit)
+            .map(|c| c.rank)
+            .collect();
+        ranks.sort_by(|a, b| b.cmp(a)); // Sort descending (Ace first)
+
+        out.push(suit.to_char());
+        out.extend(ranks.iter().map(|r| r.to_char()));
+    }
+
+    out
+}
+
+/// Format vulnerability as an sv field value
+fn format_sv(vulnerability: Vulnerability) -> &'static str {
+    match vulnerability {
+        Vulnerability::None => "o",
+        Vulnerability::NorthSouth => "n",
+        Vulnerability::EastWest => "e",
+        Vulnerability::Both => "b",
+    }
+}
+
 /// Extract and parse LIN data from a BBO handviewer URL
 pub fn parse_lin_from_url(url: &str) -> Result<LinData> {
     // Parse the URL to extract the lin parameter
@@ -480,6 +744,34 @@ mod tests {
         assert_eq!(data.play.len(), 4);
     }
 
+    #[test]
+    fn test_declarer_after_transfer() {
+        // North opens 1NT, South transfers with 2H, North plays 2S.
+        // East (holding D3) leads.
+        let lin = "pn|S,W,N,E|md|3SAKHJD876C5432,S2HQT9DKQ5CKQJT9,SQJT9HA32DAJ2CA8,|sv|o|mb|1N|mb|p|mb|2H|mb|p|mb|2S|mb|p|mb|p|mb|p|pc|D3|";
+        let data = parse_lin(lin).unwrap();
+        assert_eq!(data.declarer_from_lead(), Some(Direction::North));
+
+        let board = data.to_board(Some(1));
+        assert_eq!(board.declarer, Some(Direction::North));
+    }
+
+    #[test]
+    fn test_declarer_auction_wins_over_lead() {
+        // Same auction, but the recorded lead (D8) is South's card
+        let lin = "pn|S,W,N,E|md|3SAKHJD876C5432,S2HQT9DKQ5CKQJT9,SQJT9HA32DAJ2CA8,|sv|o|mb|1N|mb|p|mb|2H|mb|p|mb|2S|mb|p|mb|p|mb|p|pc|D8|";
+        let data = parse_lin(lin).unwrap();
+        assert_eq!(data.declarer_from_lead(), Some(Direction::East));
+        assert_eq!(data.to_board(Some(1)).declarer, Some(Direction::North));
+    }
+
+    #[test]
+    fn test_declarer_from_lead_without_auction() {
+        let lin = "pn|S,W,N,E|md|3SAKHJD876C5432,S2HQT9DKQ5CKQJT9,SQJT9HA32DAJ2CA8,|sv|o|pc|D3|";
+        let board = parse_lin(lin).unwrap().to_board(Some(1));
+        assert_eq!(board.declarer, Some(Direction::North));
+    }
+
     #[test]
     fn test_format_cardplay_by_trick() {
         let lin = "pn|S,W,N,E|md|3SAKHJD876C5432,S2HQT9DKQ5CKQJT9,SQJT9HA32DAJ2CA8,|sv|o|pc|D2|pc|DA|pc|D3|pc|D8|pc|H2|pc|H4|pc|HJ|pc|HQ|";
@@ -513,4 +805,228 @@ mod tests {
         assert_eq!(data.player_names[0], "S");
         assert_eq!(data.dealer, Direction::South);
     }
+
+    #[test]
+    fn test_write_lin_round_trip_passed_out() {
+        let lin = "pn|South,West,North,East|md|3SAKHJD876C5432,S2HQT9DKQ5CKQJT9,SQJT9HA32DAJ2CA8,|sv|e|ah|Board+2|mb|p|mb|p|mb|p|mb|p|";
+        let data = parse_lin(lin).unwrap();
+
+        let round_trip = parse_lin(&write_lin(&data)).unwrap();
+        assert_eq!(round_trip.player_names, data.player_names);
+        assert_eq!(round_trip.dealer, data.dealer);
+        assert_eq!(round_trip.vulnerability, Vulnerability::EastWest);
+        assert_eq!(round_trip.board_header, Some("Board 2".to_string()));
+        assert_eq!(
+            round_trip.deal.to_pbn(Direction::North),
+            data.deal.to_pbn(Direction::North)
+        );
+        assert_eq!(round_trip.auction.len(), 4);
+        assert!(round_trip.auction.iter().all(|b| b.bid == "p"));
+        assert!(round_trip.play.is_empty());
+        assert_eq!(round_trip.claim, None);
+    }
+
+    #[test]
+    fn test_write_lin_round_trip_with_claim() {
+        let lin = "pn|S,W,N,E|md|3SAKHJD876C5432,S2HQT9DKQ5CKQJT9,SQJT9HA32DAJ2CA8,|sv|b|mb|1N!|an|15-17|mb|p|mb|3N|mb|p|mb|p|mb|p|pc|D2|pc|DA|pc|D3|pc|D8|mc|9|";
+        let data = parse_lin(lin).unwrap();
+
+        let round_trip = parse_lin(&write_lin(&data)).unwrap();
+        assert_eq!(
+            round_trip.deal.to_pbn(Direction::North),
+            data.deal.to_pbn(Direction::North)
+        );
+        assert_eq!(round_trip.auction.len(), 6);
+        assert!(round_trip.auction[0].alert);
+        assert_eq!(round_trip.auction[0].annotation, Some("15-17".to_string()));
+        assert_eq!(
+            round_trip.format_cardplay_by_trick(),
+            data.format_cardplay_by_trick()
+        );
+        assert_eq!(round_trip.claim, Some(9));
+    }
+
+    #[test]
+    fn test_board_to_handviewer_url() {
+        let deal =
+            Deal::from_pbn("N:K843.T542.J6.863 AQJ7.K.Q75.AT942 962.AJ7.KT82.J75 T5.Q9863.A943.KQ")
+                .unwrap();
+        let board = crate::Board::new()
+            .with_number(1)
+            .with_vulnerability(Vulnerability::None)
+            .with_deal(deal);
+
+        let url = board_to_handviewer_url(&board).unwrap();
+        assert!(url.starts_with(HANDVIEWER_URL));
+
+        let data = parse_lin_from_url(&url).unwrap();
+        assert_eq!(data.dealer, Direction::North);
+        assert_eq!(
+            format_md(data.dealer, &data.deal),
+            "3S962HAJ7DKT82CJ75,ST5HQ9863DA943CKQ,SK843HT542DJ6C863,SAQJ7HKDQ75CAT942"
+        );
+        assert_eq!(data.vulnerability, Vulnerability::None);
+        assert_eq!(data.board_header, Some("Board 1".to_string()));
+    }
+
+    #[test]
+    fn test_board_to_handviewer_url_incomplete() {
+        let board = crate::Board::new().with_number(1);
+        assert!(board_to_handviewer_url(&board).is_none());
+    }
+
+    #[test]
+    fn test_to_handviewer_url_round_trip() {
+        let lin = "pn|S,W,N,E|md|1SAKHJD876C5432,S2HQT9DKQ5CKQJT9,SQJT9HA32DAJ2CA8,|sv|n|ah|Board+7|mb|1C!|an|could+be+short|";
+        let data = parse_lin(lin).unwrap();
+
+        let url = to_handviewer_url(&data);
+        assert!(url.starts_with("https://www.bridgebase.com/tools/handviewer.html?lin="));
+
+        let parsed = parse_lin_from_url(&url).unwrap();
+        assert_eq!(parsed.dealer, Direction::South);
+        assert_eq!(parsed.vulnerability, Vulnerability::NorthSouth);
+        assert_eq!(parsed.board_header, Some("Board 7".to_string()));
+        assert_eq!(
+            parsed.auction[0].annotation,
+            Some("could be short".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_lin_multi_carries_forward() {
+        let lin = "qx|o1|pn|Ann,Bob,Cat,Dan|md|3SAKHJD876C5432,S2HQT9DKQ5CKQJT9,SQJT9HA32DAJ2CA8,|ah|Board+1|sv|o|mb|1C|mb|p|mb|p|mb|p|pg||
+qx|c1|md|3SAKHJD876C5432,S2HQT9DKQ5CKQJT9,SQJT9HA32DAJ2CA8,|sv|o|mb|p|mb|p|mb|p|mb|p|pg||";
+        let boards = parse_lin_multi(lin).unwrap();
+        assert_eq!(boards.len(), 2);
+
+        assert_eq!(boards[0].room, Some("o1".to_string()));
+        assert_eq!(boards[1].room, Some("c1".to_string()));
+
+        // Closed room omits pn and ah, inherited from the open room
+        assert_eq!(boards[1].player_names[0], "Ann");
+        assert_eq!(boards[1].player_names[3], "Dan");
+        assert_eq!(boards[1].board_header, Some("Board 1".to_string()));
+        assert_eq!(boards[1].auction.len(), 4);
+    }
+
+    #[test]
+    fn test_parse_lin_multi_header_stays_with_its_board() {
+        let lin = "qx|o1|md|3SAKHJD876C5432,S2HQT9DKQ5CKQJT9,SQJT9HA32DAJ2CA8,|ah|Board+1|sv|o|
+qx|c1|md|3SAKHJD876C5432,S2HQT9DKQ5CKQJT9,SQJT9HA32DAJ2CA8,|sv|o|
+qx|o2|md|4SAKHJD876C5432,S2HQT9DKQ5CKQJT9,SQJT9HA32DAJ2CA8,|sv|n|";
+        let boards = parse_lin_multi(lin).unwrap();
+        assert_eq!(boards.len(), 3);
+        assert_eq!(boards[1].board_header, Some("Board 1".to_string()));
+        // Board 2 has no ah of its own and must not inherit Board 1's
+        assert_eq!(boards[2].board_header, None);
+        assert_eq!(room_board_number("o12"), Some(12));
+        assert_eq!(room_board_number("c12"), Some(12));
+        assert_eq!(room_board_number(""), None);
+    }
+
+    #[test]
+    fn test_parse_lin_multi_fixture() {
+        let content = include_str!("../../tests/fixtures/input/kemistry-boards-2026-01-14.lin");
+        let boards = parse_lin_multi(content).unwrap();
+        assert_eq!(boards.len(), 12);
+        assert_eq!(boards[0].room, Some("o1".to_string()));
+        assert_eq!(boards[11].room, Some("o12".to_string()));
+        assert_eq!(boards[0].player_names[0], "aam135");
+    }
+
+    #[test]
+    fn test_parse_lin_multi_without_markers() {
+        let lin = "pn|S,W,N,E|md|1SAKHJD876C5432,,,|sv|o|";
+        let boards = parse_lin_multi(lin).unwrap();
+        assert_eq!(boards.len(), 1);
+        assert_eq!(boards[0].room, None);
+    }
+
+    #[test]
+    fn test_parse_lin_with_bom() {
+        let lin = "\u{feff}pn|S,W,N,E|md|1SAKHJD876C5432,,,|sv|o|\r\n";
+        let boards = parse_lin_multi(lin).unwrap();
+        assert_eq!(boards[0].player_names[0], "S");
+        assert_eq!(boards[0].dealer, Direction::South);
+
+        let data = parse_lin(lin).unwrap();
+        assert_eq!(data.player_names[3], "E");
+    }
+
+    #[test]
+    fn test_board_number_from_header() {
+        let number = |header: &str| {
+            let mut data = parse_lin("md|1S23,,,|").unwrap();
+            data.board_header = Some(header.to_string());
+            data.board_number()
+        };
+        assert_eq!(number("Board 1"), Some(1));
+        assert_eq!(number("Board 12 (Open Room)"), Some(12));
+        assert_eq!(number("Open Room - Board 7"), Some(7));
+        assert_eq!(number("board+12"), Some(12));
+        assert_eq!(number("Board%2012"), Some(12));
+        assert_eq!(number("BOARD #3"), Some(3));
+        assert_eq!(number(" 15 "), Some(15));
+        assert_eq!(number("Round 2 Table 5"), None);
+        assert_eq!(number("Boards"), None);
+
+        let data = parse_lin("md|1S23,,,|ah|Board+9|").unwrap();
+        assert_eq!(data.board_number(), Some(9));
+        assert_eq!(data.to_board(None).number, Some(9));
+        assert_eq!(data.to_board(Some(4)).number, Some(4));
+    }
+
+    #[test]
+    fn test_read_lin_boards_to_pbn_and_back() {
+        let content = concat!(
+            "qx|o1|pn|South,West,North,East|md|3S48JH25TQD235C679,S36TH468KAD4TC45Q,",
+            "S257QAH9DJQKC8JKA,|sv|o|ah|Board 1|mb|1N|mb|p|mb|3N|mb|p|mb|p|mb|p|\n",
+            "qx|o2|md|4S48JH25TQD235C679,S36TH468KAD4TC45Q,S257QAH9DJQKC8JKA,|sv|n|ah|Board 2",
+            "|mb|p|mb|p|mb|p|mb|p|\n",
+        );
+        let boards = read_lin_boards(content).unwrap();
+        assert_eq!(boards.len(), 2);
+        assert_eq!(boards[0].number, Some(1));
+        assert_eq!(boards[0].contract.as_deref(), Some("3NT"));
+        assert_eq!(boards[0].declarer, Some(Direction::North));
+        assert_eq!(boards[1].number, Some(2));
+        assert_eq!(boards[1].contract.as_deref(), Some("Pass"));
+        assert_eq!(boards[1].declarer, None);
+
+        let round_trip =
+            crate::pbn::reader::read_pbn(&crate::pbn::writer::write_pbn(&boards)).unwrap();
+        assert_eq!(round_trip.len(), 2);
+        for (before, after) in boards.iter().zip(&round_trip) {
+            assert_eq!(after.number, before.number);
+            assert_eq!(after.dealer, before.dealer);
+            assert_eq!(after.vulnerable, before.vulnerable);
+            assert_eq!(
+                after.deal.to_pbn(Direction::North),
+                before.deal.to_pbn(Direction::North)
+            );
+            assert_eq!(after.contract, before.contract);
+        }
+    }
+
+    #[test]
+    fn test_read_lin_boards_numbers_from_qx_marker() {
+        let content = concat!(
+            "qx|o1|md|3SAKHJD876C5432,S2HQT9DKQ5CKQJT9,SQJT9HA32DAJ2CA8,|sv|o|ah|Board 1|",
+            "mb|p|mb|p|mb|p|mb|p|\n",
+            "qx|o2|md|4SAKHJD876C5432,S2HQT9DKQ5CKQJT9,SQJT9HA32DAJ2CA8,|sv|n|",
+            "mb|p|mb|p|mb|p|mb|p|\n",
+        );
+        let boards = read_lin_boards(content).unwrap();
+        assert_eq!(boards.len(), 2);
+        assert_eq!(boards[0].number, Some(1));
+        assert_eq!(boards[1].number, Some(2));
+    }
+
+    #[test]
+    fn test_read_lin_boards_numbers_by_position() {
+        let lin = "md|3SAKHJD876C5432,S2HQT9DKQ5CKQJT9,SQJT9HA32DAJ2CA8,|sv|o|";
+        let boards = read_lin_boards(lin).unwrap();
+        assert_eq!(boards[0].number, Some(1));
+    }
 }
@@ -1,12 +1,21 @@
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
 
 use bridge_parsers::acbl;
 use bridge_parsers::bws;
+use bridge_parsers::deal;
+use bridge_parsers::export;
+use bridge_parsers::filter;
+use bridge_parsers::http;
+use bridge_parsers::lin;
 use bridge_parsers::pbn;
+use bridge_parsers::scoring;
+use bridge_parsers::validate;
 use bridge_parsers::xlsx;
-use bridge_parsers::Direction;
+use bridge_parsers::{Board, Direction, Suit, Vulnerability};
+use serde::Serialize;
 
 #[derive(Parser)]
 #[command(name = "bridge-parsers")]
@@ -20,7 +29,7 @@ struct Cli {
 enum Commands {
     /// Convert between file formats
     Convert {
-        /// Input file (PBN or BWS)
+        /// Input file (PBN, BWS or LIN)
         input: PathBuf,
 
         /// Output file
@@ -30,6 +39,32 @@ enum Commands {
         /// URL to fetch ACBL masterpoint data (e.g., https://d21acbl.org/members/members-d21/)
         #[arg(long)]
         masterpoints_url: Option<String>,
+
+        /// Local CSV or JSON file of ACBL masterpoint data (instead of --masterpoints-url)
+        #[arg(long, conflicts_with = "masterpoints_url")]
+        masterpoints_file: Option<PathBuf>,
+
+        /// Match players to masterpoint data by name similarity (0.0-1.0, e.g. 0.9)
+        /// when the exact number/name lookup misses
+        #[cfg(feature = "fuzzy")]
+        #[arg(long, value_name = "SIMILARITY", value_parser = parse_fuzzy_threshold)]
+        fuzzy_threshold: Option<f64>,
+
+        /// Only process these boards, e.g. "5-8,12"
+        #[arg(long, value_name = "RANGES")]
+        boards: Option<String>,
+
+        /// Give every board this dealer ("rotate" follows the board number)
+        #[arg(long, value_enum)]
+        dealer: Option<DealerArg>,
+
+        /// Give every board this vulnerability ("rotate" follows the board number)
+        #[arg(long, value_enum)]
+        vulnerability: Option<VulnerabilityArg>,
+
+        /// Add a Frequencies sheet of each board's distinct scores (BWS to .xlsx)
+        #[arg(long)]
+        frequencies: bool,
     },
 
     /// Combine PBN (deals) and BWS (scores) into a single Excel workbook
@@ -42,26 +77,253 @@ enum Commands {
         #[arg(long)]
         bws: PathBuf,
 
-        /// Output Excel file
+        /// Output file (.xlsx, or .csv/.ods for the Game Results columns only)
         #[arg(short, long)]
         output: PathBuf,
 
         /// URL to fetch ACBL masterpoint data (e.g., https://d21acbl.org/members/members-d21/)
         #[arg(long)]
         masterpoints_url: Option<String>,
+
+        /// Local CSV or JSON file of ACBL masterpoint data (instead of --masterpoints-url)
+        #[arg(long, conflicts_with = "masterpoints_url")]
+        masterpoints_file: Option<PathBuf>,
+
+        /// Match players to masterpoint data by name similarity (0.0-1.0, e.g. 0.9)
+        /// when the exact number/name lookup misses
+        #[cfg(feature = "fuzzy")]
+        #[arg(long, value_name = "SIMILARITY", value_parser = parse_fuzzy_threshold)]
+        fuzzy_threshold: Option<f64>,
+
+        /// Add a Scorecards sheet with each pair's board-by-board results
+        #[arg(long)]
+        scorecards: bool,
+
+        /// Add a Frequencies sheet of each board's distinct scores
+        #[arg(long)]
+        frequencies: bool,
+
+        /// How pairs in IMP-scored sections are totalled and ranked
+        #[arg(long, value_enum, default_value = "butler")]
+        imp_method: ImpMethodArg,
+
+        /// Omit autofilter and MP% color scales (useful when diffing output)
+        #[arg(long)]
+        no_formatting: bool,
     },
 
     /// Display information about a file
     Info {
         /// Input file to inspect
         input: PathBuf,
+
+        /// Output as human-readable text or JSON
+        #[arg(long, value_enum, default_value = "text")]
+        format: InfoFormat,
+
+        /// Only process these boards, e.g. "5-8,12"
+        #[arg(long, value_name = "RANGES")]
+        boards: Option<String>,
     },
 
     /// Validate a file
     Validate {
-        /// Input file to validate
+        /// Input file to validate (PBN, BWS or LIN)
+        input: PathBuf,
+
+        /// Only process these boards, e.g. "5-8,12"
+        #[arg(long, value_name = "RANGES")]
+        boards: Option<String>,
+    },
+
+    /// Score results and print a ranked table per board
+    Score {
+        /// Input file (BWS, or PBN with Contract/Declarer/Result tags)
+        input: PathBuf,
+
+        /// How to compare scores on each board
+        #[arg(long, value_enum, default_value = "matchpoints")]
+        scoring: ScoringMethod,
+    },
+
+    /// Concatenate several PBN files into one
+    Merge {
+        /// PBN files to merge, in order
+        #[arg(required = true)]
+        inputs: Vec<PathBuf>,
+
+        /// Output PBN file
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Number boards 1, 2, 3... and recompute dealer and vulnerability
+        #[arg(long)]
+        renumber: bool,
+    },
+
+    /// Write each board of a file to its own file, e.g. board-001.pbn
+    Split {
+        /// Input file (PBN, BWS or LIN)
         input: PathBuf,
+
+        /// Directory for the per-board files (created if missing)
+        #[arg(short, long)]
+        output_dir: PathBuf,
+
+        /// Format of the per-board files
+        #[arg(long, value_enum, default_value = "pbn")]
+        format: SplitFormat,
+
+        /// Overwrite existing files
+        #[arg(long)]
+        force: bool,
     },
+
+    /// Compare the deals in two files board by board
+    Diff {
+        /// First file (PBN, BWS or LIN)
+        left: PathBuf,
+
+        /// Second file (PBN, BWS or LIN)
+        right: PathBuf,
+    },
+
+    /// Download the PBN and BWS files linked from an ACBL Live for Clubs game page
+    FetchGame {
+        /// Game results page URL
+        url: String,
+
+        /// Directory for the downloaded files (created if missing)
+        #[arg(short, long, default_value = ".")]
+        output_dir: PathBuf,
+    },
+}
+
+/// Output format of the `info` command
+#[derive(Clone, Copy, ValueEnum)]
+enum InfoFormat {
+    Text,
+    Json,
+}
+
+/// File format written by the `split` command
+#[derive(Clone, Copy, ValueEnum)]
+enum SplitFormat {
+    /// PBN hand record
+    Pbn,
+    /// BBO LIN string
+    Lin,
+    /// BBO handviewer URL
+    Url,
+}
+
+impl SplitFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            SplitFormat::Pbn => "pbn",
+            SplitFormat::Lin => "lin",
+            SplitFormat::Url => "url",
+        }
+    }
+}
+
+/// IMP pairs scoring for `combine`
+#[derive(Clone, Copy, ValueEnum)]
+enum ImpMethodArg {
+    /// Against each board's Butler datum
+    Butler,
+    /// Against every other result on the board
+    Cross,
+}
+
+impl From<ImpMethodArg> for xlsx::ImpMethod {
+    fn from(arg: ImpMethodArg) -> Self {
+        match arg {
+            ImpMethodArg::Butler => xlsx::ImpMethod::Butler,
+            ImpMethodArg::Cross => xlsx::ImpMethod::CrossImps,
+        }
+    }
+}
+
+/// Dealer override for `convert`
+#[derive(Clone, Copy, ValueEnum)]
+enum DealerArg {
+    N,
+    E,
+    S,
+    W,
+    /// Dealer from the board number
+    Rotate,
+}
+
+impl DealerArg {
+    /// The fixed dealer, or `None` to rotate
+    fn direction(self) -> Option<Direction> {
+        match self {
+            DealerArg::N => Some(Direction::North),
+            DealerArg::E => Some(Direction::East),
+            DealerArg::S => Some(Direction::South),
+            DealerArg::W => Some(Direction::West),
+            DealerArg::Rotate => None,
+        }
+    }
+}
+
+/// Vulnerability override for `convert`
+#[derive(Clone, Copy, ValueEnum)]
+enum VulnerabilityArg {
+    None,
+    Ns,
+    Ew,
+    All,
+    /// Vulnerability from the board number
+    Rotate,
+}
+
+impl VulnerabilityArg {
+    /// The fixed vulnerability, or `None` to rotate
+    fn vulnerability(self) -> Option<Vulnerability> {
+        match self {
+            VulnerabilityArg::None => Some(Vulnerability::None),
+            VulnerabilityArg::Ns => Some(Vulnerability::NorthSouth),
+            VulnerabilityArg::Ew => Some(Vulnerability::EastWest),
+            VulnerabilityArg::All => Some(Vulnerability::Both),
+            VulnerabilityArg::Rotate => None,
+        }
+    }
+}
+
+/// Dealer and vulnerability overrides applied to converted hand records
+#[derive(Clone, Copy, Default)]
+struct BoardOverrides {
+    dealer: Option<DealerArg>,
+    vulnerability: Option<VulnerabilityArg>,
+}
+
+impl BoardOverrides {
+    fn is_empty(&self) -> bool {
+        self.dealer.is_none() && self.vulnerability.is_none()
+    }
+
+    fn apply(&self, boards: &mut [Board]) {
+        for board in boards {
+            if let Some(dealer) = self.dealer {
+                deal::set_dealer(board, dealer.direction());
+            }
+            if let Some(vulnerability) = self.vulnerability {
+                deal::set_vulnerability(board, vulnerability.vulnerability());
+            }
+        }
+    }
+}
+
+/// Method used to compare results on a board
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum ScoringMethod {
+    /// Matchpoint percentages
+    Matchpoints,
+    /// Cross-IMPs averaged over the other results
+    Imps,
 }
 
 fn main() -> Result<()> {
@@ -74,29 +336,189 @@ fn main() -> Result<()> {
             input,
             output,
             masterpoints_url,
+            masterpoints_file,
+            #[cfg(feature = "fuzzy")]
+            fuzzy_threshold,
+            boards,
+            dealer,
+            vulnerability,
+            frequencies,
         } => {
-            convert(&input, &output, masterpoints_url.as_deref())?;
+            let selection = board_selection(boards.as_deref())?;
+            let member_data =
+                load_member_data(masterpoints_url.as_deref(), masterpoints_file.as_deref());
+            let overrides = BoardOverrides {
+                dealer,
+                vulnerability,
+            };
+            let options = xlsx::XlsxOptions {
+                frequencies,
+                #[cfg(feature = "fuzzy")]
+                fuzzy_threshold,
+                ..xlsx::XlsxOptions::default()
+            };
+            convert(
+                &input,
+                &output,
+                member_data.as_ref(),
+                selection.as_ref(),
+                overrides,
+                &options,
+            )?;
         }
         Commands::Combine {
             pbn,
             bws,
             output,
             masterpoints_url,
+            masterpoints_file,
+            #[cfg(feature = "fuzzy")]
+            fuzzy_threshold,
+            scorecards,
+            frequencies,
+            imp_method,
+            no_formatting,
+        } => {
+            let options = xlsx::XlsxOptions {
+                scorecards,
+                frequencies,
+                imp_method: imp_method.into(),
+                #[cfg(feature = "fuzzy")]
+                fuzzy_threshold,
+                ..if no_formatting {
+                    xlsx::XlsxOptions::plain()
+                } else {
+                    xlsx::XlsxOptions::default()
+                }
+            };
+            let member_data =
+                load_member_data(masterpoints_url.as_deref(), masterpoints_file.as_deref());
+            combine(&pbn, &bws, &output, member_data.as_ref(), &options)?;
+        }
+        Commands::Info {
+            input,
+            format,
+            boards,
+        } => {
+            let selection = board_selection(boards.as_deref())?;
+            info(&input, format, selection.as_ref())?;
+        }
+        Commands::Validate { input, boards } => {
+            let selection = board_selection(boards.as_deref())?;
+            validate(&input, selection.as_ref())?;
+        }
+        Commands::Score { input, scoring } => {
+            score(&input, scoring)?;
+        }
+        Commands::Merge {
+            inputs,
+            output,
+            renumber,
+        } => {
+            merge(&inputs, &output, renumber)?;
+        }
+        Commands::Split {
+            input,
+            output_dir,
+            format,
+            force,
         } => {
-            combine(&pbn, &bws, &output, masterpoints_url.as_deref())?;
+            split(&input, &output_dir, format, force)?;
         }
-        Commands::Info { input } => {
-            info(&input)?;
+        Commands::Diff { left, right } => {
+            diff(&left, &right)?;
         }
-        Commands::Validate { input } => {
-            validate(&input)?;
+        Commands::FetchGame { url, output_dir } => {
+            fetch_game(&url, &output_dir)?;
         }
     }
 
     Ok(())
 }
 
-fn convert(input: &Path, output: &Path, masterpoints_url: Option<&str>) -> Result<()> {
+/// ACBL member data keyed by member number and lowercase name
+type MemberData = HashMap<String, acbl::MemberInfo>;
+
+/// Load masterpoint data from a URL or local file, if either was given
+///
+/// Failures are reported as warnings; the output is still written without
+/// masterpoint columns.
+fn load_member_data(url: Option<&str>, file: Option<&Path>) -> Option<MemberData> {
+    let loaded = if let Some(url) = url {
+        println!("Fetching masterpoint data from: {}", url);
+        acbl::fetch_member_masterpoints(url)
+    } else if let Some(file) = file {
+        println!("Loading masterpoint data from: {}", file.display());
+        acbl::load_member_masterpoints_from_file(file)
+    } else {
+        return None;
+    };
+
+    match loaded {
+        Ok(data) => {
+            println!("Loaded {} member records", data.len());
+            Some(data)
+        }
+        Err(e) => {
+            println!("Warning: Failed to load masterpoint data: {}", e);
+            None
+        }
+    }
+}
+
+/// Parse the `--fuzzy-threshold` option, which must lie in 0.0-1.0
+#[cfg(feature = "fuzzy")]
+fn parse_fuzzy_threshold(s: &str) -> std::result::Result<f64, String> {
+    let threshold: f64 = s.parse().map_err(|_| format!("not a number: {}", s))?;
+    if (0.0..=1.0).contains(&threshold) {
+        Ok(threshold)
+    } else {
+        Err(format!("must be between 0.0 and 1.0, got {}", s))
+    }
+}
+
+/// Parse the `--boards` option, if given
+fn board_selection(spec: Option<&str>) -> Result<Option<BTreeSet<u32>>> {
+    spec.map(|s| filter::parse_board_ranges(s).context("Invalid --boards option"))
+        .transpose()
+}
+
+/// Read a PBN file, keeping only the selected boards
+fn read_pbn_selected(input: &Path, selection: Option<&BTreeSet<u32>>) -> Result<Vec<Board>> {
+    let mut boards = pbn::reader::read_pbn_file(input).context("Failed to read PBN file")?;
+    if let Some(selected) = selection {
+        filter::filter_boards(&mut boards, selected);
+    }
+    Ok(boards)
+}
+
+/// Read a BWS file, keeping only the selected boards' hand records and results
+fn read_bws_selected(input: &Path, selection: Option<&BTreeSet<u32>>) -> Result<bws::BwsData> {
+    let mut data = bws::read_bws(input).context("Failed to read BWS file")?;
+    if let Some(selected) = selection {
+        filter::filter_bws(&mut data, selected);
+    }
+    Ok(data)
+}
+
+/// Read a single- or multi-board LIN file, keeping only the selected boards
+fn read_lin_selected(input: &Path, selection: Option<&BTreeSet<u32>>) -> Result<Vec<Board>> {
+    let content = std::fs::read_to_string(input).context("Failed to read LIN file")?;
+    let mut boards = lin::read_lin_boards(&content).context("Failed to parse LIN file")?;
+    if let Some(selected) = selection {
+        filter::filter_boards(&mut boards, selected);
+    }
+    Ok(boards)
+}
+
+fn convert(
+    input: &Path,
+    output: &Path,
+    member_data: Option<&MemberData>,
+    selection: Option<&BTreeSet<u32>>,
+    overrides: BoardOverrides,
+    options: &xlsx::XlsxOptions,
+) -> Result<()> {
     let input_ext = input
         .extension()
         .and_then(|e| e.to_str())
@@ -109,27 +531,17 @@ fn convert(input: &Path, output: &Path, masterpoints_url: Option<&str>) -> Resul
         .unwrap_or("")
         .to_lowercase();
 
-    // Fetch masterpoint data if URL provided
-    let member_data = if let Some(url) = masterpoints_url {
-        println!("Fetching masterpoint data from: {}", url);
-        match acbl::fetch_member_masterpoints(url) {
-            Ok(data) => {
-                println!("Loaded {} member records", data.len());
-                Some(data)
-            }
-            Err(e) => {
-                println!("Warning: Failed to fetch masterpoint data: {}", e);
-                None
-            }
-        }
-    } else {
-        None
-    };
+    // Results were scored at the board's real vulnerability, so overrides
+    // only make sense for hand records
+    let keeps_results = matches!(output_ext.as_str(), "xlsx" | "ods" | "csv");
+    if input_ext == "bws" && keeps_results && !overrides.is_empty() {
+        anyhow::bail!("--dealer and --vulnerability can't be used when converting BWS results");
+    }
 
     // Special case: BWS to Excel preserves game results data
     if input_ext == "bws" && output_ext == "xlsx" {
         println!("Reading BWS file: {}", input.display());
-        let data = bws::read_bws(input).context("Failed to read BWS file")?;
+        let data = read_bws_selected(input, selection)?;
 
         println!("Found {} game results", data.received_data.len());
         println!("Found {} players in this game", data.player_numbers.len());
@@ -138,21 +550,52 @@ fn convert(input: &Path, output: &Path, masterpoints_url: Option<&str>) -> Resul
         }
 
         println!("Writing Excel file: {}", output.display());
-        xlsx::write_bws_to_xlsx_with_masterpoints(&data, output, member_data.as_ref())
+        xlsx::write_bws_to_xlsx_with_masterpoints(&data, output, member_data, options)
             .context("Failed to write Excel file")?;
 
         println!("Done!");
         return Ok(());
     }
 
-    let boards = match input_ext.as_str() {
+    // BWS to ODS keeps the game results too
+    if input_ext == "bws" && output_ext == "ods" {
+        println!("Reading BWS file: {}", input.display());
+        let data = read_bws_selected(input, selection)?;
+        println!("Found {} game results", data.received_data.len());
+
+        println!("Writing ODS file: {}", output.display());
+        write_ods(output, &data.boards, Some(&data))?;
+
+        println!("Done!");
+        return Ok(());
+    }
+
+    // BWS to CSV likewise keeps the game results
+    if input_ext == "bws" && output_ext == "csv" {
+        println!("Reading BWS file: {}", input.display());
+        let data = read_bws_selected(input, selection)?;
+        println!("Found {} game results", data.received_data.len());
+
+        println!("Writing CSV file: {}", output.display());
+        export::write_combined_to_csv(&data.boards, &data, output)
+            .context("Failed to write CSV file")?;
+
+        println!("Done!");
+        return Ok(());
+    }
+
+    let mut boards = match input_ext.as_str() {
         "pbn" => {
             println!("Reading PBN file: {}", input.display());
-            pbn::reader::read_pbn_file(input).context("Failed to read PBN file")?
+            read_pbn_selected(input, selection)?
+        }
+        "lin" => {
+            println!("Reading LIN file: {}", input.display());
+            read_lin_selected(input, selection)?
         }
         "bws" => {
             println!("Reading BWS file: {}", input.display());
-            let data = bws::read_bws(input).context("Failed to read BWS file")?;
+            let data = read_bws_selected(input, selection)?;
 
             if data.has_hand_records() {
                 println!("Found {} hand records", data.boards.len());
@@ -166,10 +609,10 @@ fn convert(input: &Path, output: &Path, masterpoints_url: Option<&str>) -> Resul
                 board_nums
                     .into_iter()
                     .map(|n| {
-                        bridge_parsers::Board::new()
+                        Board::new()
                             .with_number(n)
                             .with_dealer(bridge_parsers::dealer_from_board_number(n))
-                            .with_vulnerability(bridge_parsers::Vulnerability::from_board_number(n))
+                            .with_vulnerability(Vulnerability::from_board_number(n))
                     })
                     .collect()
             }
@@ -180,16 +623,37 @@ fn convert(input: &Path, output: &Path, masterpoints_url: Option<&str>) -> Resul
     };
 
     println!("Found {} boards", boards.len());
+    overrides.apply(&mut boards);
 
     match output_ext.as_str() {
         "pbn" => {
+            // Scoring and Score aren't Board fields; carry them over from a
+            // PBN input
+            let scoring = if input_ext == "pbn" {
+                let content = std::fs::read_to_string(input).context("Failed to read PBN file")?;
+                pbn::reader::read_scoring_tags(&content)
+            } else {
+                BTreeMap::new()
+            };
             println!("Writing PBN file: {}", output.display());
-            pbn::writer::write_pbn_file(&boards, output).context("Failed to write PBN file")?;
+            std::fs::write(
+                output,
+                pbn::writer::write_pbn_with_scoring(&boards, &scoring),
+            )
+            .context("Failed to write PBN file")?;
         }
         "xlsx" => {
             println!("Writing Excel file: {}", output.display());
             xlsx::write_boards_to_xlsx(&boards, output).context("Failed to write Excel file")?;
         }
+        "csv" => {
+            println!("Writing CSV file: {}", output.display());
+            export::write_boards_to_csv(&boards, output).context("Failed to write CSV file")?;
+        }
+        "ods" => {
+            println!("Writing ODS file: {}", output.display());
+            write_ods(output, &boards, None)?;
+        }
         _ => {
             anyhow::bail!("Unsupported output format: {}", output_ext);
         }
@@ -199,29 +663,30 @@ fn convert(input: &Path, output: &Path, masterpoints_url: Option<&str>) -> Resul
     Ok(())
 }
 
+/// Write boards, and game results if given, to an ODS file
+#[cfg(feature = "ods")]
+fn write_ods(output: &Path, boards: &[Board], data: Option<&bws::BwsData>) -> Result<()> {
+    use bridge_parsers::ods;
+
+    match data {
+        Some(data) => ods::write_combined_to_ods(boards, data, output),
+        None => ods::write_boards_to_ods(boards, output),
+    }
+    .context("Failed to write ODS file")
+}
+
+#[cfg(not(feature = "ods"))]
+fn write_ods(_output: &Path, _boards: &[Board], _data: Option<&bws::BwsData>) -> Result<()> {
+    anyhow::bail!("ODS output requires building with the `ods` feature")
+}
+
 fn combine(
     pbn_path: &Path,
     bws_path: &Path,
     output: &Path,
-    masterpoints_url: Option<&str>,
+    member_data: Option<&MemberData>,
+    options: &xlsx::XlsxOptions,
 ) -> Result<()> {
-    // Fetch masterpoint data if URL provided
-    let member_data = if let Some(url) = masterpoints_url {
-        println!("Fetching masterpoint data from: {}", url);
-        match acbl::fetch_member_masterpoints(url) {
-            Ok(data) => {
-                println!("Loaded {} member records", data.len());
-                Some(data)
-            }
-            Err(e) => {
-                println!("Warning: Failed to fetch masterpoint data: {}", e);
-                None
-            }
-        }
-    } else {
-        None
-    };
-
     // Read PBN file for hand records
     println!("Reading PBN file: {}", pbn_path.display());
     let boards = pbn::reader::read_pbn_file(pbn_path).context("Failed to read PBN file")?;
@@ -233,25 +698,216 @@ fn combine(
     println!("Found {} game results", bws_data.received_data.len());
     println!("Found {} players", bws_data.player_numbers.len());
 
-    // Write combined Excel file
-    println!("Writing combined Excel file: {}", output.display());
-    xlsx::write_combined_to_xlsx(&boards, &bws_data, output, member_data.as_ref())
-        .context("Failed to write Excel file")?;
+    let output_ext = output
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    if output_ext == "csv" {
+        println!("Writing combined CSV file: {}", output.display());
+        export::write_combined_to_csv(&boards, &bws_data, output)
+            .context("Failed to write CSV file")?;
+    } else if output_ext == "ods" {
+        println!("Writing combined ODS file: {}", output.display());
+        write_ods(output, &boards, Some(&bws_data))?;
+    } else {
+        // Write combined Excel file
+        println!("Writing combined Excel file: {}", output.display());
+        xlsx::write_combined_to_xlsx(&boards, &bws_data, output, member_data, options)
+            .context("Failed to write Excel file")?;
+    }
 
     println!("Done!");
     Ok(())
 }
 
-fn info(input: &Path) -> Result<()> {
+fn merge(inputs: &[PathBuf], output: &Path, renumber: bool) -> Result<()> {
+    let mut sets = Vec::new();
+    for input in inputs {
+        println!("Reading PBN file: {}", input.display());
+        let boards = pbn::reader::read_pbn_file(input)
+            .with_context(|| format!("Failed to read PBN file {}", input.display()))?;
+        println!("Found {} boards", boards.len());
+        sets.push(boards);
+    }
+
+    let merged = pbn::merge::merge_boards(sets, renumber);
+    if !merged.duplicates.is_empty() {
+        let numbers: Vec<String> = merged.duplicates.iter().map(|n| n.to_string()).collect();
+        println!(
+            "Warning: duplicate board numbers {} (use --renumber to number boards sequentially)",
+            numbers.join(", ")
+        );
+    }
+
+    println!(
+        "Writing {} boards to PBN file: {}",
+        merged.boards.len(),
+        output.display()
+    );
+    pbn::writer::write_pbn_file(&merged.boards, output).context("Failed to write PBN file")?;
+
+    println!("Done!");
+    Ok(())
+}
+
+fn split(input: &Path, output_dir: &Path, format: SplitFormat, force: bool) -> Result<()> {
+    let boards = read_boards(input)?;
+    println!("Found {} boards", boards.len());
+
+    // Unnumbered boards take their position in the file
+    let files: Vec<(PathBuf, &Board)> = boards
+        .iter()
+        .enumerate()
+        .map(|(i, board)| {
+            let number = board.number.unwrap_or(i as u32 + 1);
+            let name = format!("board-{:03}.{}", number, format.extension());
+            (output_dir.join(name), board)
+        })
+        .collect();
+
+    // Check every target first so nothing is half-written
+    let mut seen = std::collections::HashSet::new();
+    if let Some((path, _)) = files.iter().find(|(path, _)| !seen.insert(path)) {
+        anyhow::bail!(
+            "More than one board would be written to {}; board numbers must be unique",
+            path.display()
+        );
+    }
+    if !force {
+        if let Some((path, _)) = files.iter().find(|(path, _)| path.exists()) {
+            anyhow::bail!(
+                "{} already exists (use --force to overwrite)",
+                path.display()
+            );
+        }
+    }
+
+    std::fs::create_dir_all(output_dir)
+        .with_context(|| format!("Failed to create {}", output_dir.display()))?;
+    for (path, board) in &files {
+        let content = match format {
+            SplitFormat::Pbn => pbn::writer::write_pbn(std::slice::from_ref(*board)),
+            SplitFormat::Lin => lin::board_to_lin(board)
+                .map(|s| s + "\n")
+                .with_context(|| format!("{}: deal is incomplete", path.display()))?,
+            SplitFormat::Url => lin::board_to_handviewer_url(board)
+                .map(|s| s + "\n")
+                .with_context(|| format!("{}: deal is incomplete", path.display()))?,
+        };
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    println!("Wrote {} files to {}", files.len(), output_dir.display());
+    Ok(())
+}
+
+fn fetch_game(url: &str, output_dir: &Path) -> Result<()> {
+    println!("Fetching game page: {}", url);
+    let game = acbl::fetch_club_game_results(url).map_err(anyhow::Error::msg)?;
+    println!("{} {}", game.event_name, game.date);
+
+    let links: Vec<(&str, String)> = [
+        ("pbn", game.pbn_download_url()),
+        ("bws", game.bws_download_url()),
+    ]
+    .into_iter()
+    .filter_map(|(ext, url)| Some((ext, url?)))
+    .collect();
+    if links.is_empty() {
+        anyhow::bail!("No PBN or BWS download links found on the page");
+    }
+
+    std::fs::create_dir_all(output_dir)
+        .with_context(|| format!("Failed to create {}", output_dir.display()))?;
+    let config = http::ClientConfig::default();
+    for (ext, file_url) in links {
+        // Name the file after the link, falling back to game.<ext>
+        let name = url::Url::parse(&file_url)
+            .ok()
+            .and_then(|u| u.path_segments()?.next_back().map(str::to_string))
+            .filter(|name| name.to_lowercase().ends_with(&format!(".{}", ext)))
+            .unwrap_or_else(|| format!("game.{}", ext));
+        let path = output_dir.join(name);
+
+        println!("Downloading {}", file_url);
+        let bytes = acbl::download_file(&file_url, &config).map_err(anyhow::Error::msg)?;
+        std::fs::write(&path, bytes)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        println!("Wrote {}", path.display());
+    }
+
+    Ok(())
+}
+
+fn diff(left: &Path, right: &Path) -> Result<()> {
+    let left_boards = read_boards(left)?;
+    let right_boards = read_boards(right)?;
+
+    let diffs = deal::diff_boards(&left_boards, &right_boards);
+    if diffs.is_empty() {
+        println!("Deals match");
+    } else {
+        for d in &diffs {
+            println!("{}", d);
+        }
+        println!("{} differences", diffs.len());
+    }
+    Ok(())
+}
+
+/// Read the boards of a PBN, BWS or LIN file
+fn read_boards(input: &Path) -> Result<Vec<Board>> {
     let ext = input
         .extension()
         .and_then(|e| e.to_str())
         .unwrap_or("")
         .to_lowercase();
 
+    match ext.as_str() {
+        "pbn" => read_pbn_selected(input, None),
+        "bws" => Ok(read_bws_selected(input, None)?.boards),
+        "lin" => read_lin_selected(input, None),
+        _ => anyhow::bail!("Unsupported file format: {}", ext),
+    }
+}
+
+fn info(input: &Path, format: InfoFormat, selection: Option<&BTreeSet<u32>>) -> Result<()> {
+    let ext = input
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    if let InfoFormat::Json = format {
+        let json = match ext.as_str() {
+            "pbn" => {
+                let boards = read_pbn_selected(input, selection)?;
+                let infos: Vec<BoardJson> = boards.iter().map(BoardJson::from_board).collect();
+                serde_json::to_string_pretty(&infos)?
+            }
+            "bws" => {
+                let summary = read_bws_selected(input, selection)?.summary();
+                serde_json::to_string_pretty(&BwsJson {
+                    sections: summary.sections.len(),
+                    players: summary.players,
+                    results: summary.results,
+                    auctions: summary.auctions,
+                    boards: summary.boards,
+                    warnings: summary.warnings,
+                })?
+            }
+            _ => anyhow::bail!("Unsupported file format: {}", ext),
+        };
+        println!("{}", json);
+        return Ok(());
+    }
+
     match ext.as_str() {
         "pbn" => {
-            let boards = pbn::reader::read_pbn_file(input).context("Failed to read PBN file")?;
+            let boards = read_pbn_selected(input, selection)?;
             println!("PBN File: {}", input.display());
             println!("Boards: {}", boards.len());
             println!();
@@ -261,21 +917,18 @@ fn info(input: &Path) -> Result<()> {
             }
         }
         "bws" => {
-            let data = bws::read_bws(input).context("Failed to read BWS file")?;
+            let data = read_bws_selected(input, selection)?;
+            let summary = data.summary();
             println!("BWS File: {}", input.display());
             println!();
 
-            println!("Sections: {}", data.sections.len());
-            for section in &data.sections {
-                println!(
-                    "  Section {}: {} tables",
-                    section.letter.trim(),
-                    section.tables
-                );
+            println!("Sections: {}", summary.sections.len());
+            for section in &summary.sections {
+                println!("  Section {}: {} tables", section.letter, section.tables);
             }
             println!();
 
-            println!("Players: {}", data.player_names.len());
+            println!("Players: {}", summary.players);
             for player in data.player_names.iter().take(10) {
                 println!("  {} - {}", player.str_id, player.name);
             }
@@ -284,16 +937,26 @@ fn info(input: &Path) -> Result<()> {
             }
             println!();
 
-            println!("Game Results: {}", data.received_data.len());
-            let board_nums = bws::reader::get_board_numbers(&data);
-            println!("Boards played: {:?}", board_nums);
+            println!("Game Results: {}", summary.results);
+            println!("Boards played: {:?}", summary.boards);
             println!();
 
-            if data.has_hand_records() {
-                println!("Hand Records: {} boards", data.boards.len());
+            if summary.hand_records > 0 {
+                println!("Hand Records: {} boards", summary.hand_records);
             } else {
                 println!("Hand Records: None (deals stored in separate PBN file)");
             }
+            if summary.auctions > 0 {
+                println!("Auctions: {}", summary.auctions);
+            }
+
+            if !summary.warnings.is_empty() {
+                println!();
+                println!("Warnings: {}", summary.warnings.len());
+                for warning in &summary.warnings {
+                    println!("  {}", warning);
+                }
+            }
         }
         _ => {
             anyhow::bail!("Unsupported file format: {}", ext);
@@ -303,7 +966,7 @@ fn info(input: &Path) -> Result<()> {
     Ok(())
 }
 
-fn validate(input: &Path) -> Result<()> {
+fn validate(input: &Path, selection: Option<&BTreeSet<u32>>) -> Result<()> {
     let ext = input
         .extension()
         .and_then(|e| e.to_str())
@@ -312,52 +975,316 @@ fn validate(input: &Path) -> Result<()> {
 
     match ext.as_str() {
         "pbn" => {
-            let boards = pbn::reader::read_pbn_file(input).context("Failed to read PBN file")?;
+            let boards = read_pbn_selected(input, selection)?;
             println!("PBN file is valid");
             println!("  {} boards", boards.len());
 
-            let mut issues = Vec::new();
-            for board in &boards {
-                if let Some(num) = board.number {
-                    // Check hand sizes
-                    for dir in Direction::ALL {
-                        let hand = board.deal.hand(dir);
-                        let len = hand.len();
-                        if len != 13 && len != 0 {
-                            issues.push(format!(
-                                "Board {}: {} has {} cards (expected 13)",
-                                num, dir, len
-                            ));
-                        }
-                    }
-                }
-            }
-
-            if issues.is_empty() {
-                println!("  No issues found");
-            } else {
-                println!("  Issues found:");
-                for issue in issues {
-                    println!("    - {}", issue);
-                }
-            }
+            print_deal_issues(&boards);
         }
         "bws" => {
-            let data = bws::read_bws(input).context("Failed to read BWS file")?;
+            let data = read_bws_selected(input, selection)?;
             println!("BWS file is valid");
             println!("  {} sections", data.sections.len());
             println!("  {} players", data.player_names.len());
             println!("  {} results", data.received_data.len());
+
+            print_deal_issues(&data.boards);
+            print_result_issues(&data);
+        }
+        "lin" => {
+            let content = std::fs::read_to_string(input).context("Failed to read LIN file")?;
+            let hands = lin::parse_lin_multi(&content).context("Failed to parse LIN file")?;
+            // Each hand stays paired with its own board, so the two rooms of
+            // a board are checked against their own auctions
+            let (hands, boards): (Vec<&lin::LinData>, Vec<Board>) = hands
+                .iter()
+                .zip(lin::boards_from_lin(&hands))
+                .filter(|(_, board)| selection.is_none_or(|s| filter::is_selected(board, s)))
+                .unzip();
+            println!("LIN file is valid");
+            println!("  {} boards", boards.len());
+
+            print_deal_issues(&boards);
+            print_revokes(&hands, &boards);
+        }
+        _ => {
+            anyhow::bail!("Unsupported file format: {}", ext);
+        }
+    }
+
+    Ok(())
+}
+
+/// Print revokes in the recorded play, with declarer's tricks after the
+/// revoke penalty
+///
+/// `hands` and `boards` are parallel: each hand is checked against the
+/// declarer and contract of the board at the same index.
+fn print_revokes(hands: &[&lin::LinData], boards: &[Board]) {
+    let mut lines = Vec::new();
+    for (hand, board) in hands.iter().zip(boards) {
+        let number = board
+            .number
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| "?".to_string());
+        let Some(declarer) = board.declarer else {
+            continue;
+        };
+        let trump = board
+            .contract
+            .as_deref()
+            .and_then(scoring::strain_from_contract)
+            .and_then(scoring::strain_trump);
+        let revokes = validate::detect_revokes(&hand.deal, &hand.play, declarer.next(), trump);
+        if revokes.is_empty() {
+            continue;
+        }
+
+        for revoke in &revokes {
+            lines.push(format!(
+                "Board {}: trick {}: {} revoked, playing {} on a {} lead ({} trick penalty)",
+                number,
+                revoke.trick,
+                revoke.seat,
+                bridge_parsers::cards::card_to_pbn(revoke.card),
+                revoke.led.to_char(),
+                scoring::revoke_penalty_tricks(revoke)
+            ));
+        }
+        if let Some(claim) = hand.claim {
+            lines.push(format!(
+                "Board {}: declarer's {} tricks become {}",
+                number,
+                claim,
+                scoring::apply_revoke_penalty(claim, declarer, &revokes)
+            ));
+        }
+    }
+
+    if !lines.is_empty() {
+        println!("  Revokes:");
+        for line in lines {
+            println!("    - {}", line);
+        }
+    }
+}
+
+/// Print card-level problems in each board's deal, and dealer/vulnerability
+/// that disagree with the board number
+fn print_deal_issues(boards: &[Board]) {
+    let mut issues = Vec::new();
+    for board in boards {
+        let label = board
+            .number
+            .map(|n| format!("Board {}", n))
+            .unwrap_or_else(|| "Unnumbered board".to_string());
+        for issue in validate::validate_deal(&board.deal) {
+            issues.push(format!("{}: {}", label, issue));
+        }
+        if let Some(issue) = validate::check_dealer_vul_consistency(board) {
+            issues.push(format!("{}: {}", label, issue));
+        }
+    }
+
+    if issues.is_empty() {
+        println!("  No issues found");
+    } else {
+        println!("  Issues found:");
+        for issue in issues {
+            println!("    - {}", issue);
+        }
+    }
+}
+
+/// Print results that don't fit their board's hand record
+fn print_result_issues(data: &bws::BwsData) {
+    let issues = data.validate_results_against_hands();
+    if !issues.is_empty() {
+        println!("  Results inconsistent with hand records:");
+        for issue in issues {
+            println!("    - {}", issue);
+        }
+    }
+}
+
+/// A single scored result, as printed by the `score` command
+struct ScoredRow {
+    board: u32,
+    ns: String,
+    ew: String,
+    contract: String,
+    declarer: String,
+    result: String,
+    score: i32,
+}
+
+fn score(input: &Path, method: ScoringMethod) -> Result<()> {
+    let ext = input
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    let (rows, total): (Vec<ScoredRow>, usize) = match ext.as_str() {
+        "bws" => {
+            let data = bws::read_bws(input).context("Failed to read BWS file")?;
+            let rows = data
+                .received_data
+                .iter()
+                .filter_map(|r| {
+                    let score = scoring::calculate_score_for_result(r)?;
+                    Some(ScoredRow {
+                        board: r.board as u32,
+                        ns: r.pair_ns.to_string(),
+                        ew: r.pair_ew.to_string(),
+                        contract: r.contract.clone(),
+                        declarer: r.ns_ew.clone(),
+                        result: r.result.clone(),
+                        score,
+                    })
+                })
+                .collect();
+            (rows, data.received_data.len())
+        }
+        "pbn" => {
+            let boards = pbn::reader::read_pbn_file(input).context("Failed to read PBN file")?;
+            let rows = boards
+                .iter()
+                .filter_map(|b| {
+                    let score = scoring::calculate_score_for_board(b)?;
+                    Some(ScoredRow {
+                        board: b.number?,
+                        ns: "-".to_string(),
+                        ew: "-".to_string(),
+                        contract: b.contract.clone().unwrap_or_default(),
+                        declarer: b
+                            .declarer
+                            .map(|d| d.to_char().to_string())
+                            .unwrap_or_default(),
+                        result: b.result.map(|r| r.to_string()).unwrap_or_default(),
+                        score,
+                    })
+                })
+                .collect();
+            (rows, boards.len())
         }
         _ => {
             anyhow::bail!("Unsupported file format: {}", ext);
         }
+    };
+
+    if rows.len() < total {
+        println!(
+            "Warning: {} of {} results could not be scored",
+            total - rows.len(),
+            total
+        );
+    }
+
+    let mut by_board: BTreeMap<u32, Vec<&ScoredRow>> = BTreeMap::new();
+    for row in &rows {
+        by_board.entry(row.board).or_default().push(row);
+    }
+
+    let column = match method {
+        ScoringMethod::Matchpoints => "NS MP%",
+        ScoringMethod::Imps => "NS IMPs",
+    };
+
+    for (board, results) in &by_board {
+        let scores: Vec<i32> = results.iter().map(|r| r.score).collect();
+        let comparisons = match method {
+            ScoringMethod::Matchpoints => bridge_parsers::calculate_matchpoints(&scores),
+            ScoringMethod::Imps => scoring::calculate_cross_imps(&scores),
+        };
+
+        let mut order: Vec<usize> = (0..results.len()).collect();
+        order.sort_by(|&a, &b| scores[b].cmp(&scores[a]));
+
+        println!("Board {}", board);
+        println!(
+            "  {:>4} {:>4}  {:<8} {:<3} {:<6} {:>6}  {:>7}",
+            "NS", "EW", "Contract", "By", "Result", "Score", column
+        );
+        for idx in order {
+            let r = results[idx];
+            println!(
+                "  {:>4} {:>4}  {:<8} {:<3} {:<6} {:>6}  {:>7.1}",
+                r.ns, r.ew, r.contract, r.declarer, r.result, r.score, comparisons[idx]
+            );
+        }
+        println!();
     }
 
     Ok(())
 }
 
-fn print_board_info(board: &bridge_parsers::Board) {
+/// A board as emitted by `info --format json`
+#[derive(Serialize)]
+struct BoardJson {
+    number: Option<u32>,
+    dealer: Option<String>,
+    vulnerability: String,
+    hands: Vec<HandJson>,
+}
+
+/// One seat of a board in `info --format json`
+#[derive(Serialize)]
+struct HandJson {
+    direction: String,
+    hcp: u32,
+    /// Suit lengths as S-H-D-C, e.g. "4-4-3-2"
+    shape: String,
+    pbn: String,
+}
+
+impl BoardJson {
+    fn from_board(board: &Board) -> Self {
+        let hcp = board.all_hcp();
+        let hands = [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ]
+        .iter()
+        .zip(hcp.iter())
+        .map(|(&dir, &hcp)| {
+            let hand = board.deal.hand(dir);
+            let shape: Vec<String> = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs]
+                .iter()
+                .map(|&suit| hand.suit_length(suit).to_string())
+                .collect();
+            HandJson {
+                direction: dir.to_char().to_string(),
+                hcp: u32::from(hcp),
+                shape: shape.join("-"),
+                pbn: hand.to_pbn(),
+            }
+        })
+        .collect();
+
+        BoardJson {
+            number: board.number,
+            dealer: board.dealer.map(|d| d.to_char().to_string()),
+            vulnerability: board.vulnerable.to_pbn(),
+            hands,
+        }
+    }
+}
+
+/// BWS summary emitted by `info --format json`
+#[derive(Serialize)]
+struct BwsJson {
+    sections: usize,
+    players: usize,
+    results: usize,
+    auctions: usize,
+    boards: Vec<u32>,
+    warnings: Vec<String>,
+}
+
+fn print_board_info(board: &Board) {
     if let Some(num) = board.number {
         println!("Board {}", num);
     }
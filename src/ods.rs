@@ -0,0 +1,236 @@
+//! OpenDocument spreadsheet (.ods) export mirroring the Excel sheets
+//!
+//! Sheets hold the same columns as the xlsx Hand Records and combined Game
+//! Results sheets, built from the same rows as the CSV export. Formatting,
+//! the Players sheet and the other derived sheets are xlsx-only.
+
+use crate::error::Result;
+use crate::export::{board_records, combined_records};
+use crate::xlsx::writer::{COMBINED_RESULT_HEADERS, HAND_RECORD_HEADERS};
+use crate::Board;
+use std::io::{Cursor, Write};
+use std::path::Path;
+
+const MIMETYPE: &str = "application/vnd.oasis.opendocument.spreadsheet";
+
+const MANIFEST: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<manifest:manifest xmlns:manifest="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0" manifest:version="1.2">
+ <manifest:file-entry manifest:full-path="/" manifest:media-type="application/vnd.oasis.opendocument.spreadsheet"/>
+ <manifest:file-entry manifest:full-path="content.xml" manifest:media-type="text/xml"/>
+</manifest:manifest>
+"#;
+
+/// A named sheet: a header row followed by text cells
+struct Sheet<'a> {
+    name: &'a str,
+    headers: &'a [&'a str],
+    rows: Vec<Vec<String>>,
+}
+
+/// Write boards to an ODS file with a Hand Records sheet
+pub fn write_boards_to_ods(boards: &[Board], path: &Path) -> Result<()> {
+    std::fs::write(path, write_boards_to_ods_buf(boards)?)?;
+    Ok(())
+}
+
+/// Write boards to an in-memory ODS file
+pub fn write_boards_to_ods_buf(boards: &[Board]) -> Result<Vec<u8>> {
+    build_ods(&[Sheet {
+        name: "Hand Records",
+        headers: &HAND_RECORD_HEADERS,
+        rows: board_records(boards),
+    }])
+}
+
+/// Write combined PBN (deals) and BWS (scores) data to an ODS file
+///
+/// Contains the Game Results sheet, plus Hand Records when there are boards.
+pub fn write_combined_to_ods(
+    boards: &[Board],
+    bws_data: &crate::bws::BwsData,
+    path: &Path,
+) -> Result<()> {
+    std::fs::write(path, write_combined_to_ods_buf(boards, bws_data)?)?;
+    Ok(())
+}
+
+/// Write combined data to an in-memory ODS file
+pub fn write_combined_to_ods_buf(
+    boards: &[Board],
+    bws_data: &crate::bws::BwsData,
+) -> Result<Vec<u8>> {
+    let mut sheets = vec![Sheet {
+        name: "Game Results",
+        headers: &COMBINED_RESULT_HEADERS,
+        rows: combined_records(boards, bws_data),
+    }];
+    if !boards.is_empty() {
+        sheets.push(Sheet {
+            name: "Hand Records",
+            headers: &HAND_RECORD_HEADERS,
+            rows: board_records(boards),
+        });
+    }
+    build_ods(&sheets)
+}
+
+/// Package sheets as an ODS zip
+///
+/// The uncompressed `mimetype` entry must come first so the file type can
+/// be recognized without unzipping.
+fn build_ods(sheets: &[Sheet]) -> Result<Vec<u8>> {
+    use zip::write::SimpleFileOptions;
+    use zip::CompressionMethod;
+
+    let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    zip.start_file("mimetype", stored)?;
+    zip.write_all(MIMETYPE.as_bytes())?;
+    zip.start_file("META-INF/manifest.xml", deflated)?;
+    zip.write_all(MANIFEST.as_bytes())?;
+    zip.start_file("content.xml", deflated)?;
+    zip.write_all(content_xml(sheets).as_bytes())?;
+
+    Ok(zip.finish()?.into_inner())
+}
+
+/// The `content.xml` document holding every sheet
+fn content_xml(sheets: &[Sheet]) -> String {
+    let mut xml = String::from(concat!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+        "\n",
+        r#"<office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0""#,
+        r#" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0""#,
+        r#" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" office:version="1.2">"#,
+        "<office:body><office:spreadsheet>",
+    ));
+
+    for sheet in sheets {
+        xml.push_str(&format!(
+            r#"<table:table table:name="{}">"#,
+            escape_xml(sheet.name)
+        ));
+        xml.push_str("<table:table-row>");
+        for header in sheet.headers {
+            push_text_cell(&mut xml, header);
+        }
+        xml.push_str("</table:table-row>");
+
+        for row in &sheet.rows {
+            xml.push_str("<table:table-row>");
+            for value in row {
+                push_cell(&mut xml, value);
+            }
+            xml.push_str("</table:table-row>");
+        }
+        xml.push_str("</table:table>");
+    }
+
+    xml.push_str("</office:spreadsheet></office:body></office:document-content>\n");
+    xml
+}
+
+/// Append a cell, as a number when the text is plainly numeric
+///
+/// Only digits, '-' and '.' count, so results like "+1" and names that
+/// happen to parse as floats ("NaN") stay text.
+fn push_cell(xml: &mut String, value: &str) {
+    let numeric = !value.is_empty()
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_digit() || b == b'-' || b == b'.');
+    match value.parse::<f64>() {
+        Ok(number) if numeric => xml.push_str(&format!(
+            r#"<table:table-cell office:value-type="float" office:value="{}"><text:p>{}</text:p></table:table-cell>"#,
+            number, value
+        )),
+        _ if value.is_empty() => xml.push_str("<table:table-cell/>"),
+        _ => push_text_cell(xml, value),
+    }
+}
+
+fn push_text_cell(xml: &mut String, value: &str) {
+    xml.push_str(&format!(
+        r#"<table:table-cell office:value-type="string"><text:p>{}</text:p></table:table-cell>"#,
+        escape_xml(value)
+    ));
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Deal, Direction, Vulnerability};
+    use std::io::Read;
+
+    fn read_entry(bytes: &[u8], name: &str) -> String {
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        let mut content = String::new();
+        archive
+            .by_name(name)
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        content
+    }
+
+    #[test]
+    fn test_write_boards_to_ods() {
+        let deal =
+            Deal::from_pbn("N:K843.T542.J6.863 AQJ7.K.Q75.AT942 962.AJ7.KT82.J75 T5.Q9863.A943.KQ")
+                .unwrap();
+        let board = Board::new()
+            .with_number(1)
+            .with_dealer(Direction::North)
+            .with_vulnerability(Vulnerability::None)
+            .with_deal(deal);
+
+        let bytes = write_boards_to_ods_buf(&[board]).unwrap();
+
+        let archive = zip::ZipArchive::new(Cursor::new(&bytes[..])).unwrap();
+        assert_eq!(archive.name_for_index(0), Some("mimetype"));
+        assert_eq!(read_entry(&bytes, "mimetype"), MIMETYPE);
+
+        let content = read_entry(&bytes, "content.xml");
+        assert!(content.contains(r#"<table:table table:name="Hand Records">"#));
+        for header in HAND_RECORD_HEADERS {
+            assert!(content.contains(&format!("<text:p>{}</text:p>", header)));
+        }
+        assert!(content.contains(r#"office:value-type="float" office:value="1""#));
+    }
+
+    #[test]
+    fn test_write_combined_to_ods_headers() {
+        let data = crate::bws::BwsData::default();
+        let bytes = write_combined_to_ods_buf(&[], &data).unwrap();
+        let content = read_entry(&bytes, "content.xml");
+
+        assert!(content.contains(r#"<table:table table:name="Game Results">"#));
+        assert!(!content.contains("Hand Records"));
+        for header in COMBINED_RESULT_HEADERS {
+            assert!(content.contains(&format!("<text:p>{}</text:p>", escape_xml(header))));
+        }
+    }
+
+    #[test]
+    fn test_cell_types() {
+        let mut xml = String::new();
+        push_cell(&mut xml, "+1");
+        push_cell(&mut xml, "NaN");
+        push_cell(&mut xml, "A&B");
+        assert!(!xml.contains("float"));
+        assert!(xml.contains("A&amp;B"));
+
+        let mut xml = String::new();
+        push_cell(&mut xml, "-620");
+        assert!(xml.contains(r#"office:value="-620""#));
+    }
+}
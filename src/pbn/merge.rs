@@ -0,0 +1,104 @@
+//! Combine boards from several PBN files into one set
+
+use crate::deal::renumber_board;
+use crate::Board;
+use std::collections::BTreeSet;
+
+/// Result of merging board sets
+#[derive(Debug, Clone)]
+pub struct MergedBoards {
+    /// All boards, in input order
+    pub boards: Vec<Board>,
+    /// Board numbers that appeared more than once (only when not renumbering)
+    pub duplicates: Vec<u32>,
+}
+
+/// Concatenate board sets in order
+///
+/// With `renumber`, boards are numbered 1, 2, 3... across all sets and each
+/// board's dealer and vulnerability are recomputed from its new number.
+/// Otherwise numbers are kept and any repeats are reported in `duplicates`.
+pub fn merge_boards(sets: Vec<Vec<Board>>, renumber: bool) -> MergedBoards {
+    let mut boards: Vec<Board> = sets.into_iter().flatten().collect();
+    let mut duplicates = BTreeSet::new();
+
+    if renumber {
+        for (i, board) in boards.iter_mut().enumerate() {
+            renumber_board(board, i as u32 + 1);
+        }
+    } else {
+        let mut seen = BTreeSet::new();
+        for number in boards.iter().filter_map(|b| b.number) {
+            if !seen.insert(number) {
+                duplicates.insert(number);
+            }
+        }
+    }
+
+    MergedBoards {
+        boards,
+        duplicates: duplicates.into_iter().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbn::read_pbn;
+    use crate::{Direction, Vulnerability};
+
+    const ROUND: &str = r#"
+[Board "1"]
+[Dealer "N"]
+[Vulnerable "None"]
+[Deal "N:K843.T542.J6.863 AQJ7.K.Q75.AT942 962.AJ7.KT82.J75 T5.Q9863.A943.KQ"]
+
+[Board "2"]
+[Dealer "E"]
+[Vulnerable "NS"]
+[Deal "E:Q7.AKT9.JT3.JT96 J653.QJ8.A.AQ732 K92.654.K954.K84 AT84.732.Q8762.5"]
+"#;
+
+    #[test]
+    fn test_merge_renumber() {
+        let merged = merge_boards(
+            vec![read_pbn(ROUND).unwrap(), read_pbn(ROUND).unwrap()],
+            true,
+        );
+
+        let numbers: Vec<_> = merged.boards.iter().map(|b| b.number).collect();
+        assert_eq!(numbers, vec![Some(1), Some(2), Some(3), Some(4)]);
+
+        let dealers: Vec<_> = merged.boards.iter().map(|b| b.dealer).collect();
+        assert_eq!(
+            dealers,
+            vec![
+                Some(Direction::North),
+                Some(Direction::East),
+                Some(Direction::South),
+                Some(Direction::West)
+            ]
+        );
+        assert_eq!(merged.boards[2].vulnerable, Vulnerability::EastWest);
+        assert_eq!(merged.boards[3].vulnerable, Vulnerability::Both);
+        assert!(merged.duplicates.is_empty());
+
+        // Deals are carried over unchanged
+        assert_eq!(
+            merged.boards[2].deal.to_pbn(Direction::North),
+            merged.boards[0].deal.to_pbn(Direction::North)
+        );
+    }
+
+    #[test]
+    fn test_merge_reports_duplicates() {
+        let merged = merge_boards(
+            vec![read_pbn(ROUND).unwrap(), read_pbn(ROUND).unwrap()],
+            false,
+        );
+
+        let numbers: Vec<_> = merged.boards.iter().map(|b| b.number).collect();
+        assert_eq!(numbers, vec![Some(1), Some(2), Some(1), Some(2)]);
+        assert_eq!(merged.duplicates, vec![1, 2]);
+    }
+}
@@ -1,11 +1,13 @@
 use crate::error::Result;
-use crate::{Board, Deal, Direction, Vulnerability};
+use crate::{Auction, Board, Deal, Direction, Hand, Strain, Vulnerability};
 use nom::{
     bytes::complete::{take_until, take_while1},
     character::complete::{char, space0},
     sequence::delimited,
     IResult, Parser,
 };
+use std::collections::{BTreeMap, HashMap};
+use std::io::BufRead;
 
 /// A parsed PBN tag pair
 #[derive(Debug, Clone)]
@@ -44,66 +46,383 @@ fn tag_pair(input: &str) -> IResult<&str, TagPair> {
 }
 
 /// Read boards from PBN content
+///
+/// Event, Site and Date carry forward: a board that omits them keeps the
+/// previous board's values. A tag value of `#` copies the previous value of
+/// the same tag, as the PBN standard allows.
 pub fn read_pbn(content: &str) -> Result<Vec<Board>> {
-    let mut boards = Vec::new();
-    let mut current_board = Board::new();
-    let mut has_content = false;
-    let mut in_commentary = false;
+    let mut parser = PbnParser::default();
+    let mut boards: Vec<Board> = content
+        .lines()
+        .filter_map(|line| parser.feed_line(line))
+        .collect();
+    boards.extend(parser.finish());
+    Ok(boards)
+}
+
+/// Double-dummy tricks for each declarer and strain, from an
+/// `[OptimumResultTable]` section
+pub type OptimumResultTable = Vec<(Direction, Strain, u8)>;
+
+/// Read the OptimumResultTable section of each board, keyed by board number
+///
+/// `Board` has no field for the table, so it is read separately from the
+/// boards. Sections on unnumbered boards, or whose column spec lacks a
+/// declarer, strain or result column, are skipped.
+pub fn read_optimum_result_tables(content: &str) -> BTreeMap<u32, OptimumResultTable> {
+    let mut parser = PbnParser {
+        optimum_tables: Some(Vec::new()),
+        ..PbnParser::default()
+    };
+    for line in content.lines() {
+        parser.feed_line(line);
+    }
+    parser.finish();
+    parser
+        .optimum_tables
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(number, table)| Some((number?, table)))
+        .collect()
+}
 
+/// The `[Scoring]` and `[Score]` tags of a board
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScoringTags {
+    /// Scoring method, e.g. "MP" or "IMP"
+    pub scoring: Option<String>,
+    /// Score of the played contract, e.g. "NS 620"
+    pub score: Option<String>,
+}
+
+/// Read the Scoring and Score tags of each board, keyed by board number
+///
+/// Like the OptimumResultTable, `Board` has no fields for these, so they are
+/// read separately from the boards. Unnumbered boards and boards with
+/// neither tag are skipped.
+pub fn read_scoring_tags(content: &str) -> BTreeMap<u32, ScoringTags> {
+    let mut parser = PbnParser {
+        scoring_tags: Some(Vec::new()),
+        ..PbnParser::default()
+    };
     for line in content.lines() {
-        let line = line.trim();
+        parser.feed_line(line);
+    }
+    parser.finish();
+    parser
+        .scoring_tags
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(number, tags)| Some((number?, tags)))
+        .collect()
+}
+
+/// Parse the data rows of an OptimumResultTable section
+///
+/// `columns` is the section's tag value, such as
+/// `"Declarer;Denomination\2R;Result\2R"`: column names separated by `;`,
+/// each optionally followed by a `\` format suffix. The declarer, strain
+/// (`Denomination` or `Strain`) and result (`Result` or `Tricks`) columns
+/// may be in any order. Returns `None` if any of them is missing; rows that
+/// can't be read are skipped.
+pub fn parse_optimum_result_table<S: AsRef<str>>(
+    columns: &str,
+    rows: &[S],
+) -> Option<OptimumResultTable> {
+    let names: Vec<&str> = columns
+        .split(';')
+        .map(|c| c.split('\\').next().unwrap_or("").trim())
+        .collect();
+    let column = |aliases: &[&str]| {
+        names
+            .iter()
+            .position(|n| aliases.iter().any(|a| n.eq_ignore_ascii_case(a)))
+    };
+    let declarer_col = column(&["Declarer"])?;
+    let strain_col = column(&["Denomination", "Strain"])?;
+    let result_col = column(&["Result", "Tricks"])?;
+
+    let table = rows
+        .iter()
+        .filter_map(|row| {
+            let fields: Vec<&str> = row.as_ref().split_whitespace().collect();
+            let declarer = fields
+                .get(declarer_col)?
+                .chars()
+                .next()
+                .and_then(|c| Direction::from_char(c.to_ascii_uppercase()))?;
+            let strain = parse_strain(fields.get(strain_col)?)?;
+            let tricks = fields.get(result_col)?.parse::<u8>().ok()?;
+            (tricks <= 13).then_some((declarer, strain, tricks))
+        })
+        .collect();
+    Some(table)
+}
+
+/// Parse a strain written as in PBN: `NT` (or `N`), `S`, `H`, `D` or `C`
+fn parse_strain(s: &str) -> Option<Strain> {
+    match s.to_ascii_uppercase().as_str() {
+        "NT" | "N" => Some(Strain::NoTrump),
+        "S" => Some(Strain::Spades),
+        "H" => Some(Strain::Hearts),
+        "D" => Some(Strain::Diamonds),
+        "C" => Some(Strain::Clubs),
+        _ => None,
+    }
+}
+
+/// Read boards lazily from buffered PBN input
+///
+/// Each board is yielded as soon as the blank line ending it is read, so
+/// only the board being parsed is held in memory. Tags carry forward as in
+/// [`read_pbn`]. A read error is yielded once and ends the iteration.
+pub fn read_pbn_from_reader<R: BufRead>(reader: R) -> PbnBoards<R> {
+    PbnBoards {
+        lines: reader.lines(),
+        parser: PbnParser::default(),
+        done: false,
+    }
+}
+
+/// Iterator over the boards of a PBN stream, from [`read_pbn_from_reader`]
+pub struct PbnBoards<R> {
+    lines: std::io::Lines<R>,
+    parser: PbnParser,
+    done: bool,
+}
+
+impl<R: BufRead> Iterator for PbnBoards<R> {
+    type Item = Result<Board>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        for line in self.lines.by_ref() {
+            match line {
+                Ok(line) => {
+                    if let Some(board) = self.parser.feed_line(&line) {
+                        return Some(Ok(board));
+                    }
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e.into()));
+                }
+            }
+        }
+        self.done = true;
+        self.parser.finish().map(Ok)
+    }
+}
+
+/// Line-by-line PBN parsing state
+struct PbnParser {
+    current_board: Board,
+    has_content: bool,
+    in_commentary: bool,
+    previous_values: HashMap<String, String>,
+    /// Dealer and call tokens of an `[Auction]` section being read
+    auction: Option<(Direction, Vec<String>)>,
+    /// Column spec and rows of an `[OptimumResultTable]` section being read
+    optimum: Option<(String, Vec<String>)>,
+    /// Finished OptimumResultTable sections with their board numbers, when
+    /// collecting them
+    optimum_tables: Option<Vec<(Option<u32>, OptimumResultTable)>>,
+    /// Scoring and Score tags of the board being read
+    scoring: ScoringTags,
+    /// Finished boards' Scoring and Score tags with their board numbers, when
+    /// collecting them
+    scoring_tags: Option<Vec<(Option<u32>, ScoringTags)>>,
+}
+
+impl Default for PbnParser {
+    fn default() -> Self {
+        PbnParser {
+            current_board: Board::new(),
+            has_content: false,
+            in_commentary: false,
+            previous_values: HashMap::new(),
+            auction: None,
+            optimum: None,
+            optimum_tables: None,
+            scoring: ScoringTags::default(),
+            scoring_tags: None,
+        }
+    }
+}
+
+impl PbnParser {
+    /// Consume one line, returning the board it completes, if any
+    fn feed_line(&mut self, line: &str) -> Option<Board> {
+        // Files saved by Windows editors may start with a UTF-8 byte order
+        // mark; `trim` already drops the `\r` of CRLF line endings
+        let line = line.trim_start_matches('\u{feff}').trim();
 
         // Track multi-line commentary blocks { ... }
         // Commentary can start and end on same line, or span multiple lines
-        if in_commentary {
+        if self.in_commentary {
             if line.contains('}') {
-                in_commentary = false;
+                self.in_commentary = false;
             }
-            continue;
+            return None;
         }
 
         // Check for start of commentary
         if line.starts_with('{') {
             // If closing brace on same line, it's a single-line comment
             if !line.contains('}') {
-                in_commentary = true;
+                self.in_commentary = true;
             }
-            continue;
+            return None;
         }
 
         // Empty line may signal end of board (but not inside commentary)
         if line.is_empty() {
-            if has_content {
-                boards.push(current_board);
-                current_board = Board::new();
-                has_content = false;
+            self.finish_sections();
+            if self.has_content {
+                self.finish_scoring();
+                let next_board = carried_forward(&self.current_board);
+                self.has_content = false;
+                return Some(std::mem::replace(&mut self.current_board, next_board));
             }
-            continue;
+            return None;
         }
 
         // Skip line comments and directives
         if line.starts_with(';') || line.starts_with('%') {
-            continue;
+            return None;
         }
 
         // Parse tag pair
         if line.starts_with('[') {
-            if let Ok((_, tag)) = tag_pair(line) {
-                has_content = true;
-                apply_tag_to_board(&mut current_board, &tag);
+            self.finish_sections();
+            if let Ok((_, mut tag)) = tag_pair(line) {
+                self.has_content = true;
+                if tag.value == "#" {
+                    if let Some(previous) = self.previous_values.get(&tag.name) {
+                        tag.value = previous.clone();
+                    }
+                }
+                if tag.name == "Auction" {
+                    if let Some(dealer) = tag.value.chars().next().and_then(Direction::from_char) {
+                        self.auction = Some((dealer, Vec::new()));
+                    }
+                }
+                if tag.name == "OptimumResultTable" && self.optimum_tables.is_some() {
+                    self.optimum = Some((tag.value.clone(), Vec::new()));
+                }
+                if self.scoring_tags.is_some() {
+                    let value = (!tag.value.is_empty()).then(|| tag.value.clone());
+                    match tag.name.as_str() {
+                        "Scoring" => self.scoring.scoring = value,
+                        "Score" => self.scoring.score = value,
+                        _ => {}
+                    }
+                }
+                apply_tag_to_board(&mut self.current_board, &tag);
+                self.previous_values.insert(tag.name, tag.value);
+            }
+            return None;
+        }
+
+        // Calls following an Auction tag
+        if let Some((_, tokens)) = self.auction.as_mut() {
+            tokens.extend(line.split_whitespace().map(str::to_string));
+            return None;
+        }
+
+        // Rows following an OptimumResultTable tag
+        if let Some((_, rows)) = self.optimum.as_mut() {
+            rows.push(line.to_string());
+            return None;
+        }
+
+        // Other data lines (like Play data) - skip for now
+        None
+    }
+
+    /// Store the Auction or OptimumResultTable section just ended, if any
+    fn finish_sections(&mut self) {
+        if let Some((dealer, tokens)) = self.auction.take() {
+            self.current_board.auction = Some(parse_auction(dealer, &tokens));
+        }
+        if let Some((columns, rows)) = self.optimum.take() {
+            if let (Some(tables), Some(table)) = (
+                self.optimum_tables.as_mut(),
+                parse_optimum_result_table(&columns, &rows),
+            ) {
+                tables.push((self.current_board.number, table));
+            }
+        }
+    }
+
+    /// Store the Scoring and Score tags of the board just ended, if collecting
+    fn finish_scoring(&mut self) {
+        let scoring = std::mem::take(&mut self.scoring);
+        if let Some(tags) = self.scoring_tags.as_mut() {
+            if scoring != ScoringTags::default() {
+                tags.push((self.current_board.number, scoring));
             }
-            continue;
         }
+    }
 
-        // Other data lines (like OptimumResultTable data) - skip for now
+    /// The last board, when the input doesn't end with a blank line
+    fn finish(&mut self) -> Option<Board> {
+        self.finish_sections();
+        if !self.has_content {
+            return None;
+        }
+        self.finish_scoring();
+        self.has_content = false;
+        Some(std::mem::replace(&mut self.current_board, Board::new()))
     }
+}
 
-    // Don't forget the last board
-    if has_content {
-        boards.push(current_board);
+/// Build an auction from the call tokens of a PBN Auction section
+///
+/// A note reference such as `=1=` annotates the call before it, and `AP`
+/// stands for the passes that close the auction: three after the last bid
+/// or double, or four when nobody has bid. Other tokens that aren't
+/// calls (`-`, `*`, `$` suffixes) are skipped.
+fn parse_auction(dealer: Direction, tokens: &[String]) -> Auction {
+    use crate::Call;
+
+    let mut calls: Vec<(Call, Option<String>)> = Vec::new();
+    for token in tokens {
+        if let Some(note) = token.strip_prefix('=').and_then(|t| t.strip_suffix('=')) {
+            if let Some((_, annotation)) = calls.last_mut() {
+                *annotation = Some(note.to_string());
+            }
+        } else if token.eq_ignore_ascii_case("AP") {
+            let trailing = calls
+                .iter()
+                .rev()
+                .take_while(|(call, _)| *call == Call::Pass)
+                .count();
+            let needed = if trailing == calls.len() { 4 } else { 3 };
+            for _ in trailing..needed {
+                calls.push((Call::Pass, None));
+            }
+        } else if let Some(call) = crate::auction::parse_call(token) {
+            calls.push((call, None));
+        }
     }
 
-    Ok(boards)
+    let mut auction = Auction::new(dealer);
+    for (call, annotation) in calls {
+        auction.add_annotated_call(call, annotation);
+    }
+    auction
+}
+
+/// A new board starting with the game-level tags of `previous`
+fn carried_forward(previous: &Board) -> Board {
+    let mut board = Board::new();
+    board.event = previous.event.clone();
+    board.site = previous.site.clone();
+    board.date = previous.date.clone();
+    board
 }
 
 /// Apply a parsed tag to a board
@@ -123,24 +442,19 @@ fn apply_tag_to_board(board: &mut Board, tag: &TagPair) {
             board.vulnerable = Vulnerability::from_pbn(&tag.value).unwrap_or_default();
         }
         "Deal" => {
-            if let Some(deal) = Deal::from_pbn(&tag.value) {
+            if let Some(deal) = parse_deal(&tag.value) {
                 board.deal = deal;
             }
         }
+        // An empty value clears a carried-forward one
         "Event" => {
-            if !tag.value.is_empty() {
-                board.event = Some(tag.value.clone());
-            }
+            board.event = (!tag.value.is_empty()).then(|| tag.value.clone());
         }
         "Site" => {
-            if !tag.value.is_empty() {
-                board.site = Some(tag.value.clone());
-            }
+            board.site = (!tag.value.is_empty()).then(|| tag.value.clone());
         }
         "Date" => {
-            if !tag.value.is_empty() {
-                board.date = Some(tag.value.clone());
-            }
+            board.date = (!tag.value.is_empty()).then(|| tag.value.clone());
         }
         "DoubleDummyTricks" => {
             board.double_dummy_tricks = Some(tag.value.clone());
@@ -151,16 +465,88 @@ fn apply_tag_to_board(board: &mut Board, tag: &TagPair) {
         "ParContract" => {
             board.par_contract = Some(tag.value.clone());
         }
+        "Contract" => {
+            if !tag.value.is_empty() {
+                board.contract = Some(tag.value.clone());
+            }
+        }
+        "Declarer" => {
+            if let Some(c) = tag.value.chars().next() {
+                board.declarer = Direction::from_char(c);
+            }
+        }
+        "Result" => {
+            board.result = tag.value.parse().ok();
+        }
         _ => {
             // Ignore other tags for now
         }
     }
 }
 
+/// Parse a PBN Deal tag value, filling in one omitted hand
+///
+/// PBN lets a deal leave one hand as `-` (or drop it entirely), in which case
+/// it holds every card not in the other three. Returns `None` if the given
+/// hands share a card or the inferred hand would not have 13 cards.
+pub fn parse_deal(value: &str) -> Option<Deal> {
+    let (first, hands) = value.trim().split_once(':')?;
+    let first = Direction::from_char(first.chars().next()?)?;
+    let hand_strs: Vec<&str> = hands.split_whitespace().collect();
+
+    let complete = hand_strs.len() == 4 && hand_strs.iter().all(|h| *h != "-");
+    if complete {
+        return Deal::from_pbn(value);
+    }
+    if hand_strs.len() < 3 || hand_strs.len() > 4 {
+        return None;
+    }
+
+    // Hands are listed clockwise from the first direction
+    let mut deal = Deal::new();
+    let mut missing = None;
+    let mut dir = first;
+    for i in 0..4 {
+        match hand_strs.get(i) {
+            Some(&h) if h != "-" => {
+                let hand = Hand::from_pbn(h)?;
+                for card in hand.cards().iter() {
+                    if Direction::ALL.iter().any(|&d| deal.hand(d).has_card(*card)) {
+                        return None;
+                    }
+                }
+                deal.set_hand(dir, hand);
+            }
+            _ => {
+                if missing.is_some() {
+                    return None;
+                }
+                missing = Some(dir);
+            }
+        }
+        dir = dir.next();
+    }
+
+    if let Some(missing) = missing {
+        let mut hand = Hand::new();
+        for card in crate::cards::deck() {
+            if !Direction::ALL.iter().any(|&d| deal.hand(d).has_card(card)) {
+                hand.add_card(card);
+            }
+        }
+        if hand.len() != 13 {
+            return None;
+        }
+        deal.set_hand(missing, hand);
+    }
+
+    Some(deal)
+}
+
 /// Read boards from a PBN file
 pub fn read_pbn_file(path: &std::path::Path) -> Result<Vec<Board>> {
-    let content = std::fs::read_to_string(path)?;
-    read_pbn(&content)
+    let file = std::fs::File::open(path)?;
+    read_pbn_from_reader(std::io::BufReader::new(file)).collect()
 }
 
 #[cfg(test)]
@@ -183,6 +569,45 @@ mod tests {
         assert_eq!(tag.name, "Deal");
     }
 
+    #[test]
+    fn test_parse_deal_with_dash_hand() {
+        let full =
+            parse_deal("N:K843.T542.J6.863 AQJ7.K.Q75.AT942 962.AJ7.KT82.J75 T5.Q9863.A943.KQ")
+                .unwrap();
+        let deal = parse_deal("N:K843.T542.J6.863 AQJ7.K.Q75.AT942 962.AJ7.KT82.J75 -").unwrap();
+
+        assert_eq!(deal.hand(Direction::West).len(), 13);
+        assert_eq!(
+            deal.hand(Direction::West).to_pbn(),
+            full.hand(Direction::West).to_pbn()
+        );
+        assert_eq!(deal.to_pbn(Direction::North), full.to_pbn(Direction::North));
+    }
+
+    #[test]
+    fn test_parse_deal_dash_first_hand() {
+        let deal = parse_deal("E:- 962.AJ7.KT82.J75 T5.Q9863.A943.KQ K843.T542.J6.863").unwrap();
+        let east = Hand::from_pbn("AQJ7.K.Q75.AT942").unwrap();
+        assert_eq!(deal.hand(Direction::East).to_pbn(), east.to_pbn());
+    }
+
+    #[test]
+    fn test_parse_deal_three_hands() {
+        let deal = parse_deal("N:K843.T542.J6.863 AQJ7.K.Q75.AT942 962.AJ7.KT82.J75").unwrap();
+        let west = Hand::from_pbn("T5.Q9863.A943.KQ").unwrap();
+        assert_eq!(deal.hand(Direction::West).to_pbn(), west.to_pbn());
+    }
+
+    #[test]
+    fn test_parse_deal_rejects_bad_hands() {
+        // South repeats North's spade king
+        assert!(parse_deal("N:K843.T542.J6.863 AQJ7.K.Q75.AT942 K62.AJ7.KT82.J75 -").is_none());
+        // Short hands leave more than 13 cards for the missing one
+        assert!(parse_deal("N:K843.T542.J6.86 AQJ7.K.Q75.AT942 962.AJ7.KT82.J75 -").is_none());
+        // Only one hand may be omitted
+        assert!(parse_deal("N:K843.T542.J6.863 - 962.AJ7.KT82.J75 -").is_none());
+    }
+
     #[test]
     fn test_read_simple_pbn() {
         let pbn = r#"
@@ -262,4 +687,178 @@ several lines with blank lines inside.}
             "E:Q7.AKT9.JT3.JT96 J653.QJ8.A.AQ732 K92.654.K954.K84 AT84.732.Q8762.5"
         );
     }
+
+    #[test]
+    fn test_event_carries_forward() {
+        let pbn = r#"
+[Event "Tuesday Pairs"]
+[Site "Club"]
+[Board "1"]
+[Deal "N:K843.T542.J6.863 AQJ7.K.Q75.AT942 962.AJ7.KT82.J75 T5.Q9863.A943.KQ"]
+
+[Board "2"]
+[Deal "E:Q7.AKT9.JT3.JT96 J653.QJ8.A.AQ732 K92.654.K954.K84 AT84.732.Q8762.5"]
+
+[Site ""]
+[Board "3"]
+"#;
+        let boards = read_pbn(pbn).unwrap();
+        assert_eq!(boards.len(), 3);
+        assert_eq!(boards[1].event.as_deref(), Some("Tuesday Pairs"));
+        assert_eq!(boards[1].site.as_deref(), Some("Club"));
+        assert_eq!(boards[2].event.as_deref(), Some("Tuesday Pairs"));
+        assert_eq!(boards[2].site, None);
+    }
+
+    #[test]
+    fn test_hash_copies_previous_value() {
+        let pbn = r##"
+[Event "Tuesday Pairs"]
+[Board "4"]
+[Dealer "W"]
+
+[Event "#"]
+[Board "#"]
+[Dealer "#"]
+"##;
+        let boards = read_pbn(pbn).unwrap();
+        assert_eq!(boards[1].event.as_deref(), Some("Tuesday Pairs"));
+        assert_eq!(boards[1].number, Some(4));
+        assert_eq!(boards[1].dealer, Some(Direction::West));
+    }
+
+    #[test]
+    fn test_read_pbn_from_reader_is_lazy() {
+        // The second board is unreadable; the first is yielded before it
+        // is reached
+        let mut input = b"[Event \"Club\"]\n[Board \"1\"]\n\n[Board \"2\"]\n".to_vec();
+        input.extend_from_slice(&[0xff, b'\n']);
+
+        let mut boards = read_pbn_from_reader(&input[..]);
+        let first = boards.next().unwrap().unwrap();
+        assert_eq!(first.number, Some(1));
+        assert_eq!(first.event.as_deref(), Some("Club"));
+        assert!(boards.next().unwrap().is_err());
+        assert!(boards.next().is_none());
+    }
+
+    #[test]
+    fn test_read_pbn_from_reader_two_boards() {
+        let input = "[Event \"Club\"]\n[Board \"1\"]\n\n[Board \"2\"]\n";
+        let mut boards = read_pbn_from_reader(input.as_bytes());
+        assert_eq!(boards.next().unwrap().unwrap().number, Some(1));
+        let second = boards.next().unwrap().unwrap();
+        assert_eq!(second.number, Some(2));
+        assert_eq!(second.event.as_deref(), Some("Club"));
+        assert!(boards.next().is_none());
+    }
+
+    #[test]
+    fn test_read_auction() {
+        let pbn = "\
+[Board \"1\"]
+[Dealer \"E\"]
+[Auction \"E\"]
+1NT =1= Pass 3NT AP
+[Note \"1:15-17\"]
+";
+        let boards = read_pbn(pbn).unwrap();
+        let auction = boards[0].auction.as_ref().unwrap();
+        let calls: Vec<String> = auction.calls.iter().map(|c| c.call.to_pbn()).collect();
+        assert_eq!(calls.len(), 6);
+        assert_eq!(calls[0], crate::Call::from_pbn("1NT").unwrap().to_pbn());
+        assert_eq!(auction.calls[0].annotation.as_deref(), Some("1"));
+        assert!(auction.calls[3..]
+            .iter()
+            .all(|c| c.call == crate::Call::Pass));
+
+        // A passed-out board closes after four passes, however AP is reached
+        for tokens in ["AP", "Pass AP", "Pass Pass Pass AP"] {
+            let pbn = format!("[Board \"2\"]\n[Dealer \"N\"]\n[Auction \"N\"]\n{tokens}\n");
+            let boards = read_pbn(&pbn).unwrap();
+            let auction = boards[0].auction.as_ref().unwrap();
+            assert_eq!(auction.calls.len(), 4, "{tokens}");
+            assert!(auction.calls.iter().all(|c| c.call == crate::Call::Pass));
+        }
+    }
+
+    #[test]
+    fn test_read_optimum_result_table() {
+        let pbn = "\
+[Board \"1\"]
+[Dealer \"N\"]
+[Vulnerable \"None\"]
+[Deal \"N:K843.T542.J6.863 AQJ7.K.Q75.AT942 962.AJ7.KT82.J75 T5.Q9863.A943.KQ\"]
+[OptimumScore \"EW 420\"]
+[OptimumResultTable \"Declarer;Denomination\\2R;Result\\2R\"]
+N NT  4
+N  S  4
+N  H  5
+N  D  5
+N  C  3
+S NT  4
+S  S  4
+S  H  5
+S  D  5
+S  C  3
+E NT  9
+E  S  9
+E  H  8
+E  D  8
+E  C 10
+W NT  9
+W  S  9
+W  H  8
+W  D  8
+W  C 10
+
+[Board \"2\"]
+[Dealer \"E\"]
+";
+        let tables = read_optimum_result_tables(pbn);
+        assert_eq!(tables.len(), 1);
+
+        let table = &tables[&1];
+        assert_eq!(table.len(), 20);
+        assert_eq!(table[0], (Direction::North, Strain::NoTrump, 4));
+        assert!(table.contains(&(Direction::East, Strain::Clubs, 10)));
+        assert!(table.contains(&(Direction::West, Strain::Hearts, 8)));
+
+        // The boards themselves read as before
+        let boards = read_pbn(pbn).unwrap();
+        assert_eq!(boards.len(), 2);
+        assert_eq!(boards[0].optimum_score.as_deref(), Some("EW 420"));
+    }
+
+    #[test]
+    fn test_parse_optimum_result_table_columns() {
+        // Columns in another order, with a bad row
+        let rows = ["10 C S", "9 NT N", "x H E"];
+        let table = parse_optimum_result_table("Result;Denomination;Declarer", &rows).unwrap();
+        assert_eq!(
+            table,
+            vec![
+                (Direction::South, Strain::Clubs, 10),
+                (Direction::North, Strain::NoTrump, 9),
+            ]
+        );
+
+        assert!(parse_optimum_result_table("Declarer;Result", &rows).is_none());
+    }
+
+    #[test]
+    fn test_read_pbn_with_bom_and_crlf() {
+        let pbn =
+            "\u{feff}[Board \"1\"]\r\n[Dealer \"N\"]\r\n\r\n[Board \"2\"]\r\n[Dealer \"E\"]\r\n";
+        let boards = read_pbn(pbn).unwrap();
+        assert_eq!(boards.len(), 2);
+        assert_eq!(boards[0].number, Some(1));
+        assert_eq!(boards[0].dealer, Some(Direction::North));
+        assert_eq!(boards[1].dealer, Some(Direction::East));
+
+        let boards: Vec<Board> = read_pbn_from_reader(pbn.as_bytes())
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(boards[0].number, Some(1));
+    }
 }
@@ -1,7 +1,15 @@
+use super::reader::ScoringTags;
 use crate::{Auction, Board, Direction, PlaySequence};
+use std::collections::BTreeMap;
 
 /// Write boards to PBN format
 pub fn write_pbn(boards: &[Board]) -> String {
+    write_pbn_with_scoring(boards, &BTreeMap::new())
+}
+
+/// Write boards to PBN format with their Scoring and Score tags, keyed by
+/// board number as returned by [`super::reader::read_scoring_tags`]
+pub fn write_pbn_with_scoring(boards: &[Board], scoring: &BTreeMap<u32, ScoringTags>) -> String {
     let mut output = String::new();
 
     // PBN header
@@ -13,7 +21,8 @@ pub fn write_pbn(boards: &[Board]) -> String {
         if i > 0 {
             output.push('\n');
         }
-        output.push_str(&board_to_pbn(board));
+        let tags = board.number.and_then(|n| scoring.get(&n));
+        output.push_str(&board_to_pbn_with_scoring(board, tags));
     }
 
     output
@@ -21,6 +30,11 @@ pub fn write_pbn(boards: &[Board]) -> String {
 
 /// Convert a single board to PBN format
 pub fn board_to_pbn(board: &Board) -> String {
+    board_to_pbn_with_scoring(board, None)
+}
+
+/// Convert a single board to PBN format, with its Scoring and Score tags
+pub fn board_to_pbn_with_scoring(board: &Board, scoring: Option<&ScoringTags>) -> String {
     let mut lines = Vec::new();
 
     // Event tag
@@ -87,7 +101,8 @@ pub fn board_to_pbn(board: &Board) -> String {
     lines.push(format!("[Deal \"{}\"]", board.deal.to_pbn(first_dir)));
 
     // Scoring
-    lines.push("[Scoring \"\"]".to_string());
+    let scoring_method = scoring.and_then(|t| t.scoring.as_deref());
+    lines.push(format!("[Scoring \"{}\"]", scoring_method.unwrap_or("")));
 
     // Declarer
     if let Some(declarer) = board.declarer {
@@ -110,6 +125,11 @@ pub fn board_to_pbn(board: &Board) -> String {
         lines.push("[Result \"\"]".to_string());
     }
 
+    // Score, when known
+    if let Some(score) = scoring.and_then(|t| t.score.as_deref()) {
+        lines.push(format!("[Score \"{}\"]", score));
+    }
+
     // Analysis tags if present
     if let Some(ref dd) = board.double_dummy_tricks {
         lines.push(format!("[DoubleDummyTricks \"{}\"]", dd));
@@ -175,7 +195,7 @@ fn format_play(play: &PlaySequence) -> String {
             .cards
             .iter()
             .map(|opt| {
-                opt.map(|c| format!("{}{}", c.suit.to_char(), c.rank.to_char()))
+                opt.map(crate::cards::card_to_pbn)
                     .unwrap_or_else(|| "-".to_string())
             })
             .collect();
@@ -225,4 +245,90 @@ mod tests {
         assert!(pbn.starts_with("% PBN 2.1\n"));
         assert!(pbn.contains("% EXPORT"));
     }
+
+    #[test]
+    fn test_result_tags_round_trip() {
+        let pbn = "\
+[Board \"3\"]
+[Dealer \"S\"]
+[Vulnerable \"EW\"]
+[Deal \"N:K843.T542.J6.863 AQJ7.K.Q75.AT942 962.AJ7.KT82.J75 T5.Q9863.A943.KQ\"]
+[Scoring \"MP\"]
+[Declarer \"E\"]
+[Contract \"4SX\"]
+[Result \"9\"]
+[Score \"NS 500\"]
+";
+        let boards = crate::pbn::read_pbn(pbn).unwrap();
+        assert_eq!(boards[0].contract.as_deref(), Some("4SX"));
+        assert_eq!(boards[0].declarer, Some(Direction::East));
+        assert_eq!(boards[0].result, Some(9));
+        let scoring = crate::pbn::reader::read_scoring_tags(pbn);
+        let expected = ScoringTags {
+            scoring: Some("MP".to_string()),
+            score: Some("NS 500".to_string()),
+        };
+        assert_eq!(scoring.get(&3), Some(&expected));
+
+        let written = write_pbn_with_scoring(&boards, &scoring);
+        assert!(written.contains("[Scoring \"MP\"]"));
+        assert!(written.contains("[Declarer \"E\"]"));
+        assert!(written.contains("[Contract \"4SX\"]"));
+        assert!(written.contains("[Result \"9\"]"));
+        assert!(written.contains("[Score \"NS 500\"]"));
+
+        let round_trip = crate::pbn::read_pbn(&written).unwrap();
+        assert_eq!(round_trip[0].contract, boards[0].contract);
+        assert_eq!(round_trip[0].declarer, boards[0].declarer);
+        assert_eq!(round_trip[0].result, boards[0].result);
+        assert_eq!(crate::pbn::reader::read_scoring_tags(&written), scoring);
+
+        // Without scoring tags the Scoring tag is written empty and Score omitted
+        let plain = board_to_pbn(&boards[0]);
+        assert!(plain.contains("[Scoring \"\"]"));
+        assert!(!plain.contains("[Score "));
+    }
+
+    #[test]
+    fn test_auction_round_trip() {
+        use crate::{Auction, Call};
+
+        let deal =
+            Deal::from_pbn("N:K843.T542.J6.863 AQJ7.K.Q75.AT942 962.AJ7.KT82.J75 T5.Q9863.A943.KQ")
+                .unwrap();
+        let mut auction = Auction::new(Direction::East);
+        for call in [
+            "1S", "Pass", "2NT", "X", "3S", "Pass", "4S", "Pass", "Pass", "Pass",
+        ] {
+            let call = match call {
+                "Pass" => Call::Pass,
+                "X" => Call::Double,
+                bid => Call::from_pbn(bid).unwrap(),
+            };
+            auction.add_annotated_call(call, None);
+        }
+        let mut board = Board::new()
+            .with_number(2)
+            .with_dealer(Direction::East)
+            .with_vulnerability(Vulnerability::NorthSouth)
+            .with_deal(deal)
+            .with_auction(auction);
+        board.contract = Some("4S".to_string());
+        board.declarer = Some(Direction::East);
+        board.result = Some(10);
+
+        let written = board_to_pbn(&board);
+        assert!(written.contains("[Auction \"E\"]"));
+
+        let round_trip = crate::pbn::read_pbn(&written).unwrap();
+        let original = board.auction.as_ref().unwrap();
+        let parsed = round_trip[0].auction.as_ref().unwrap();
+        assert_eq!(parsed.calls.len(), original.calls.len());
+        for (a, b) in parsed.calls.iter().zip(&original.calls) {
+            assert_eq!(a.call, b.call);
+        }
+        assert_eq!(round_trip[0].contract.as_deref(), Some("4S"));
+        assert_eq!(round_trip[0].declarer, Some(Direction::East));
+        assert_eq!(round_trip[0].result, Some(10));
+    }
 }
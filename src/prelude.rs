@@ -0,0 +1,24 @@
+//! Commonly used types, for glob import
+//!
+//! ```
+//! use bridge_parsers::prelude::*;
+//!
+//! let deal = Deal::from_pbn(
+//!     "N:K843.T542.J6.863 AQJ7.K.Q75.AT942 962.AJ7.KT82.J75 T5.Q9863.A943.KQ",
+//! )
+//! .unwrap();
+//! let board = Board::new()
+//!     .with_number(1)
+//!     .with_dealer(Direction::North)
+//!     .with_vulnerability(Vulnerability::None)
+//!     .with_deal(deal);
+//!
+//! let north: &Hand = board.deal.hand(Direction::North);
+//! assert!(north.has_card(Card::new(Suit::Spades, Rank::King)));
+//! assert_eq!(Contract::parse("3NT").map(|c| c.strain), Some(Strain::NoTrump));
+//! ```
+
+pub use crate::{
+    Board, BridgeError, Card, Contract, Deal, Direction, Hand, Rank, Result, Strain, Suit,
+    Vulnerability,
+};
@@ -0,0 +1,1001 @@
+//! Duplicate scoring helpers shared by the xlsx writer and the CLI
+//!
+//! Rubber bridge scoring lives in [`rubber`], and a reference score table
+//! for checking `Contract::score` in [`table`].
+//!
+//! Scores are always reported from the North-South perspective so that
+//! results from different tables can be compared directly.
+
+pub mod rubber;
+pub mod table;
+
+pub use table::{score_table, verify_against_table, ScoreTableEntry};
+
+use crate::bws::tables::ReceivedDataRow;
+use crate::validate::{same_side, Revoke};
+use crate::{Board, Contract, Direction, Doubled, Strain, Suit, Vulnerability};
+
+/// Check whether a contract string denotes a passed-out board
+///
+/// BWS stores these as "PASS" (or leaves the contract empty), PBN as "Pass".
+pub fn is_passed_out(contract: &str) -> bool {
+    let contract = contract.trim();
+    contract.is_empty() || contract.eq_ignore_ascii_case("pass") || contract == "-"
+}
+
+/// Parse a declarer direction from a BWS `NS/EW` field or PBN tag ("N", "E", "S", "W")
+pub fn parse_declarer(s: &str) -> Option<Direction> {
+    match s.trim() {
+        "N" => Some(Direction::North),
+        "S" => Some(Direction::South),
+        "E" => Some(Direction::East),
+        "W" => Some(Direction::West),
+        _ => None,
+    }
+}
+
+/// Parse a contract string with an optional trailing declarer
+///
+/// Accepts the plain forms understood by `Contract::parse` ("4S", "3NTX") as
+/// well as forms naming declarer, either space-separated ("3NT E", "6HXX S")
+/// or appended ("4SXW"). A trailing letter is only treated as declarer when
+/// the rest still parses as a contract, so "3N" remains 3 notrump.
+pub fn parse_contract_with_declarer(s: &str) -> Option<(Contract, Option<Direction>)> {
+    let s = s.trim();
+
+    if let Some((contract_str, declarer_str)) = s.rsplit_once(char::is_whitespace) {
+        let declarer = parse_declarer(declarer_str)?;
+        let contract = Contract::parse(contract_str.trim())?;
+        return Some((contract, Some(declarer)));
+    }
+
+    if s.len() > 2 {
+        let (contract_str, last) = s.split_at(s.len() - 1);
+        if let Some(declarer) = parse_declarer(&last.to_uppercase()) {
+            if let Some(contract) = Contract::parse(contract_str) {
+                return Some((contract, Some(declarer)));
+            }
+        }
+    }
+
+    Contract::parse(s).map(|c| (c, None))
+}
+
+/// Strain of a contract string, which may name declarer ("4S N", "6HXXE")
+///
+/// Only the contract's own strain counts: in "4S N" the N is North, while
+/// "3N" and "3NT" are notrump.
+pub fn strain_from_contract(s: &str) -> Option<Strain> {
+    parse_contract_with_declarer(s).map(|(contract, _)| contract.strain)
+}
+
+/// Trump suit for a strain, or `None` for notrump
+pub fn strain_trump(strain: Strain) -> Option<Suit> {
+    match strain {
+        Strain::Clubs => Some(Suit::Clubs),
+        Strain::Diamonds => Some(Suit::Diamonds),
+        Strain::Hearts => Some(Suit::Hearts),
+        Strain::Spades => Some(Suit::Spades),
+        Strain::NoTrump => None,
+    }
+}
+
+/// Strain played with a suit as trumps
+pub fn strain_from_suit(suit: Suit) -> Strain {
+    match suit {
+        Suit::Clubs => Strain::Clubs,
+        Suit::Diamonds => Strain::Diamonds,
+        Suit::Hearts => Strain::Hearts,
+        Suit::Spades => Strain::Spades,
+    }
+}
+
+/// Contract-string suffix for a doubling state: "", "X" or "XX"
+pub fn doubled_suffix(doubled: Doubled) -> &'static str {
+    match doubled {
+        Doubled::None => "",
+        Doubled::Doubled => "X",
+        Doubled::Redoubled => "XX",
+    }
+}
+
+/// Parse a doubling suffix as written by [`doubled_suffix`], in any case
+pub fn parse_doubled(s: &str) -> Option<Doubled> {
+    match s.trim().to_uppercase().as_str() {
+        "" => Some(Doubled::None),
+        "X" => Some(Doubled::Doubled),
+        "XX" => Some(Doubled::Redoubled),
+        _ => None,
+    }
+}
+
+/// Strain index in double-dummy solver order: spades 0, hearts 1,
+/// diamonds 2, clubs 3, notrump 4
+pub fn strain_solver_index(strain: Strain) -> usize {
+    match strain {
+        Strain::Spades => 0,
+        Strain::Hearts => 1,
+        Strain::Diamonds => 2,
+        Strain::Clubs => 3,
+        Strain::NoTrump => 4,
+    }
+}
+
+/// Tricks transferred for an established revoke (Law 64A)
+///
+/// When the revoking player won the revoke trick, that trick and one more
+/// later trick won by their side are transferred. Otherwise one trick is
+/// transferred if their side won the revoke trick or any later trick.
+pub fn revoke_penalty_tricks(revoke: &Revoke) -> u8 {
+    let cap = if revoke.won_revoke_trick { 2 } else { 1 };
+    revoke.side_tricks.min(cap) as u8
+}
+
+/// Declarer's trick count after transferring tricks for revokes
+///
+/// A defender's revoke gives declarer tricks; a revoke by declarer or dummy
+/// takes them away. A repeated revoke in the same suit by the same player
+/// carries no further penalty (Law 64B2).
+pub fn apply_revoke_penalty(tricks: u8, declarer: Direction, revokes: &[Revoke]) -> u8 {
+    let mut penalized: Vec<(Direction, Suit)> = Vec::new();
+    let mut tricks = tricks as i32;
+    for revoke in revokes {
+        if penalized.contains(&(revoke.seat, revoke.led)) {
+            continue;
+        }
+        penalized.push((revoke.seat, revoke.led));
+
+        let transfer = revoke_penalty_tricks(revoke) as i32;
+        if same_side(revoke.seat, declarer) {
+            tricks -= transfer;
+        } else {
+            tricks += transfer;
+        }
+    }
+    tricks.clamp(0, 13) as u8
+}
+
+/// Convert a declarer-perspective score to the North-South perspective
+pub fn to_ns_perspective(score: i32, declarer: Direction) -> i32 {
+    match declarer {
+        Direction::North | Direction::South => score,
+        Direction::East | Direction::West => -score,
+    }
+}
+
+/// Number of tricks declarer needs to make a contract (level + 6)
+pub fn tricks_needed(contract: &Contract) -> u8 {
+    contract.level + 6
+}
+
+/// Whether a contract is worth a game bonus when made
+///
+/// Game needs 100 trick points, counting doubling: 3NT, 4H/4S and 5C/5D
+/// are games, and so is 2SX (120). Vulnerability affects the size of the
+/// bonus, not whether there is one.
+pub fn is_game(contract: &Contract) -> bool {
+    let multiplier = match contract.doubled {
+        Doubled::None => 1,
+        Doubled::Doubled => 2,
+        Doubled::Redoubled => 4,
+    };
+    contract_trick_points(contract.strain, contract.level as i32) * multiplier >= 100
+}
+
+/// Whether a contract is a small or grand slam
+pub fn is_slam(contract: &Contract) -> bool {
+    contract.level >= 6
+}
+
+/// Whether a result relative to the contract (0 = just made) makes it
+pub fn is_making(tricks_relative: i32) -> bool {
+    tricks_relative >= 0
+}
+
+/// Undoubled trick points for `level` tricks in a strain
+pub(crate) fn contract_trick_points(strain: Strain, level: i32) -> i32 {
+    match strain {
+        Strain::Clubs | Strain::Diamonds => 20 * level,
+        Strain::Hearts | Strain::Spades => 30 * level,
+        Strain::NoTrump => 40 + 30 * (level - 1),
+    }
+}
+
+/// Interpret the BWS `Contract` and `NS/EW` fields as a contract and declarer
+///
+/// Bridgemate writes contracts with spaces and lowercase doubles ("4 S",
+/// "3 NT x", "6 H xx"); plain forms ("4S", "3NX") and a trailing declarer
+/// ("3NT E") are accepted too. "N" and "NT" are both notrump. Returns
+/// `None` for passed-out boards ("PASS" or blank) and anything unreadable.
+///
+/// Declarer comes from `ns_ew`, or from the contract when `ns_ew` is blank.
+/// When both name a declarer and they disagree, a warning is logged and
+/// `ns_ew` wins.
+pub fn contract_from_bws_fields(contract: &str, ns_ew: &str) -> Option<(Contract, Direction)> {
+    if is_passed_out(contract) {
+        return None;
+    }
+
+    let compact: String = contract
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_uppercase();
+    let (parsed, named) = parse_contract_with_declarer(&compact)?;
+    let declarer = match (parse_declarer(ns_ew), named) {
+        (Some(declarer), Some(named)) if named != declarer => {
+            log::warn!(
+                "Contract {} names declarer {} but NS/EW column says {}",
+                contract,
+                named,
+                declarer
+            );
+            declarer
+        }
+        (Some(declarer), _) => declarer,
+        (None, named) => named?,
+    };
+    Some((parsed, declarer))
+}
+
+/// A played contract with its outcome, as needed to score it
+#[derive(Debug, Clone)]
+pub struct ContractResult {
+    pub contract: Contract,
+    /// Tricks over (positive) or under (negative) the contract
+    pub tricks_relative: i32,
+    pub declarer: Direction,
+    /// Whether declarer's side is vulnerable
+    pub vulnerable: bool,
+}
+
+impl ContractResult {
+    /// Parse a BWS-style result: contract ("4S", "3NTX E"), result ("=",
+    /// "+1", "-2"), declarer ("N", "E", "S", "W") and board number, which
+    /// gives the vulnerability
+    ///
+    /// Returns `None` for passed-out boards and for anything that can't be
+    /// interpreted; see [`contract_from_bws_fields`].
+    pub fn parse(contract: &str, result: &str, ns_ew: &str, board: u32) -> Option<Self> {
+        let (parsed, declarer) = contract_from_bws_fields(contract, ns_ew)?;
+        let tricks_relative = Contract::parse_result(result)?;
+        let vulnerable = Vulnerability::from_board_number(board).is_vulnerable(declarer);
+
+        Some(Self {
+            contract: parsed,
+            tricks_relative,
+            declarer,
+            vulnerable,
+        })
+    }
+
+    /// Number of tricks declarer took
+    pub fn tricks_made(&self) -> i32 {
+        i32::from(tricks_needed(&self.contract)) + self.tricks_relative
+    }
+
+    /// Score from declarer's perspective
+    pub fn score(&self) -> i32 {
+        self.contract.score(self.tricks_relative, self.vulnerable)
+    }
+
+    /// Score from the North-South perspective
+    pub fn ns_score(&self) -> i32 {
+        to_ns_perspective(self.score(), self.declarer)
+    }
+}
+
+/// NS and EW matchpoint percentages of one result
+///
+/// A compared result splits 100% between the sides, but a director-assigned
+/// average such as "Ave+/Ave+" gives each side its own percentage.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MatchpointPct {
+    /// North-South percentage
+    pub ns: f64,
+    /// East-West percentage
+    pub ew: f64,
+}
+
+impl MatchpointPct {
+    /// NS percentage of a compared result, EW receiving the remainder of 100%
+    pub fn from_ns(ns: f64) -> Self {
+        Self { ns, ew: 100.0 - ns }
+    }
+
+    /// Percentage of the NS or EW side
+    pub fn side(&self, is_ns: bool) -> f64 {
+        if is_ns {
+            self.ns
+        } else {
+            self.ew
+        }
+    }
+}
+
+/// Matchpoint percentages of a director-assigned average score
+///
+/// Recognized tokens, in any case: "Ave+" or "A+" (60%), "Ave" or "A" (50%)
+/// and "Ave-" or "A-" (40%); "Av" may stand for "Ave". A split such as
+/// "Ave+/Ave-" gives NS the first half and EW the second, so both sides may
+/// get Ave+ when neither is at fault; a single token gives EW the remainder
+/// of 100%. The token may be the whole contract or result field, or the
+/// remarks of a row that doesn't otherwise score, such as one whose contract
+/// was left blank.
+pub fn adjusted_score_pct(result: &ReceivedDataRow) -> Option<MatchpointPct> {
+    let fields = [Some(result.contract.as_str()), Some(result.result.as_str())];
+    let remarks = calculate_score_for_result(result)
+        .is_none()
+        .then_some(result.remarks.as_deref())
+        .flatten();
+    fields
+        .into_iter()
+        .chain(std::iter::once(remarks))
+        .flatten()
+        .find_map(average_field_pct)
+}
+
+/// Percentages for an average token or an NS/EW split of two tokens
+fn average_field_pct(field: &str) -> Option<MatchpointPct> {
+    match field.split_once('/') {
+        Some((ns, ew)) => Some(MatchpointPct {
+            ns: average_token_pct(ns)?,
+            ew: average_token_pct(ew)?,
+        }),
+        None => average_token_pct(field).map(MatchpointPct::from_ns),
+    }
+}
+
+/// Percentage for a single average token
+fn average_token_pct(token: &str) -> Option<f64> {
+    let token = token.trim().to_uppercase();
+    let (base, modifier) = match token.strip_suffix(['+', '-']) {
+        Some(base) => (base, token.chars().last()),
+        None => (token.as_str(), None),
+    };
+    if !matches!(base.trim(), "AVE" | "AV" | "A") {
+        return None;
+    }
+    Some(match modifier {
+        Some('+') => 60.0,
+        Some('-') => 40.0,
+        _ => 50.0,
+    })
+}
+
+/// Calculate the NS score for a BWS result row
+///
+/// Passed-out boards score 0. Returns `None` when the contract, result, or
+/// declarer can't be interpreted, or when a blank contract stands for the
+/// average recorded in the remarks rather than a pass-out.
+pub fn calculate_score_for_result(result: &ReceivedDataRow) -> Option<i32> {
+    if is_passed_out(&result.contract) {
+        let remarks_average = result.remarks.as_deref().and_then(average_field_pct);
+        if result.contract.trim().is_empty() && remarks_average.is_some() {
+            return None;
+        }
+        return Some(0);
+    }
+
+    ContractResult::parse(
+        &result.contract,
+        &result.result,
+        &result.ns_ew,
+        result.board as u32,
+    )
+    .map(|r| r.ns_score())
+}
+
+/// Calculate the NS score for a board carrying `[Contract]`, `[Declarer]` and `[Result]`
+///
+/// The PBN result is the number of tricks taken by declarer. Passed-out
+/// boards score 0.
+pub fn calculate_score_for_board(board: &Board) -> Option<i32> {
+    let contract_str = board.contract.as_deref()?;
+    if is_passed_out(contract_str) {
+        return Some(0);
+    }
+
+    let contract = Contract::parse(contract_str)?;
+    let declarer = board.declarer?;
+    let tricks_taken = board.result? as i32;
+    let result = ContractResult {
+        tricks_relative: tricks_taken - i32::from(tricks_needed(&contract)),
+        contract,
+        declarer,
+        vulnerable: board.vulnerable.is_vulnerable(declarer),
+    };
+
+    Some(result.ns_score())
+}
+
+/// NS score of every contract makeable double-dummy on a board
+///
+/// `table` holds declarer tricks per (declarer, strain), as read from a PBN
+/// `[OptimumResultTable]`. Each entry with at least seven tricks gives every
+/// undoubled contract from the one level up to the highest it makes, scored
+/// with any overtricks at the board's vulnerability. Entries keep the table's
+/// order, lowest level first within each declarer and strain.
+pub fn score_matrix(
+    board: &Board,
+    table: &[(Direction, Strain, u8)],
+) -> Vec<(Contract, Direction, i32)> {
+    let mut matrix = Vec::new();
+    for &(declarer, strain, tricks) in table {
+        let tricks = tricks.min(13);
+        let letter = strain_trump(strain).map_or('N', |suit| suit.to_char());
+        for level in 1..=tricks.saturating_sub(6) {
+            let Some(contract) = Contract::parse(&format!("{}{}", level, letter)) else {
+                continue;
+            };
+            let result = ContractResult {
+                tricks_relative: i32::from(tricks) - i32::from(tricks_needed(&contract)),
+                contract: contract.clone(),
+                declarer,
+                vulnerable: board.vulnerable.is_vulnerable(declarer),
+            };
+            matrix.push((contract, declarer, result.ns_score()));
+        }
+    }
+    matrix
+}
+
+/// Convert a score difference to IMPs using the standard WBF scale
+pub fn imps_for_difference(diff: i32) -> i32 {
+    const IMP_SCALE: [i32; 24] = [
+        20, 50, 90, 130, 170, 220, 270, 320, 370, 430, 500, 600, 750, 900, 1100, 1300, 1500, 1750,
+        2000, 2250, 2500, 3000, 3500, 4000,
+    ];
+
+    let imps = IMP_SCALE.iter().take_while(|&&t| diff.abs() >= t).count() as i32;
+    if diff < 0 {
+        -imps
+    } else {
+        imps
+    }
+}
+
+/// Calculate cross-IMPs for a set of NS scores on one board
+///
+/// Each score is compared against every other score on the board and the
+/// IMPs are averaged over the number of comparisons.
+pub fn calculate_cross_imps(scores_ns: &[i32]) -> Vec<f64> {
+    if scores_ns.len() < 2 {
+        return vec![0.0; scores_ns.len()];
+    }
+
+    let comparisons = (scores_ns.len() - 1) as f64;
+    scores_ns
+        .iter()
+        .enumerate()
+        .map(|(i, &score)| {
+            let total: i32 = scores_ns
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, &other)| imps_for_difference(score - other))
+                .sum();
+            total as f64 / comparisons
+        })
+        .collect()
+}
+
+/// Calculate Neuberg-adjusted matchpoints for one board
+///
+/// `top` is the common matchpoint top (one point per score beaten, half per
+/// tie) that every board is scaled to, normally one less than the largest
+/// number of times any board was played. With `A` results on this board and
+/// `E = top + 1` expected results, a raw score `M` becomes
+/// `((2M + 1) * E / A - 1) / 2`. When `A == E` this leaves scores unchanged.
+pub fn calculate_matchpoints_neuberg(scores_ns: &[i32], top: f64) -> Vec<f64> {
+    let played = scores_ns.len() as f64;
+    let expected = top + 1.0;
+
+    scores_ns
+        .iter()
+        .map(|&score| {
+            let raw: f64 = scores_ns
+                .iter()
+                .map(|&other| match score.cmp(&other) {
+                    std::cmp::Ordering::Greater => 1.0,
+                    std::cmp::Ordering::Equal => 0.5,
+                    std::cmp::Ordering::Less => 0.0,
+                })
+                .sum::<f64>()
+                - 0.5; // Don't count the tie with itself
+
+            ((2.0 * raw + 1.0) * expected / played - 1.0) / 2.0
+        })
+        .collect()
+}
+
+/// Calculate the Butler datum for a set of NS scores on one board
+///
+/// The highest and lowest scores are discarded and the remaining scores are
+/// averaged, rounded to the nearest 10. With fewer than 3 scores nothing is
+/// trimmed; callers normally skip such boards.
+pub fn butler_datum(scores_ns: &[i32]) -> i32 {
+    if scores_ns.is_empty() {
+        return 0;
+    }
+
+    let mut sorted = scores_ns.to_vec();
+    sorted.sort_unstable();
+    let trimmed = if sorted.len() >= 3 {
+        &sorted[1..sorted.len() - 1]
+    } else {
+        &sorted[..]
+    };
+
+    let mean = trimmed.iter().sum::<i32>() as f64 / trimmed.len() as f64;
+    ((mean / 10.0).round() * 10.0) as i32
+}
+
+/// IMPs won by NS for a score compared against the board's Butler datum
+pub fn butler_imps(score_ns: i32, datum: i32) -> i32 {
+    imps_for_difference(score_ns - datum)
+}
+
+/// Convert an IMP margin to Victory Points on the WBF 20-VP continuous scale
+///
+/// Returns `(vp_for, vp_against)` from the point of view of the side with
+/// `imp_diff`. The blitz margin is `15 * sqrt(boards)`; at or beyond it the
+/// winner gets 20 and the loser 0. VPs are rounded to two decimals and the
+/// pair always sums to 20.
+pub fn imps_to_vp(imp_diff: i32, boards: u32) -> (f64, f64) {
+    let tau = (5f64.sqrt() - 1.0) / 2.0;
+    let blitz = 15.0 * f64::from(boards.max(1)).sqrt();
+    let margin = f64::from(imp_diff.unsigned_abs());
+
+    let winner = if margin >= blitz {
+        20.0
+    } else {
+        let vp = 10.0 + 10.0 * (1.0 - tau.powf(3.0 * margin / blitz)) / (1.0 - tau.powi(3));
+        ((vp * 100.0).round() / 100.0).min(20.0)
+    };
+    let loser = ((20.0 - winner) * 100.0).round() / 100.0;
+
+    if imp_diff < 0 {
+        (loser, winner)
+    } else {
+        (winner, loser)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result_row(board: i32, ns_ew: &str, contract: &str, result: &str) -> ReceivedDataRow {
+        ReceivedDataRow {
+            id: 1,
+            section: 1,
+            table: 1,
+            round: 1,
+            board,
+            pair_ns: 1,
+            pair_ew: 1,
+            declarer: 0,
+            ns_ew: ns_ew.to_string(),
+            contract: contract.to_string(),
+            result: result.to_string(),
+            lead_card: None,
+            remarks: None,
+        }
+    }
+
+    #[test]
+    fn test_passed_out_scores_zero() {
+        assert_eq!(
+            calculate_score_for_result(&result_row(1, "", "PASS", "")),
+            Some(0)
+        );
+        assert_eq!(
+            calculate_score_for_result(&result_row(1, "", "", "")),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_score_for_result_vulnerability() {
+        // Board 1: nobody vulnerable, board 3: EW vulnerable
+        assert_eq!(
+            calculate_score_for_result(&result_row(1, "N", "4S", "=")),
+            Some(420)
+        );
+        assert_eq!(
+            calculate_score_for_result(&result_row(3, "E", "4S", "=")),
+            Some(-620)
+        );
+        assert_eq!(
+            calculate_score_for_result(&result_row(3, "N", "4S", "=")),
+            Some(420)
+        );
+    }
+
+    #[test]
+    fn test_parse_contract_with_declarer() {
+        let (contract, declarer) = parse_contract_with_declarer("3NT E").unwrap();
+        assert_eq!(declarer, Some(Direction::East));
+        assert_eq!(
+            contract.score(0, false),
+            Contract::parse("3NT").unwrap().score(0, false)
+        );
+
+        let (contract, declarer) = parse_contract_with_declarer("6HXX S").unwrap();
+        assert_eq!(declarer, Some(Direction::South));
+        assert_eq!(
+            contract.score(0, true),
+            Contract::parse("6HXX").unwrap().score(0, true)
+        );
+
+        let (_, declarer) = parse_contract_with_declarer("4SXW").unwrap();
+        assert_eq!(declarer, Some(Direction::West));
+    }
+
+    #[test]
+    fn test_parse_contract_without_declarer() {
+        let (_, declarer) = parse_contract_with_declarer("4SX").unwrap();
+        assert_eq!(declarer, None);
+
+        // "N" here is the notrump strain, not North
+        let (contract, declarer) = parse_contract_with_declarer("3N").unwrap();
+        assert_eq!(declarer, None);
+        assert_eq!(contract.score(0, false), 400);
+
+        assert!(parse_contract_with_declarer("3NT Q").is_none());
+    }
+
+    #[test]
+    fn test_imps_for_difference() {
+        assert_eq!(imps_for_difference(0), 0);
+        assert_eq!(imps_for_difference(10), 0);
+        assert_eq!(imps_for_difference(20), 1);
+        assert_eq!(imps_for_difference(420), 9);
+        assert_eq!(imps_for_difference(-620), -12);
+        assert_eq!(imps_for_difference(5000), 24);
+    }
+
+    #[test]
+    fn test_cross_imps() {
+        let imps = calculate_cross_imps(&[420, 420, -50]);
+        assert_eq!(imps, vec![5.0, 5.0, -10.0]);
+        assert_eq!(calculate_cross_imps(&[100]), vec![0.0]);
+    }
+
+    #[test]
+    fn test_butler_datum_trims_extremes() {
+        // 1430 and -100 are discarded; mean of 420, 450, 420 is 430
+        assert_eq!(butler_datum(&[420, 1430, 450, -100, 420]), 430);
+        // Rounded to the nearest 10
+        assert_eq!(butler_datum(&[400, 420, 450, 1000]), 440);
+        // Too few scores to trim
+        assert_eq!(butler_datum(&[100, 200]), 150);
+    }
+
+    #[test]
+    fn test_butler_imps() {
+        assert_eq!(butler_imps(1430, 430), 14);
+        assert_eq!(butler_imps(-100, 430), -11);
+        assert_eq!(butler_imps(420, 430), 0);
+    }
+
+    #[test]
+    fn test_neuberg_normalizes_to_common_top() {
+        let top = 5.0;
+        let board_a = calculate_matchpoints_neuberg(&[420, 450, 400, -50, 170, 620], top);
+        let board_b = calculate_matchpoints_neuberg(&[420, 450, -50, 620], top);
+
+        // A board played the full number of times is unchanged
+        let mut sorted_a = board_a.clone();
+        sorted_a.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(sorted_a, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+
+        // Both boards average half the common top and stay within it
+        for board in [&board_a, &board_b] {
+            let avg = board.iter().sum::<f64>() / board.len() as f64;
+            assert!((avg - top / 2.0).abs() < 1e-9);
+            assert!(board.iter().all(|&mp| mp <= top && mp >= 0.0));
+        }
+
+        // Top on the 4-table board: ((2*3 + 1) * 6 / 4 - 1) / 2
+        assert!((board_b[3] - 4.75).abs() < 1e-9);
+    }
+
+    /// Undertrick penalties for 1-13 down, per the Laws of Duplicate Bridge
+    const DOUBLED_NON_VUL: [i32; 13] = [
+        100, 300, 500, 800, 1100, 1400, 1700, 2000, 2300, 2600, 2900, 3200, 3500,
+    ];
+    const DOUBLED_VUL: [i32; 13] = [
+        200, 500, 800, 1100, 1400, 1700, 2000, 2300, 2600, 2900, 3200, 3500, 3800,
+    ];
+
+    #[test]
+    fn test_undertrick_table() {
+        for (i, (&nv, &v)) in DOUBLED_NON_VUL.iter().zip(&DOUBLED_VUL).enumerate() {
+            let down = i as i32 + 1;
+            let cases = [
+                ("7NT", false, 50 * down),
+                ("7NT", true, 100 * down),
+                ("7NTX", false, nv),
+                ("7NTX", true, v),
+                ("7NTXX", false, 2 * nv),
+                ("7NTXX", true, 2 * v),
+            ];
+
+            for (contract, vul, penalty) in cases {
+                let parsed = Contract::parse(contract).unwrap();
+                assert_eq!(
+                    parsed.score(-down, vul),
+                    -penalty,
+                    "{} down {} vul={}",
+                    contract,
+                    down,
+                    vul
+                );
+                assert_eq!(
+                    rubber::score_rubber(&parsed, -down, vul, false).defenders_above,
+                    penalty,
+                    "rubber {} down {} vul={}",
+                    contract,
+                    down,
+                    vul
+                );
+            }
+        }
+
+        // 7 down doubled non-vulnerable: 500 for the first three, 300 each after
+        assert_eq!(DOUBLED_NON_VUL[6], 500 + 4 * 300);
+    }
+
+    #[test]
+    fn test_imps_to_vp_12_boards() {
+        assert_eq!(imps_to_vp(0, 12), (10.0, 10.0));
+        assert_eq!(imps_to_vp(1, 12), (10.36, 9.64));
+        assert_eq!(imps_to_vp(5, 12), (11.7, 8.3));
+        assert_eq!(imps_to_vp(10, 12), (13.18, 6.82));
+        assert_eq!(imps_to_vp(20, 12), (15.58, 4.42));
+        assert_eq!(imps_to_vp(-30, 12), (2.6, 17.4));
+        assert_eq!(imps_to_vp(51, 12), (19.92, 0.08));
+    }
+
+    #[test]
+    fn test_imps_to_vp_blitz() {
+        // Blitz for 12 boards is 15 * sqrt(12) = 51.96 IMPs
+        assert_eq!(imps_to_vp(52, 12), (20.0, 0.0));
+        assert_eq!(imps_to_vp(-75, 12), (0.0, 20.0));
+    }
+
+    #[test]
+    fn test_contract_result_parse() {
+        // Board 2: NS vulnerable; 3NT by South, two overtricks
+        let result = ContractResult::parse("3NT", "+2", "S", 2).unwrap();
+        assert_eq!(result.declarer, Direction::South);
+        assert!(result.vulnerable);
+        assert_eq!(result.tricks_relative, 2);
+        assert_eq!(result.tricks_made(), 11);
+        assert_eq!(result.score(), 660);
+        assert_eq!(result.ns_score(), 660);
+
+        // Doubled and down two by West on board 3 (EW vulnerable)
+        let result = ContractResult::parse("4HX W", "-2", "W", 3).unwrap();
+        assert_eq!(result.tricks_made(), 8);
+        assert_eq!(result.score(), -500);
+        assert_eq!(result.ns_score(), 500);
+
+        assert!(ContractResult::parse("PASS", "", "", 1).is_none());
+        assert!(ContractResult::parse("4S", "=", "X", 1).is_none());
+    }
+
+    #[test]
+    fn test_game_and_slam_detection() {
+        let contract = |s: &str| Contract::parse(s).unwrap();
+        assert!(is_game(&contract("3NT")));
+        assert!(is_game(&contract("5C")));
+        assert!(is_game(&contract("4H")));
+        assert!(is_game(&contract("2SX")));
+        assert!(!is_game(&contract("2S")));
+        assert!(!is_game(&contract("4D")));
+        assert!(!is_game(&contract("1CX")));
+        assert!(is_game(&contract("1CXX")));
+
+        assert_eq!(tricks_needed(&contract("3NT")), 9);
+        assert!(is_slam(&contract("6C")));
+        assert!(!is_slam(&contract("5NT")));
+        assert!(is_making(0));
+        assert!(!is_making(-1));
+    }
+
+    #[test]
+    fn test_strain_from_contract() {
+        assert_eq!(strain_from_contract("4S N"), Some(Strain::Spades));
+        assert_eq!(strain_from_contract("4H S"), Some(Strain::Hearts));
+        assert_eq!(strain_from_contract("3NT"), Some(Strain::NoTrump));
+        assert_eq!(strain_from_contract("3N"), Some(Strain::NoTrump));
+        assert_eq!(strain_from_contract("2DXW"), Some(Strain::Diamonds));
+        assert_eq!(strain_from_contract("PASS"), None);
+
+        assert_eq!(strain_trump(Strain::Spades), Some(Suit::Spades));
+        assert_eq!(strain_trump(Strain::NoTrump), None);
+        assert_eq!(strain_solver_index(Strain::Spades), 0);
+        assert_eq!(strain_solver_index(Strain::NoTrump), 4);
+    }
+
+    #[test]
+    fn test_contract_from_bws_fields() {
+        let (contract, declarer) = contract_from_bws_fields("4 S", "N").unwrap();
+        assert_eq!(declarer, Direction::North);
+        assert_eq!(contract.strain, Strain::Spades);
+        assert_eq!(contract.level, 4);
+
+        let (contract, declarer) = contract_from_bws_fields("3 NT x", "E").unwrap();
+        assert_eq!(declarer, Direction::East);
+        assert_eq!(contract.strain, Strain::NoTrump);
+        assert_eq!(contract.doubled, Doubled::Doubled);
+
+        let (contract, _) = contract_from_bws_fields("6 H xx", "W").unwrap();
+        assert_eq!(contract.doubled, Doubled::Redoubled);
+        let (contract, _) = contract_from_bws_fields("3N", "S").unwrap();
+        assert_eq!(contract.strain, Strain::NoTrump);
+
+        // Declarer named only in the contract
+        let (_, declarer) = contract_from_bws_fields("2D W", "").unwrap();
+        assert_eq!(declarer, Direction::West);
+
+        assert!(contract_from_bws_fields("PASS", "").is_none());
+        assert!(contract_from_bws_fields("", "N").is_none());
+        assert!(contract_from_bws_fields("4S", "").is_none());
+
+        // EW declarer: 3NT= on board 1 is -400 for NS
+        assert_eq!(
+            calculate_score_for_result(&result_row(1, "E", "3 NT", "=")),
+            Some(-400)
+        );
+    }
+
+    #[test]
+    fn test_adjusted_score_pct() {
+        let pct =
+            |contract: &str, result: &str| adjusted_score_pct(&result_row(1, "", contract, result));
+        let split = |ns, ew| Some(MatchpointPct { ns, ew });
+        assert_eq!(pct("Ave+", ""), split(60.0, 40.0));
+        assert_eq!(pct("AVE", ""), split(50.0, 50.0));
+        assert_eq!(pct("4S", "A-"), split(40.0, 60.0));
+        assert_eq!(pct("Ave-/Ave+", ""), split(40.0, 60.0));
+
+        // Splits needn't add to 100%
+        assert_eq!(pct("Ave+/Ave+", ""), split(60.0, 60.0));
+        assert_eq!(pct("Ave/Ave-", ""), split(50.0, 40.0));
+        assert_eq!(pct("Ave+/4S", ""), None);
+
+        assert_eq!(pct("4S", "="), None);
+        assert_eq!(pct("PASS", ""), None);
+
+        // Remarks only count when the row has no score of its own
+        let mut row = result_row(1, "", "", "");
+        row.contract = "ADJ".to_string();
+        row.remarks = Some("Av+".to_string());
+        assert_eq!(adjusted_score_pct(&row), Some(MatchpointPct::from_ns(60.0)));
+
+        // A blank contract with an average in the remarks isn't a pass-out
+        let mut row = result_row(1, "", "", "");
+        row.remarks = Some("Ave+".to_string());
+        assert_eq!(calculate_score_for_result(&row), None);
+        assert_eq!(adjusted_score_pct(&row), Some(MatchpointPct::from_ns(60.0)));
+        row.remarks = Some("Director called".to_string());
+        assert_eq!(calculate_score_for_result(&row), Some(0));
+        assert_eq!(adjusted_score_pct(&row), None);
+
+        let mut row = result_row(1, "N", "4S", "=");
+        row.remarks = Some("A".to_string());
+        assert_eq!(adjusted_score_pct(&row), None);
+    }
+
+    #[test]
+    fn test_strain_suit_conversion() {
+        for suit in [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades] {
+            assert_eq!(strain_trump(strain_from_suit(suit)), Some(suit));
+        }
+        assert_eq!(strain_trump(Strain::NoTrump), None);
+    }
+
+    #[test]
+    fn test_doubled_round_trip() {
+        assert_eq!(doubled_suffix(Doubled::None), "");
+        assert_eq!(doubled_suffix(Doubled::Doubled), "X");
+        assert_eq!(doubled_suffix(Doubled::Redoubled), "XX");
+        assert_eq!(parse_doubled(""), Some(Doubled::None));
+        assert_eq!(parse_doubled("X"), Some(Doubled::Doubled));
+        assert_eq!(parse_doubled("xx"), Some(Doubled::Redoubled));
+        assert_eq!(parse_doubled("XXX"), None);
+    }
+
+    fn revoke(seat: Direction, won_revoke_trick: bool, side_tricks: usize) -> Revoke {
+        Revoke {
+            trick: 3,
+            seat,
+            card: crate::Card::new(Suit::Hearts, crate::Rank::Two),
+            led: Suit::Spades,
+            won_revoke_trick,
+            side_tricks,
+        }
+    }
+
+    #[test]
+    fn test_revoke_penalty_tricks() {
+        assert_eq!(revoke_penalty_tricks(&revoke(Direction::East, true, 3)), 2);
+        assert_eq!(revoke_penalty_tricks(&revoke(Direction::East, true, 1)), 1);
+        assert_eq!(revoke_penalty_tricks(&revoke(Direction::East, false, 2)), 1);
+        assert_eq!(revoke_penalty_tricks(&revoke(Direction::East, false, 0)), 0);
+    }
+
+    #[test]
+    fn test_apply_revoke_penalty() {
+        // A defender's revoke not won by the revoker transfers one trick
+        let revokes = [revoke(Direction::East, false, 2)];
+        assert_eq!(apply_revoke_penalty(9, Direction::South, &revokes), 10);
+
+        // Dummy's revoke costs declarer
+        let revokes = [revoke(Direction::North, true, 4)];
+        assert_eq!(apply_revoke_penalty(9, Direction::South, &revokes), 7);
+
+        // A second revoke in the same suit by the same player is not penalized
+        let revokes = [
+            revoke(Direction::East, false, 2),
+            revoke(Direction::East, false, 2),
+        ];
+        assert_eq!(apply_revoke_penalty(13, Direction::South, &revokes), 13);
+    }
+
+    #[test]
+    fn test_score_matrix_best_contract_matches_optimum_score() {
+        // NS make 4S and 3NT and EW make nothing; the PBN par is NS 420
+        let mut pbn = String::from(
+            "[Board \"1\"]\n[Vulnerable \"None\"]\n[OptimumScore \"NS 420\"]\n\
+             [OptimumResultTable \"Declarer;Denomination\\2R;Result\\2R\"]\n",
+        );
+        for dir in ["N", "E", "S", "W"] {
+            let ns = dir == "N" || dir == "S";
+            for (strain, ns_tricks) in [("S", 10), ("H", 8), ("D", 7), ("C", 7), ("NT", 9)] {
+                let tricks = if ns { ns_tricks } else { 13 - ns_tricks };
+                pbn.push_str(&format!("{} {} {}\n", dir, strain, tricks));
+            }
+        }
+        let board = &crate::pbn::reader::read_pbn(&pbn).unwrap()[0];
+        let table = &crate::pbn::reader::read_optimum_result_tables(&pbn)[&1];
+
+        let matrix = score_matrix(board, table);
+        // 4 spade, 2 heart, 1 diamond, 1 club and 3 notrump levels, for N and S
+        assert_eq!(matrix.len(), 22);
+        assert!(matrix
+            .iter()
+            .all(|(_, dir, _)| matches!(dir, Direction::North | Direction::South)));
+
+        let (best, _, score) = matrix.iter().max_by_key(|(_, _, score)| *score).unwrap();
+        assert_eq!(best.strain, Strain::Spades);
+        assert_eq!(best.level, 4);
+        let par: i32 = board
+            .optimum_score
+            .as_deref()
+            .and_then(|s| s.strip_prefix("NS "))
+            .and_then(|s| s.parse().ok())
+            .unwrap();
+        assert_eq!(*score, par);
+
+        // Lower levels score their overtricks
+        let scores: Vec<i32> = matrix
+            .iter()
+            .filter(|(c, dir, _)| c.strain == Strain::NoTrump && *dir == Direction::South)
+            .map(|(_, _, score)| *score)
+            .collect();
+        assert_eq!(scores, vec![150, 150, 400]);
+    }
+
+    #[test]
+    fn test_score_matrix_ew_scores_are_negative() {
+        let board = Board::new().with_vulnerability(Vulnerability::EastWest);
+        let matrix = score_matrix(&board, &[(Direction::East, Strain::Hearts, 10)]);
+        let scores: Vec<i32> = matrix.iter().map(|(_, _, score)| *score).collect();
+        assert_eq!(scores, vec![-170, -170, -170, -620]);
+    }
+}
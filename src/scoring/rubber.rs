@@ -0,0 +1,359 @@
+//! Rubber bridge (total-point) scoring
+//!
+//! Unlike duplicate, trick points for bid-and-made contracts go "below the
+//! line" toward game, while overtricks, penalties, slam and honor bonuses go
+//! "above the line". There is no per-deal game bonus; instead the side that
+//! first wins two games receives the rubber bonus.
+
+use super::contract_trick_points;
+use crate::{Contract, Doubled};
+
+/// Points from one deal, split by where they are recorded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RubberScore {
+    /// Trick points for the declaring side, counting toward game
+    pub declarer_below: i32,
+    /// Overtrick, insult and slam bonuses for the declaring side
+    pub declarer_above: i32,
+    /// Undertrick penalties for the defending side
+    pub defenders_above: i32,
+}
+
+/// Honors held in one hand, scored above the line for that hand's side
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Honors {
+    /// Four of the five trump honors (100)
+    FourTrumpHonors,
+    /// All five trump honors (150)
+    FiveTrumpHonors,
+    /// All four aces at notrump (150)
+    FourAces,
+}
+
+impl Honors {
+    /// Above-the-line bonus for these honors
+    pub fn points(&self) -> i32 {
+        match self {
+            Honors::FourTrumpHonors => 100,
+            Honors::FiveTrumpHonors | Honors::FourAces => 150,
+        }
+    }
+}
+
+/// A partnership at the rubber table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    NorthSouth,
+    EastWest,
+}
+
+impl Side {
+    fn index(self) -> usize {
+        match self {
+            Side::NorthSouth => 0,
+            Side::EastWest => 1,
+        }
+    }
+
+    fn other(self) -> Side {
+        match self {
+            Side::NorthSouth => Side::EastWest,
+            Side::EastWest => Side::NorthSouth,
+        }
+    }
+}
+
+/// Score a deal under rubber bridge rules
+///
+/// `tricks_relative` is tricks taken relative to the contract (0 = just made,
+/// negative = down). With `above_line_only`, trick points are recorded above
+/// the line as well, so the deal does not count toward game.
+pub fn score_rubber(
+    contract: &Contract,
+    tricks_relative: i32,
+    vulnerable: bool,
+    above_line_only: bool,
+) -> RubberScore {
+    let multiplier = match contract.doubled {
+        Doubled::None => 1,
+        Doubled::Doubled => 2,
+        Doubled::Redoubled => 4,
+    };
+
+    if tricks_relative < 0 {
+        return RubberScore {
+            defenders_above: undertrick_penalty(-tricks_relative, vulnerable, multiplier),
+            ..RubberScore::default()
+        };
+    }
+
+    let level = contract.level as i32;
+    let trick_points = contract_trick_points(contract.strain, level) * multiplier;
+
+    let overtrick_points = tricks_relative
+        * match multiplier {
+            1 => {
+                contract_trick_points(contract.strain, 2)
+                    - contract_trick_points(contract.strain, 1)
+            }
+            2 => {
+                if vulnerable {
+                    200
+                } else {
+                    100
+                }
+            }
+            _ => {
+                if vulnerable {
+                    400
+                } else {
+                    200
+                }
+            }
+        };
+
+    let insult = match multiplier {
+        1 => 0,
+        2 => 50,
+        _ => 100,
+    };
+
+    let slam = match level {
+        6 => {
+            if vulnerable {
+                750
+            } else {
+                500
+            }
+        }
+        7 => {
+            if vulnerable {
+                1500
+            } else {
+                1000
+            }
+        }
+        _ => 0,
+    };
+
+    let mut score = RubberScore {
+        declarer_below: trick_points,
+        declarer_above: overtrick_points + insult + slam,
+        defenders_above: 0,
+    };
+    if above_line_only {
+        score.declarer_above += score.declarer_below;
+        score.declarer_below = 0;
+    }
+    score
+}
+
+/// Penalty for going down `down` tricks; `multiplier` is 1, 2 or 4
+fn undertrick_penalty(down: i32, vulnerable: bool, multiplier: i32) -> i32 {
+    if multiplier == 1 {
+        return down * if vulnerable { 100 } else { 50 };
+    }
+
+    let doubled: i32 = (1..=down)
+        .map(|n| match (vulnerable, n) {
+            (false, 1) => 100,
+            (false, 2 | 3) => 200,
+            (true, 1) => 200,
+            _ => 300,
+        })
+        .sum();
+    doubled * multiplier / 2
+}
+
+/// Running score of a rubber between two sides
+#[derive(Debug, Clone, Default)]
+pub struct RubberState {
+    /// Below-the-line points toward the current game, per side
+    partial: [i32; 2],
+    /// All below-the-line points scored, per side
+    below: [i32; 2],
+    /// All above-the-line points scored, per side
+    above: [i32; 2],
+    /// Games won, per side
+    games: [u8; 2],
+}
+
+impl RubberState {
+    /// Start a new rubber
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a side has won a game and is therefore vulnerable
+    pub fn is_vulnerable(&self, side: Side) -> bool {
+        self.games[side.index()] > 0
+    }
+
+    /// Games won by a side
+    pub fn games(&self, side: Side) -> u8 {
+        self.games[side.index()]
+    }
+
+    /// Whether one side has won two games
+    pub fn is_complete(&self) -> bool {
+        self.games.iter().any(|&g| g >= 2)
+    }
+
+    /// Record a deal played by `declarer`, using the side's current vulnerability
+    ///
+    /// Completing a game (100 below the line) clears both sides' partscores.
+    /// Winning the second game adds the rubber bonus: 700 for two games to
+    /// none, 500 for two games to one. Deals after completion are ignored.
+    pub fn record(&mut self, declarer: Side, contract: &Contract, tricks_relative: i32) {
+        if self.is_complete() {
+            return;
+        }
+
+        let vulnerable = self.is_vulnerable(declarer);
+        let score = score_rubber(contract, tricks_relative, vulnerable, false);
+        let (d, o) = (declarer.index(), declarer.other().index());
+
+        self.below[d] += score.declarer_below;
+        self.above[d] += score.declarer_above;
+        self.above[o] += score.defenders_above;
+        self.partial[d] += score.declarer_below;
+
+        if self.partial[d] >= 100 {
+            self.games[d] += 1;
+            self.partial = [0, 0];
+
+            if self.games[d] == 2 {
+                self.above[d] += if self.games[o] == 0 { 700 } else { 500 };
+            }
+        }
+    }
+
+    /// Record honors held by a player of `side`
+    pub fn record_honors(&mut self, side: Side, honors: Honors) {
+        self.above[side.index()] += honors.points();
+    }
+
+    /// Points below the line for a side
+    pub fn below_line(&self, side: Side) -> i32 {
+        self.below[side.index()]
+    }
+
+    /// Points above the line for a side
+    pub fn above_line(&self, side: Side) -> i32 {
+        self.above[side.index()]
+    }
+
+    /// Total points for a side
+    pub fn total(&self, side: Side) -> i32 {
+        self.below_line(side) + self.above_line(side)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contract(s: &str) -> Contract {
+        Contract::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_trick_points_below_line() {
+        assert_eq!(
+            score_rubber(&contract("2S"), 0, false, false).declarer_below,
+            60
+        );
+        assert_eq!(
+            score_rubber(&contract("3NT"), 0, false, false).declarer_below,
+            100
+        );
+        assert_eq!(
+            score_rubber(&contract("5C"), 0, false, false).declarer_below,
+            100
+        );
+        assert_eq!(
+            score_rubber(&contract("2HX"), 0, false, false).declarer_below,
+            120
+        );
+    }
+
+    #[test]
+    fn test_overtricks_and_bonuses_above_line() {
+        let score = score_rubber(&contract("3NT"), 1, false, false);
+        assert_eq!(score.declarer_above, 30);
+
+        // Doubled: 100 per overtrick not vulnerable, plus 50 for the insult
+        let score = score_rubber(&contract("2HX"), 1, false, false);
+        assert_eq!(score.declarer_above, 150);
+
+        let score = score_rubber(&contract("6C"), 0, true, false);
+        assert_eq!(score.declarer_below, 120);
+        assert_eq!(score.declarer_above, 750);
+
+        let score = score_rubber(&contract("7NT"), 0, false, false);
+        assert_eq!(score.declarer_above, 1000);
+    }
+
+    #[test]
+    fn test_above_line_only() {
+        let score = score_rubber(&contract("4S"), 1, false, true);
+        assert_eq!(score.declarer_below, 0);
+        assert_eq!(score.declarer_above, 150);
+    }
+
+    #[test]
+    fn test_undertricks() {
+        let penalty = |c: &str, down: i32, vul: bool| {
+            score_rubber(&contract(c), -down, vul, false).defenders_above
+        };
+        assert_eq!(penalty("4S", 2, false), 100);
+        assert_eq!(penalty("4S", 2, true), 200);
+        assert_eq!(penalty("4SX", 1, false), 100);
+        assert_eq!(penalty("4SX", 3, false), 500);
+        assert_eq!(penalty("4SX", 4, false), 800);
+        assert_eq!(penalty("4SX", 2, true), 500);
+        assert_eq!(penalty("4SXX", 2, true), 1000);
+    }
+
+    #[test]
+    fn test_complete_rubber() {
+        let mut rubber = RubberState::new();
+
+        // NS partscore, then EW game over it
+        rubber.record(Side::NorthSouth, &contract("2S"), 0);
+        rubber.record(Side::EastWest, &contract("3NT"), 1);
+        assert_eq!(rubber.games(Side::EastWest), 1);
+        assert!(rubber.is_vulnerable(Side::EastWest));
+
+        // NS's earlier 60 no longer counts toward game
+        rubber.record(Side::NorthSouth, &contract("4H"), 0);
+        assert_eq!(rubber.games(Side::NorthSouth), 1);
+        assert!(!rubber.is_complete());
+
+        // EW vulnerable, doubled down two
+        rubber.record(Side::EastWest, &contract("4SX"), -2);
+
+        // NS vulnerable small slam with an overtrick and four trump honors
+        rubber.record(Side::NorthSouth, &contract("6C"), 1);
+        rubber.record_honors(Side::NorthSouth, Honors::FourTrumpHonors);
+        assert!(rubber.is_complete());
+
+        assert_eq!(rubber.below_line(Side::NorthSouth), 300);
+        // 500 penalty + 20 overtrick + 750 slam + 500 rubber (2-1) + 100 honors
+        assert_eq!(rubber.above_line(Side::NorthSouth), 1870);
+        assert_eq!(rubber.total(Side::NorthSouth), 2170);
+        assert_eq!(rubber.total(Side::EastWest), 130);
+
+        // Further deals are not scored
+        rubber.record(Side::EastWest, &contract("7NT"), 0);
+        assert_eq!(rubber.total(Side::EastWest), 130);
+    }
+
+    #[test]
+    fn test_two_nil_rubber_bonus() {
+        let mut rubber = RubberState::new();
+        rubber.record(Side::EastWest, &contract("4S"), 0);
+        rubber.record(Side::EastWest, &contract("5D"), 0);
+        assert_eq!(rubber.above_line(Side::EastWest), 700);
+    }
+}
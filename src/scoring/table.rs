@@ -0,0 +1,91 @@
+//! Reference duplicate score table
+//!
+//! The table lists the score for every contract (1C to 7NTXX), vulnerability
+//! and number of tricks from all-down to all-made, derived from the Laws of
+//! Duplicate Bridge independently of `Contract::score`. It is embedded as CSV
+//! so consumers can check the scoring they rely on.
+
+use crate::Contract;
+use serde::Deserialize;
+
+const SCORE_TABLE_CSV: &str = include_str!("score_table.csv");
+
+/// One row of the reference score table
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ScoreTableEntry {
+    /// Contract as accepted by `Contract::parse` (e.g. "4S", "3NTX", "6HXX")
+    pub contract: String,
+    /// Whether declarer is vulnerable
+    pub vulnerable: bool,
+    /// Tricks taken relative to the contract (0 = just made, negative = down)
+    pub tricks_relative: i32,
+    /// Score for the declaring side
+    pub score: i32,
+}
+
+/// The canonical score for every contract, result and vulnerability
+pub fn score_table() -> Vec<ScoreTableEntry> {
+    let mut reader = csv::Reader::from_reader(SCORE_TABLE_CSV.as_bytes());
+    reader.deserialize().flatten().collect()
+}
+
+/// Entries of the reference table that `Contract::score` disagrees with
+///
+/// Each mismatch is returned with the score the crate computed, or `None`
+/// if the contract couldn't be parsed.
+pub fn score_table_mismatches() -> Vec<(ScoreTableEntry, Option<i32>)> {
+    score_table()
+        .into_iter()
+        .filter_map(|entry| {
+            let actual = Contract::parse(&entry.contract)
+                .map(|c| c.score(entry.tricks_relative, entry.vulnerable));
+            (actual != Some(entry.score)).then_some((entry, actual))
+        })
+        .collect()
+}
+
+/// Check `Contract::score` against every entry of the reference table
+pub fn verify_against_table() -> bool {
+    score_table_mismatches().is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_is_complete() {
+        let table = score_table();
+        // 35 contracts, 3 doubling states, 2 vulnerabilities, 14 results each
+        assert_eq!(table.len(), 35 * 3 * 2 * 14);
+
+        let find = |contract: &str, vulnerable: bool, tricks_relative: i32| {
+            table
+                .iter()
+                .find(|e| {
+                    e.contract == contract
+                        && e.vulnerable == vulnerable
+                        && e.tricks_relative == tricks_relative
+                })
+                .map(|e| e.score)
+        };
+        assert_eq!(find("4S", false, 0), Some(420));
+        assert_eq!(find("3NT", true, 1), Some(630));
+        assert_eq!(find("6HXX", true, 0), Some(2070));
+        assert_eq!(find("1CX", false, 1), Some(240));
+        assert_eq!(find("7NTX", false, -13), Some(-3500));
+    }
+
+    #[test]
+    fn test_contract_score_matches_table() {
+        let mismatches = score_table_mismatches();
+        assert!(
+            mismatches.is_empty(),
+            "{} mismatches, first: {:?}",
+            mismatches.len(),
+            mismatches.first()
+        );
+        assert!(verify_against_table());
+    }
+}
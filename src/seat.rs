@@ -0,0 +1,108 @@
+//! Seat indexes and rotation for directions
+//!
+//! `Direction` comes from bridge-types; these free functions give the rest of
+//! the crate one clockwise seat index (N=0, E=1, S=2, W=3) and one place for
+//! BBO's LIN seat order (S, W, N, E), instead of repeating match arms.
+
+use crate::Direction;
+
+/// Directions in BBO LIN order, as used by the `pn` names and `md` hands
+pub const LIN_SEATS: [Direction; 4] = [
+    Direction::South,
+    Direction::West,
+    Direction::North,
+    Direction::East,
+];
+
+/// Clockwise seat index: North 0, East 1, South 2, West 3
+pub fn seat_index(direction: Direction) -> usize {
+    match direction {
+        Direction::North => 0,
+        Direction::East => 1,
+        Direction::South => 2,
+        Direction::West => 3,
+    }
+}
+
+/// Direction at a clockwise seat index (see [`seat_index`])
+pub fn direction_from_index(index: usize) -> Option<Direction> {
+    match index {
+        0 => Some(Direction::North),
+        1 => Some(Direction::East),
+        2 => Some(Direction::South),
+        3 => Some(Direction::West),
+        _ => None,
+    }
+}
+
+/// The direction `by` quarter-turns clockwise from `direction`
+///
+/// Rotating by 1 gives the player on the left; by 4 (or any multiple) gives
+/// the same direction back.
+pub fn rotate(direction: Direction, by: u8) -> Direction {
+    let index = (seat_index(direction) + usize::from(by % 4)) % 4;
+    direction_from_index(index).unwrap_or(direction)
+}
+
+/// The player on the right, who plays before `direction` to a trick
+pub fn previous(direction: Direction) -> Direction {
+    rotate(direction, 3)
+}
+
+/// The partner sitting opposite
+pub fn partner(direction: Direction) -> Direction {
+    rotate(direction, 2)
+}
+
+/// Position of a direction in LIN order (see [`LIN_SEATS`]), 0..4
+///
+/// The `md` dealer digit is this position plus one.
+pub fn lin_seat(direction: Direction) -> usize {
+    (seat_index(direction) + 2) % 4
+}
+
+/// Direction at a position in LIN order (see [`LIN_SEATS`])
+pub fn from_lin_seat(index: usize) -> Option<Direction> {
+    LIN_SEATS.get(index).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seat_index_round_trip() {
+        for (i, dir) in Direction::ALL.into_iter().enumerate() {
+            assert_eq!(seat_index(dir), i);
+            assert_eq!(direction_from_index(i), Some(dir));
+        }
+        assert_eq!(direction_from_index(4), None);
+    }
+
+    #[test]
+    fn test_rotate_matches_next() {
+        for dir in Direction::ALL {
+            assert_eq!(rotate(dir, 0), dir);
+            assert_eq!(rotate(dir, 1), dir.next());
+            assert_eq!(rotate(dir, 2), dir.next().next());
+            assert_eq!(rotate(dir, 5), dir.next());
+            assert_eq!(previous(dir).next(), dir);
+        }
+        assert_eq!(partner(Direction::East), Direction::West);
+        assert_eq!(previous(Direction::North), Direction::West);
+    }
+
+    #[test]
+    fn test_lin_seats() {
+        // pn and md list South, West, North, East
+        assert_eq!(lin_seat(Direction::South), 0);
+        assert_eq!(lin_seat(Direction::West), 1);
+        assert_eq!(lin_seat(Direction::North), 2);
+        assert_eq!(lin_seat(Direction::East), 3);
+        for (i, dir) in LIN_SEATS.into_iter().enumerate() {
+            assert_eq!(lin_seat(dir), i);
+            assert_eq!(from_lin_seat(i), Some(dir));
+        }
+        assert_eq!(from_lin_seat(4), None);
+    }
+}
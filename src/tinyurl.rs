@@ -1,8 +1,14 @@
 //! URL resolution with rate limiting for TinyURL and similar services
 
 use crate::error::{BridgeError, Result};
+use crate::http::ClientConfig;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
+
+/// Hosts of URL shortening services that redirect to BBO hand records
+const SHORTENER_HOSTS: &[&str] = &["tinyurl.com", "bit.ly", "is.gd", "t.co", "ow.ly"];
 
 /// Configuration for URL resolution with rate limiting
 pub struct UrlResolver {
@@ -11,6 +17,49 @@ pub struct UrlResolver {
     batch_size: usize,
     batch_delay_ms: u64,
     requests_in_batch: usize,
+    cache: Option<UrlCache>,
+    network_requests: usize,
+    retry: RetryPolicy,
+}
+
+/// JSON-backed cache of resolved URLs, keyed by the short URL
+struct UrlCache {
+    path: PathBuf,
+    entries: HashMap<String, String>,
+}
+
+impl UrlCache {
+    /// Load the cache from disk, starting empty if the file doesn't exist yet
+    fn load(path: &Path) -> Result<Self> {
+        let entries = if path.exists() {
+            let content = std::fs::read_to_string(path)?;
+            if content.trim().is_empty() {
+                HashMap::new()
+            } else {
+                serde_json::from_str(&content)?
+            }
+        } else {
+            HashMap::new()
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            entries,
+        })
+    }
+
+    fn get(&self, short_url: &str) -> Option<&str> {
+        self.entries.get(short_url).map(String::as_str)
+    }
+
+    /// Record a resolved URL and persist the whole cache
+    fn insert(&mut self, short_url: &str, resolved: &str) -> Result<()> {
+        self.entries
+            .insert(short_url.to_string(), resolved.to_string());
+        let content = serde_json::to_string_pretty(&self.entries)?;
+        std::fs::write(&self.path, content)?;
+        Ok(())
+    }
 }
 
 impl UrlResolver {
@@ -25,99 +74,154 @@ impl UrlResolver {
     /// * `delay_ms` - Delay between individual requests in milliseconds
     /// * `batch_size` - Number of requests before a longer pause
     /// * `batch_delay_ms` - Duration of the longer pause in milliseconds
+    ///
+    /// Uses the default [`ClientConfig`]; pass custom client settings to
+    /// [`UrlResolver::with_client_config`].
     pub fn with_config(delay_ms: u64, batch_size: usize, batch_delay_ms: u64) -> Self {
-        let client = reqwest::blocking::Client::builder()
+        Self::with_client_config(
+            delay_ms,
+            batch_size,
+            batch_delay_ms,
+            &ClientConfig::default(),
+        )
+        .expect("Failed to create HTTP client")
+    }
+
+    /// Create a URL resolver with rate limiting and HTTP client settings
+    ///
+    /// Arguments are as for [`UrlResolver::with_config`]. Fails if the client
+    /// can't be built, e.g. for a malformed proxy URL.
+    pub fn with_client_config(
+        delay_ms: u64,
+        batch_size: usize,
+        batch_delay_ms: u64,
+        config: &ClientConfig,
+    ) -> Result<Self> {
+        let client = config
+            .blocking_builder()?
             .redirect(reqwest::redirect::Policy::none()) // Don't follow redirects automatically
-            .timeout(Duration::from_secs(30))
-            .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36")
             .build()
-            .expect("Failed to create HTTP client");
+            .map_err(|e| BridgeError::UrlResolution(format!("Failed to create client: {}", e)))?;
 
-        Self {
+        Ok(Self {
             client,
             delay_ms,
             batch_size,
             batch_delay_ms,
             requests_in_batch: 0,
-        }
+            cache: None,
+            network_requests: 0,
+            retry: RetryPolicy::default(),
+        })
+    }
+
+    /// Configure retrying of rate-limited requests
+    ///
+    /// When a request is rate limited (429, or a Cloudflare 503), the resolver
+    /// waits for the server's `Retry-After` delay if given, otherwise for
+    /// `base_backoff_ms * 2^attempt`, and retries up to `max_retries` times
+    /// before returning `BridgeError::RateLimited`. Either wait is capped by
+    /// [`UrlResolver::with_max_backoff`].
+    pub fn with_retry(mut self, max_retries: u32, base_backoff_ms: u64) -> Self {
+        self.retry.max_retries = max_retries;
+        self.retry.base_backoff_ms = base_backoff_ms;
+        self
+    }
+
+    /// Cap the wait before retrying a rate-limited request (default 60s)
+    ///
+    /// Applies to the server's `Retry-After` delay as well as the computed
+    /// backoff, so a server can't stall the resolver for hours.
+    pub fn with_max_backoff(mut self, max_backoff_ms: u64) -> Self {
+        self.retry.max_backoff_ms = max_backoff_ms;
+        self
+    }
+
+    /// Use a persistent JSON cache file for resolved URLs
+    ///
+    /// Cached URLs are returned without any network request or rate-limit delay.
+    /// Newly resolved URLs are written back to the file immediately, so the
+    /// cache survives across runs. The file is created on first write.
+    pub fn with_cache(mut self, path: &Path) -> Result<Self> {
+        self.cache = Some(UrlCache::load(path)?);
+        Ok(self)
+    }
+
+    /// Number of HTTP requests made by this resolver (cache hits don't count)
+    pub fn network_requests(&self) -> usize {
+        self.network_requests
+    }
+
+    /// Check whether a URL points at a known URL shortening service
+    pub fn is_shortener(url: &str) -> bool {
+        let Ok(parsed) = url::Url::parse(url.trim()) else {
+            return false;
+        };
+        let Some(host) = parsed.host_str() else {
+            return false;
+        };
+        let host = host.to_lowercase();
+        let host = host.strip_prefix("www.").unwrap_or(&host);
+        SHORTENER_HOSTS.contains(&host)
+    }
+
+    /// Check whether a URL already carries LIN data (e.g. a BBO handviewer link)
+    pub fn has_lin(url: &str) -> bool {
+        url.contains("lin=")
     }
 
     /// Resolve a shortened URL to its final destination
     ///
-    /// This follows redirects manually to capture the final URL.
+    /// This follows redirects manually to capture the final URL. URLs that
+    /// already contain `lin=` (such as direct BBO handviewer links) are returned
+    /// as-is without a network request. When a cache is configured, it is
+    /// consulted first and updated on a miss.
     pub fn resolve(&mut self, short_url: &str) -> Result<String> {
+        if Self::has_lin(short_url) {
+            return Ok(short_url.to_string());
+        }
+
+        if let Some(cached) = self.cache.as_ref().and_then(|c| c.get(short_url)) {
+            return Ok(cached.to_string());
+        }
+
+        let resolved = self.resolve_uncached(short_url)?;
+
+        if let Some(ref mut cache) = self.cache {
+            cache.insert(short_url, &resolved)?;
+        }
+
+        Ok(resolved)
+    }
+
+    /// Resolve a URL over the network, ignoring the cache
+    fn resolve_uncached(&mut self, short_url: &str) -> Result<String> {
         // Apply rate limiting
         self.apply_rate_limit();
 
-        let mut current_url = short_url.to_string();
-        let mut redirects = 0;
-        const MAX_REDIRECTS: usize = 10;
-
+        let mut resolution = Resolution::new(short_url);
         loop {
+            self.network_requests += 1;
             let response = self
                 .client
-                .get(&current_url)
+                .get(&resolution.current_url)
                 .send()
                 .map_err(|e| BridgeError::UrlResolution(format!("Request failed: {}", e)))?;
 
             let status = response.status();
+            let headers = response.headers().clone();
+            // The body is only needed to recognize a Cloudflare challenge
+            let body = if status == reqwest::StatusCode::SERVICE_UNAVAILABLE {
+                response.text().unwrap_or_default()
+            } else {
+                String::new()
+            };
 
-            // Check for rate limiting
-            if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-                return Err(BridgeError::RateLimited);
-            }
-
-            // Check for service unavailable (often indicates rate limiting)
-            if status == reqwest::StatusCode::SERVICE_UNAVAILABLE {
-                // Check if it's a Cloudflare block
-                let body = response.text().unwrap_or_default();
-                if body.contains("Just a moment") || body.contains("Cloudflare") {
-                    return Err(BridgeError::RateLimited);
-                }
-                return Err(BridgeError::UrlResolution(
-                    "Service unavailable".to_string(),
-                ));
-            }
-
-            // Handle redirects
-            if status.is_redirection() {
-                if let Some(location) = response.headers().get(reqwest::header::LOCATION) {
-                    let location_str = location.to_str().map_err(|_| {
-                        BridgeError::UrlResolution("Invalid redirect URL".to_string())
-                    })?;
-
-                    // Handle relative URLs
-                    current_url = if location_str.starts_with("http") {
-                        location_str.to_string()
-                    } else {
-                        // Parse the current URL and resolve the relative URL
-                        let base = url::Url::parse(&current_url).map_err(|e| {
-                            BridgeError::UrlResolution(format!("Invalid URL: {}", e))
-                        })?;
-                        base.join(location_str)
-                            .map_err(|e| {
-                                BridgeError::UrlResolution(format!("Invalid redirect: {}", e))
-                            })?
-                            .to_string()
-                    };
-
-                    redirects += 1;
-                    if redirects > MAX_REDIRECTS {
-                        return Err(BridgeError::UrlResolution("Too many redirects".to_string()));
-                    }
-                    continue;
-                }
-            }
-
-            // If we get here, we've reached the final URL
-            if status.is_success() || !status.is_redirection() {
-                return Ok(current_url);
+            match resolution.advance(&self.retry, status, &headers, &body)? {
+                Next::Done(url) => return Ok(url),
+                Next::Follow => {}
+                Next::Retry(wait) => thread::sleep(wait),
             }
-
-            return Err(BridgeError::UrlResolution(format!(
-                "Unexpected status: {}",
-                status
-            )));
         }
     }
 
@@ -141,12 +245,367 @@ impl UrlResolver {
     }
 }
 
+/// Resolve a redirect `Location` (absolute or relative) against the current URL
+fn redirect_target(current_url: &str, location: &str) -> Result<String> {
+    if location.starts_with("http") {
+        return Ok(location.to_string());
+    }
+
+    let base = url::Url::parse(current_url)
+        .map_err(|e| BridgeError::UrlResolution(format!("Invalid URL: {}", e)))?;
+    Ok(base
+        .join(location)
+        .map_err(|e| BridgeError::UrlResolution(format!("Invalid redirect: {}", e)))?
+        .to_string())
+}
+
+/// Retry settings shared by [`UrlResolver`] and [`AsyncUrlResolver`]
+#[derive(Debug, Clone, Copy)]
+struct RetryPolicy {
+    max_retries: u32,
+    base_backoff_ms: u64,
+    max_backoff_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_backoff_ms: 1000,
+            max_backoff_ms: 60_000,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait before retry `attempt` (0-based): the server's `Retry-After` if
+    /// given, otherwise exponential backoff, capped at `max_backoff_ms`
+    fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        retry_after
+            .unwrap_or_else(|| backoff_delay(self.base_backoff_ms, attempt))
+            .min(Duration::from_millis(self.max_backoff_ms))
+    }
+}
+
+/// What a resolver does after a response
+#[derive(Debug, PartialEq)]
+enum Next {
+    /// The URL requested was the final one
+    Done(String),
+    /// Request the redirect target
+    Follow,
+    /// Rate limited: request the same URL again after waiting
+    Retry(Duration),
+}
+
+/// Progress resolving one URL: where it has got to, and how many redirects
+/// and retries that took
+struct Resolution {
+    current_url: String,
+    redirects: usize,
+    retries: u32,
+}
+
+impl Resolution {
+    const MAX_REDIRECTS: usize = 10;
+
+    fn new(short_url: &str) -> Self {
+        Self {
+            current_url: short_url.to_string(),
+            redirects: 0,
+            retries: 0,
+        }
+    }
+
+    /// Classify the response to a request for `current_url`
+    ///
+    /// A 429 is rate limiting, and so is a 503 whose `body` is a Cloudflare
+    /// challenge; any other 503 is an error. A redirect moves `current_url`
+    /// to its target. Any other non-redirect status ends the resolution.
+    fn advance(
+        &mut self,
+        policy: &RetryPolicy,
+        status: reqwest::StatusCode,
+        headers: &reqwest::header::HeaderMap,
+        body: &str,
+    ) -> Result<Next> {
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            || status == reqwest::StatusCode::SERVICE_UNAVAILABLE
+        {
+            if status == reqwest::StatusCode::SERVICE_UNAVAILABLE
+                && !body.contains("Just a moment")
+                && !body.contains("Cloudflare")
+            {
+                return Err(BridgeError::UrlResolution(
+                    "Service unavailable".to_string(),
+                ));
+            }
+
+            if self.retries >= policy.max_retries {
+                return Err(BridgeError::RateLimited);
+            }
+            let retry_after = headers
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(parse_retry_after);
+            let wait = policy.delay(self.retries, retry_after);
+            log::warn!(
+                "Rate limited resolving {}, retrying in {:?}",
+                self.current_url,
+                wait
+            );
+            self.retries += 1;
+            return Ok(Next::Retry(wait));
+        }
+
+        if status.is_redirection() {
+            let Some(location) = headers.get(reqwest::header::LOCATION) else {
+                return Err(BridgeError::UrlResolution(format!(
+                    "Unexpected status: {}",
+                    status
+                )));
+            };
+            let location = location
+                .to_str()
+                .map_err(|_| BridgeError::UrlResolution("Invalid redirect URL".to_string()))?;
+            self.current_url = redirect_target(&self.current_url, location)?;
+
+            self.redirects += 1;
+            if self.redirects > Self::MAX_REDIRECTS {
+                return Err(BridgeError::UrlResolution("Too many redirects".to_string()));
+            }
+            return Ok(Next::Follow);
+        }
+
+        Ok(Next::Done(self.current_url.clone()))
+    }
+}
+
+/// Exponential backoff: `base_backoff_ms * 2^attempt`
+fn backoff_delay(base_backoff_ms: u64, attempt: u32) -> Duration {
+    let factor = 1u64 << attempt.min(16);
+    Duration::from_millis(base_backoff_ms.saturating_mul(factor))
+}
+
+/// Parse a `Retry-After` header value, given either as seconds or an HTTP-date
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let date = httpdate::parse_http_date(value).ok()?;
+    // A date in the past means we can retry immediately
+    Some(
+        date.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
 impl Default for UrlResolver {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Token-bucket rate limiter shared by concurrent requests
+///
+/// Tokens refill at one per `interval` up to `burst`. With a burst of 1 this
+/// enforces a minimum spacing of `interval` between requests.
+struct RateLimiter {
+    interval: Duration,
+    burst: f64,
+    state: tokio::sync::Mutex<(f64, tokio::time::Instant)>,
+}
+
+impl RateLimiter {
+    fn new(interval: Duration, burst: u32) -> Self {
+        let burst = f64::from(burst.max(1));
+        Self {
+            interval,
+            burst,
+            state: tokio::sync::Mutex::new((burst, tokio::time::Instant::now())),
+        }
+    }
+
+    /// Wait until a token is available and take it
+    async fn acquire(&self) {
+        if self.interval.is_zero() {
+            return;
+        }
+
+        loop {
+            let wait = {
+                let mut state = self.state.lock().await;
+                let (tokens, last) = &mut *state;
+                let now = tokio::time::Instant::now();
+                let refill = now.duration_since(*last).as_secs_f64() / self.interval.as_secs_f64();
+                *tokens = (*tokens + refill).min(self.burst);
+                *last = now;
+
+                if *tokens >= 1.0 {
+                    *tokens -= 1.0;
+                    return;
+                }
+                self.interval.mul_f64(1.0 - *tokens)
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// State shared by the tasks of an [`AsyncUrlResolver`]
+struct AsyncShared {
+    client: reqwest::Client,
+    limiter: RateLimiter,
+    retry: RetryPolicy,
+}
+
+/// Async URL resolver for resolving many URLs concurrently
+///
+/// Requests run on a `reqwest::Client` with at most `concurrency` in flight,
+/// and are started no faster than the token-bucket rate limit allows. Rate
+/// limited responses are retried the same way as [`UrlResolver`]. Use the
+/// blocking resolver when URLs are resolved one at a time.
+pub struct AsyncUrlResolver {
+    shared: std::sync::Arc<AsyncShared>,
+    concurrency: usize,
+}
+
+impl AsyncUrlResolver {
+    /// Create an async resolver
+    ///
+    /// # Arguments
+    /// * `concurrency` - Maximum number of requests in flight
+    /// * `delay_ms` - Average spacing between request starts in milliseconds
+    /// * `burst` - Number of requests that may start back-to-back after an idle period
+    pub fn new(concurrency: usize, delay_ms: u64, burst: u32) -> Result<Self> {
+        Self::with_client_config(concurrency, delay_ms, burst, &ClientConfig::default())
+    }
+
+    /// Create an async resolver with HTTP client settings
+    ///
+    /// Arguments are as for [`AsyncUrlResolver::new`].
+    pub fn with_client_config(
+        concurrency: usize,
+        delay_ms: u64,
+        burst: u32,
+        config: &ClientConfig,
+    ) -> Result<Self> {
+        let client = config
+            .async_builder()?
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .map_err(|e| BridgeError::UrlResolution(format!("Failed to create client: {}", e)))?;
+
+        Ok(Self {
+            shared: std::sync::Arc::new(AsyncShared {
+                client,
+                limiter: RateLimiter::new(Duration::from_millis(delay_ms), burst),
+                retry: RetryPolicy::default(),
+            }),
+            concurrency: concurrency.max(1),
+        })
+    }
+
+    /// Configure retrying of rate-limited requests (see [`UrlResolver::with_retry`])
+    pub fn with_retry(mut self, max_retries: u32, base_backoff_ms: u64) -> Self {
+        if let Some(shared) = std::sync::Arc::get_mut(&mut self.shared) {
+            shared.retry.max_retries = max_retries;
+            shared.retry.base_backoff_ms = base_backoff_ms;
+        }
+        self
+    }
+
+    /// Cap the wait before retrying (see [`UrlResolver::with_max_backoff`])
+    pub fn with_max_backoff(mut self, max_backoff_ms: u64) -> Self {
+        if let Some(shared) = std::sync::Arc::get_mut(&mut self.shared) {
+            shared.retry.max_backoff_ms = max_backoff_ms;
+        }
+        self
+    }
+
+    /// Resolve many URLs concurrently, returning results in input order
+    ///
+    /// URLs that already carry LIN data are returned as-is without a request.
+    /// Must be called from within a Tokio runtime.
+    pub async fn resolve_many(&self, urls: &[String]) -> Vec<Result<String>> {
+        let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(self.concurrency));
+        let mut tasks = tokio::task::JoinSet::new();
+
+        for (index, url) in urls.iter().enumerate() {
+            let shared = std::sync::Arc::clone(&self.shared);
+            let semaphore = std::sync::Arc::clone(&semaphore);
+            let url = url.clone();
+            tasks.spawn(async move {
+                if UrlResolver::has_lin(&url) {
+                    return (index, Ok(url));
+                }
+                let Ok(_permit) = semaphore.acquire_owned().await else {
+                    return (
+                        index,
+                        Err(BridgeError::UrlResolution("Resolver shut down".to_string())),
+                    );
+                };
+                (index, shared.resolve(&url).await)
+            });
+        }
+
+        let mut results: Vec<Option<Result<String>>> = urls.iter().map(|_| None).collect();
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok((index, result)) => results[index] = Some(result),
+                Err(e) => log::warn!("URL resolution task failed: {}", e),
+            }
+        }
+
+        results
+            .into_iter()
+            .map(|r| {
+                r.unwrap_or_else(|| {
+                    Err(BridgeError::UrlResolution(
+                        "Task did not complete".to_string(),
+                    ))
+                })
+            })
+            .collect()
+    }
+}
+
+impl AsyncShared {
+    /// Resolve one URL, following redirects and retrying when rate limited
+    async fn resolve(&self, short_url: &str) -> Result<String> {
+        self.limiter.acquire().await;
+
+        let mut resolution = Resolution::new(short_url);
+        loop {
+            let response = self
+                .client
+                .get(&resolution.current_url)
+                .send()
+                .await
+                .map_err(|e| BridgeError::UrlResolution(format!("Request failed: {}", e)))?;
+
+            let status = response.status();
+            let headers = response.headers().clone();
+            let body = if status == reqwest::StatusCode::SERVICE_UNAVAILABLE {
+                response.text().await.unwrap_or_default()
+            } else {
+                String::new()
+            };
+
+            match resolution.advance(&self.retry, status, &headers, &body)? {
+                Next::Done(url) => return Ok(url),
+                Next::Follow => {}
+                Next::Retry(wait) => {
+                    tokio::time::sleep(wait).await;
+                    self.limiter.acquire().await;
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -174,4 +633,272 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_is_shortener() {
+        assert!(UrlResolver::is_shortener("http://tinyurl.com/2n8bjtmz"));
+        assert!(UrlResolver::is_shortener("https://www.tinyurl.com/abc"));
+        assert!(UrlResolver::is_shortener("https://bit.ly/3xYz"));
+        assert!(UrlResolver::is_shortener("https://is.gd/abc"));
+        assert!(UrlResolver::is_shortener("https://t.co/abc"));
+        assert!(UrlResolver::is_shortener("http://ow.ly/abc"));
+        assert!(!UrlResolver::is_shortener(
+            "https://www.bridgebase.com/tools/handviewer.html?lin=pn%7CS%7C"
+        ));
+        assert!(!UrlResolver::is_shortener("https://notbit.ly/abc"));
+        assert!(!UrlResolver::is_shortener("not a url"));
+    }
+
+    #[test]
+    fn test_direct_lin_passthrough() {
+        let url = "https://www.bridgebase.com/tools/handviewer.html?lin=pn%7CS%2CW%2CN%2CE%7C";
+        let mut resolver = UrlResolver::with_config(0, 10, 0);
+        assert_eq!(resolver.resolve(url).unwrap(), url);
+        assert_eq!(resolver.network_requests(), 0);
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("1"), Some(Duration::from_secs(1)));
+        assert_eq!(parse_retry_after(" 120 "), Some(Duration::from_secs(120)));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+
+    #[test]
+    fn test_backoff_doubles() {
+        let resolver = UrlResolver::with_config(0, 10, 0).with_retry(3, 100);
+        assert_eq!(resolver.retry.delay(0, None), Duration::from_millis(100));
+        assert_eq!(resolver.retry.delay(1, None), Duration::from_millis(200));
+        assert_eq!(resolver.retry.delay(2, None), Duration::from_millis(400));
+    }
+
+    #[test]
+    fn test_retry_after_capped_at_max_backoff() {
+        let resolver = UrlResolver::with_config(0, 10, 0)
+            .with_retry(3, 100)
+            .with_max_backoff(5_000);
+        let hours = Some(Duration::from_secs(3 * 3600));
+        assert_eq!(resolver.retry.delay(0, hours), Duration::from_secs(5));
+        assert_eq!(resolver.retry.delay(10, None), Duration::from_secs(5));
+        assert_eq!(
+            resolver.retry.delay(0, Some(Duration::from_secs(2))),
+            Duration::from_secs(2)
+        );
+    }
+
+    #[test]
+    fn test_resolution_advance() {
+        use reqwest::header::{HeaderMap, HeaderValue, LOCATION, RETRY_AFTER};
+        use reqwest::StatusCode;
+
+        let policy = RetryPolicy {
+            max_retries: 1,
+            base_backoff_ms: 10,
+            max_backoff_ms: 1_000,
+        };
+        let mut resolution = Resolution::new("http://tinyurl.com/abc");
+
+        let mut headers = HeaderMap::new();
+        headers.insert(LOCATION, HeaderValue::from_static("/next"));
+        assert_eq!(
+            resolution
+                .advance(&policy, StatusCode::MOVED_PERMANENTLY, &headers, "")
+                .unwrap(),
+            Next::Follow
+        );
+        assert_eq!(resolution.current_url, "http://tinyurl.com/next");
+
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("7200"));
+        assert_eq!(
+            resolution
+                .advance(&policy, StatusCode::TOO_MANY_REQUESTS, &headers, "")
+                .unwrap(),
+            Next::Retry(Duration::from_secs(1))
+        );
+        assert!(matches!(
+            resolution.advance(&policy, StatusCode::TOO_MANY_REQUESTS, &headers, ""),
+            Err(BridgeError::RateLimited)
+        ));
+
+        let headers = HeaderMap::new();
+        assert!(matches!(
+            resolution.advance(&policy, StatusCode::SERVICE_UNAVAILABLE, &headers, "down"),
+            Err(BridgeError::UrlResolution(_))
+        ));
+        assert_eq!(
+            resolution
+                .advance(&policy, StatusCode::OK, &headers, "")
+                .unwrap(),
+            Next::Done("http://tinyurl.com/next".to_string())
+        );
+    }
+
+    /// Serve one canned HTTP response per connection, in order
+    fn spawn_fake_server(responses: Vec<&'static str>) -> String {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf);
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        format!("http://{}/", addr)
+    }
+
+    #[test]
+    fn test_retry_after_then_success() {
+        let url = spawn_fake_server(vec![
+            "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 1\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        ]);
+
+        let mut resolver = UrlResolver::with_config(0, 10, 0).with_retry(2, 10);
+        let resolved = resolver.resolve(&url).unwrap();
+        assert_eq!(resolved, url);
+        assert_eq!(resolver.network_requests(), 2);
+    }
+
+    #[test]
+    fn test_rate_limited_after_retries_exhausted() {
+        let url = spawn_fake_server(vec![
+            "HTTP/1.1 429 Too Many Requests\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 429 Too Many Requests\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        ]);
+
+        let mut resolver = UrlResolver::with_config(0, 10, 0).with_retry(1, 10);
+        assert!(matches!(
+            resolver.resolve(&url),
+            Err(BridgeError::RateLimited)
+        ));
+        assert_eq!(resolver.network_requests(), 2);
+    }
+
+    #[test]
+    fn test_cache_hit_skips_network() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache_path = dir.path().join("urls.json");
+        std::fs::write(
+            &cache_path,
+            r#"{"http://tinyurl.com/abc123": "https://www.bridgebase.com/tools/handviewer.html?lin=pn%7CS%7C"}"#,
+        )
+        .unwrap();
+
+        let mut resolver = UrlResolver::with_config(0, 10, 0)
+            .with_cache(&cache_path)
+            .unwrap();
+
+        for _ in 0..2 {
+            let url = resolver.resolve("http://tinyurl.com/abc123").unwrap();
+            assert!(url.contains("lin="));
+        }
+        // No request was sent, so both answers came from the cache
+        assert_eq!(resolver.network_requests(), 0);
+    }
+
+    #[test]
+    fn test_cache_persists_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache_path = dir.path().join("urls.json");
+
+        let mut cache = UrlCache::load(&cache_path).unwrap();
+        assert!(cache.get("http://bit.ly/x").is_none());
+        cache
+            .insert("http://bit.ly/x", "https://www.bridgebase.com/x")
+            .unwrap();
+
+        let reloaded = UrlCache::load(&cache_path).unwrap();
+        assert_eq!(
+            reloaded.get("http://bit.ly/x"),
+            Some("https://www.bridgebase.com/x")
+        );
+    }
+
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_rate_limiter_spacing_under_load() {
+        let rt = runtime();
+        let limiter = std::sync::Arc::new(RateLimiter::new(Duration::from_millis(20), 1));
+
+        let mut starts = rt.block_on(async {
+            let mut tasks = tokio::task::JoinSet::new();
+            for _ in 0..10 {
+                let limiter = std::sync::Arc::clone(&limiter);
+                tasks.spawn(async move {
+                    limiter.acquire().await;
+                    std::time::Instant::now()
+                });
+            }
+            let mut starts = Vec::new();
+            while let Some(start) = tasks.join_next().await {
+                starts.push(start.unwrap());
+            }
+            starts
+        });
+
+        starts.sort();
+        // The first request goes immediately; nine more need nine intervals
+        let elapsed = starts[9].duration_since(starts[0]);
+        assert!(elapsed >= Duration::from_millis(170), "{:?}", elapsed);
+        for pair in starts.windows(2) {
+            assert!(pair[1].duration_since(pair[0]) >= Duration::from_millis(15));
+        }
+    }
+
+    #[test]
+    fn test_rate_limiter_burst() {
+        let rt = runtime();
+        let limiter = RateLimiter::new(Duration::from_millis(200), 3);
+
+        let elapsed = rt.block_on(async {
+            let start = std::time::Instant::now();
+            for _ in 0..3 {
+                limiter.acquire().await;
+            }
+            start.elapsed()
+        });
+        assert!(elapsed < Duration::from_millis(100), "{:?}", elapsed);
+    }
+
+    #[test]
+    fn test_resolve_many_skips_lin_urls() {
+        let rt = runtime();
+        let resolver = AsyncUrlResolver::new(4, 0, 1).unwrap();
+        let urls = vec![
+            "https://www.bridgebase.com/tools/handviewer.html?lin=pn|a|".to_string(),
+            "https://www.bridgebase.com/tools/handviewer.html?lin=pn|b|".to_string(),
+        ];
+
+        let results = rt.block_on(resolver.resolve_many(&urls));
+        let resolved: Vec<String> = results.into_iter().map(|r| r.unwrap()).collect();
+        assert_eq!(resolved, urls);
+    }
+
+    #[test]
+    fn test_redirect_target() {
+        assert_eq!(
+            redirect_target("https://tinyurl.com/abc", "https://example.com/x").unwrap(),
+            "https://example.com/x"
+        );
+        assert_eq!(
+            redirect_target("https://tinyurl.com/abc", "/other").unwrap(),
+            "https://tinyurl.com/other"
+        );
+    }
 }
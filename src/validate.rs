@@ -0,0 +1,530 @@
+//! Consistency checks for parsed deals
+//!
+//! Hand records imported from BWS or PBN files are occasionally corrupt: a
+//! card dealt to two players, a card missing, or a hand of the wrong size.
+//! Dealer and vulnerability tags can disagree with the board number, and
+//! recorded cardplay can be corrupt too, with a card played by a player who
+//! doesn't hold it or a failure to follow suit. Revokes can also be
+//! collected across a whole hand for rectification.
+
+use crate::cards::card_to_pbn;
+use crate::{dealer_from_board_number, Board, Card, Deal, Direction, Suit, Vulnerability};
+use std::fmt;
+
+/// A problem found in a deal
+#[derive(Debug, Clone, PartialEq)]
+pub enum DealIssue {
+    /// The same card is held more than once
+    DuplicateCard {
+        card: Card,
+        directions: Vec<Direction>,
+    },
+    /// A card is not held by anyone
+    MissingCard(Card),
+    /// A hand does not hold 13 cards
+    WrongHandLength { direction: Direction, len: usize },
+}
+
+impl fmt::Display for DealIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DealIssue::DuplicateCard { card, directions } => {
+                let names: Vec<String> = directions.iter().map(|d| d.to_string()).collect();
+                if directions.len() == 2 && directions[0] != directions[1] {
+                    write!(
+                        f,
+                        "{} appears in both {}",
+                        card_to_pbn(*card),
+                        names.join(" and ")
+                    )
+                } else {
+                    write!(
+                        f,
+                        "{} appears {} times ({})",
+                        card_to_pbn(*card),
+                        directions.len(),
+                        names.join(", ")
+                    )
+                }
+            }
+            DealIssue::MissingCard(card) => {
+                write!(f, "{} is missing from the deal", card_to_pbn(*card))
+            }
+            DealIssue::WrongHandLength { direction, len } => {
+                write!(f, "{} has {} cards (expected 13)", direction, len)
+            }
+        }
+    }
+}
+
+/// Check that a deal holds each of the 52 cards exactly once, 13 per hand
+///
+/// A deal with all four hands empty (no hand record) has no issues.
+pub fn validate_deal(deal: &Deal) -> Vec<DealIssue> {
+    if Direction::ALL.iter().all(|&d| deal.hand(d).is_empty()) {
+        return Vec::new();
+    }
+
+    let mut issues = Vec::new();
+
+    for direction in Direction::ALL {
+        let len = deal.hand(direction).len();
+        if len != 13 {
+            issues.push(DealIssue::WrongHandLength { direction, len });
+        }
+    }
+
+    for card in crate::cards::deck() {
+        let mut directions = Vec::new();
+        for dir in Direction::ALL {
+            let count = deal
+                .hand(dir)
+                .cards()
+                .iter()
+                .filter(|c| **c == card)
+                .count();
+            directions.extend(std::iter::repeat_n(dir, count));
+        }
+
+        match directions.len() {
+            0 => issues.push(DealIssue::MissingCard(card)),
+            1 => {}
+            _ => issues.push(DealIssue::DuplicateCard { card, directions }),
+        }
+    }
+
+    issues
+}
+
+/// Check a board's dealer and vulnerability against its board number
+///
+/// Returns a message describing any mismatch with the standard rotation, or
+/// `None` if they agree. Boards without a number, and a missing dealer, are
+/// not checked.
+pub fn check_dealer_vul_consistency(board: &Board) -> Option<String> {
+    let number = board.number?;
+    let mut problems = Vec::new();
+
+    let expected_dealer = dealer_from_board_number(number);
+    if let Some(dealer) = board.dealer.filter(|&d| d != expected_dealer) {
+        problems.push(format!(
+            "dealer {} (board {} is dealt by {})",
+            dealer.to_char(),
+            number,
+            expected_dealer.to_char()
+        ));
+    }
+
+    let expected_vul = Vulnerability::from_board_number(number);
+    if board.vulnerable != expected_vul {
+        problems.push(format!(
+            "vulnerability {} (board {} is {})",
+            board.vulnerable.to_pbn(),
+            number,
+            expected_vul.to_pbn()
+        ));
+    }
+
+    if problems.is_empty() {
+        None
+    } else {
+        Some(format!("unexpected {}", problems.join(" and ")))
+    }
+}
+
+/// An illegal card in a play sequence
+///
+/// `trick` is 1-based.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlayError {
+    /// The player does not hold the card (or already played it)
+    CardNotHeld {
+        trick: usize,
+        seat: Direction,
+        card: Card,
+    },
+    /// The player failed to follow suit while holding the suit led
+    Revoke {
+        trick: usize,
+        seat: Direction,
+        card: Card,
+        led: Suit,
+    },
+}
+
+impl fmt::Display for PlayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlayError::CardNotHeld { trick, seat, card } => write!(
+                f,
+                "trick {}: {} played {} without holding it",
+                trick,
+                seat,
+                card_to_pbn(*card)
+            ),
+            PlayError::Revoke {
+                trick,
+                seat,
+                card,
+                led,
+            } => write!(
+                f,
+                "trick {}: {} revoked, playing {} on a {} lead",
+                trick,
+                seat,
+                card_to_pbn(*card),
+                led.to_char(),
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PlayError {}
+
+/// Check that a play sequence is legal for a deal
+///
+/// Each card must be held by the player in turn, and players must follow
+/// suit when they can. The winner of each trick (highest trump, otherwise
+/// highest card of the suit led) leads to the next. A final incomplete
+/// trick is checked as far as it goes.
+pub fn validate_play(
+    deal: &Deal,
+    play: &[Card],
+    leader: Direction,
+    trump: Option<Suit>,
+) -> std::result::Result<(), PlayError> {
+    let mut remaining: Vec<(Direction, Vec<Card>)> = Direction::ALL
+        .iter()
+        .map(|&d| (d, deal.hand(d).cards().to_vec()))
+        .collect();
+    let mut leader = leader;
+
+    for (index, trick) in play.chunks(4).enumerate() {
+        let trick_no = index + 1;
+        let led = trick[0].suit;
+        let mut seat = leader;
+        let mut winner = (leader, trick[0]);
+
+        for &card in trick {
+            let Some((_, hand)) = remaining.iter_mut().find(|(d, _)| *d == seat) else {
+                break;
+            };
+            let Some(pos) = hand.iter().position(|&c| c == card) else {
+                return Err(PlayError::CardNotHeld {
+                    trick: trick_no,
+                    seat,
+                    card,
+                });
+            };
+            if card.suit != led && hand.iter().any(|c| c.suit == led) {
+                return Err(PlayError::Revoke {
+                    trick: trick_no,
+                    seat,
+                    card,
+                    led,
+                });
+            }
+            hand.remove(pos);
+
+            if beats(card, winner.1, led, trump) {
+                winner = (seat, card);
+            }
+            seat = seat.next();
+        }
+
+        leader = winner.0;
+    }
+
+    Ok(())
+}
+
+/// A failure to follow suit, with the tricks won from it on
+///
+/// `trick` is 1-based. The trick counts bound the revoke penalty; see
+/// [`crate::scoring::revoke_penalty_tricks`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Revoke {
+    pub trick: usize,
+    pub seat: Direction,
+    pub card: Card,
+    pub led: Suit,
+    /// Whether the revoking player won the revoke trick
+    pub won_revoke_trick: bool,
+    /// Tricks won by the revoking side from the revoke trick on
+    pub side_tricks: usize,
+}
+
+/// Find every revoke in a play sequence
+///
+/// Unlike [`validate_play`] this carries on past a revoke, since the revoke
+/// card is held and the play stays consistent. It stops at a card the
+/// player doesn't hold. Only completed tricks count as won.
+pub fn detect_revokes(
+    deal: &Deal,
+    play: &[Card],
+    leader: Direction,
+    trump: Option<Suit>,
+) -> Vec<Revoke> {
+    let mut remaining: Vec<(Direction, Vec<Card>)> = Direction::ALL
+        .iter()
+        .map(|&d| (d, deal.hand(d).cards().to_vec()))
+        .collect();
+    let mut leader = leader;
+    let mut found: Vec<(usize, Direction, Card, Suit)> = Vec::new();
+    let mut winners: Vec<Direction> = Vec::new();
+
+    'play: for (index, trick) in play.chunks(4).enumerate() {
+        let led = trick[0].suit;
+        let mut seat = leader;
+        let mut winner = (leader, trick[0]);
+
+        for &card in trick {
+            let Some((_, hand)) = remaining.iter_mut().find(|(d, _)| *d == seat) else {
+                break 'play;
+            };
+            let Some(pos) = hand.iter().position(|&c| c == card) else {
+                break 'play;
+            };
+            if card.suit != led && hand.iter().any(|c| c.suit == led) {
+                found.push((index + 1, seat, card, led));
+            }
+            hand.remove(pos);
+
+            if beats(card, winner.1, led, trump) {
+                winner = (seat, card);
+            }
+            seat = seat.next();
+        }
+
+        if trick.len() == 4 {
+            winners.push(winner.0);
+        }
+        leader = winner.0;
+    }
+
+    found
+        .into_iter()
+        .map(|(trick, seat, card, led)| Revoke {
+            trick,
+            seat,
+            card,
+            led,
+            won_revoke_trick: winners.get(trick - 1) == Some(&seat),
+            side_tricks: winners
+                .iter()
+                .skip(trick - 1)
+                .filter(|&&w| same_side(w, seat))
+                .count(),
+        })
+        .collect()
+}
+
+/// Whether two seats are partners (or the same seat)
+pub(crate) fn same_side(a: Direction, b: Direction) -> bool {
+    a == b || crate::seat::partner(a) == b
+}
+
+/// Whether `card` beats the current winning card of a trick
+fn beats(card: Card, winning: Card, led: Suit, trump: Option<Suit>) -> bool {
+    if card.suit == winning.suit {
+        return card.rank > winning.rank;
+    }
+    match trump {
+        Some(t) if card.suit == t => true,
+        Some(t) if winning.suit == t => false,
+        _ => card.suit == led && winning.suit != led,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Hand, Rank, Suit};
+
+    #[test]
+    fn test_valid_deal() {
+        let deal =
+            Deal::from_pbn("N:K843.T542.J6.863 AQJ7.K.Q75.AT942 962.AJ7.KT82.J75 T5.Q9863.A943.KQ")
+                .unwrap();
+        assert!(validate_deal(&deal).is_empty());
+    }
+
+    #[test]
+    fn test_empty_deal() {
+        assert!(validate_deal(&Deal::new()).is_empty());
+    }
+
+    #[test]
+    fn test_duplicated_ace() {
+        // East holds the spade ace as well as North; South's spade 2 is nowhere
+        let mut deal = Deal::new();
+        deal.set_hand(
+            Direction::North,
+            Hand::from_pbn("AK84.T542.J6.863").unwrap(),
+        );
+        deal.set_hand(Direction::East, Hand::from_pbn("AQJ7.K.Q75.AT942").unwrap());
+        deal.set_hand(
+            Direction::South,
+            Hand::from_pbn("963.AJ7.KT82.J75").unwrap(),
+        );
+        deal.set_hand(Direction::West, Hand::from_pbn("T5.Q9863.A943.KQ").unwrap());
+
+        let issues = validate_deal(&deal);
+        let ace = Card::new(Suit::Spades, Rank::Ace);
+        assert!(issues.contains(&DealIssue::DuplicateCard {
+            card: ace,
+            directions: vec![Direction::North, Direction::East],
+        }));
+        assert!(issues.contains(&DealIssue::MissingCard(Card::new(Suit::Spades, Rank::Two))));
+        assert!(!issues
+            .iter()
+            .any(|i| matches!(i, DealIssue::WrongHandLength { .. })));
+
+        let duplicate = issues
+            .iter()
+            .find(|i| matches!(i, DealIssue::DuplicateCard { .. }))
+            .unwrap();
+        assert_eq!(duplicate.to_string(), "SA appears in both North and East");
+    }
+
+    #[test]
+    fn test_short_hand() {
+        let mut deal =
+            Deal::from_pbn("N:K843.T542.J6.863 AQJ7.K.Q75.AT942 962.AJ7.KT82.J75 T5.Q9863.A943.KQ")
+                .unwrap();
+        deal.set_hand(Direction::West, Hand::from_pbn("T5.Q9863.A943.K").unwrap());
+
+        let issues = validate_deal(&deal);
+        assert!(issues.contains(&DealIssue::WrongHandLength {
+            direction: Direction::West,
+            len: 12,
+        }));
+        assert!(issues.contains(&DealIssue::MissingCard(Card::new(Suit::Clubs, Rank::Queen))));
+    }
+
+    fn small_deal(north: &str, east: &str, south: &str, west: &str) -> Deal {
+        let mut deal = Deal::new();
+        for (dir, pbn) in [
+            (Direction::North, north),
+            (Direction::East, east),
+            (Direction::South, south),
+            (Direction::West, west),
+        ] {
+            deal.set_hand(dir, Hand::from_pbn(pbn).unwrap());
+        }
+        deal
+    }
+
+    fn cards(s: &str) -> Vec<Card> {
+        s.split_whitespace()
+            .map(|c| {
+                let mut chars = c.chars();
+                let suit = Suit::from_char(chars.next().unwrap()).unwrap();
+                let rank = Rank::from_char(chars.next().unwrap()).unwrap();
+                Card::new(suit, rank)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_legal_play() {
+        let deal = small_deal("A.2..", "K.3..", "2.A..", "Q.4..");
+        let play = cards("SQ SA SK S2 H2 H3 HA H4");
+        assert_eq!(validate_play(&deal, &play, Direction::West, None), Ok(()));
+    }
+
+    #[test]
+    fn test_ruff_wins_trick() {
+        // North ruffs the spade lead, so North leads to the second trick
+        let deal = small_deal(".2.5.", "K.3..", "2.A..", "Q.4..");
+        let play = cards("SQ H2 SK S2 D5 H3 HA H4");
+        let trump = Some(Suit::Hearts);
+        assert_eq!(validate_play(&deal, &play, Direction::West, trump), Ok(()));
+    }
+
+    #[test]
+    fn test_south_revokes() {
+        let deal = small_deal("A.2..", "K.3..", "2.A..", "Q.4..");
+        let play = cards("SQ SA SK HA");
+        let err = validate_play(&deal, &play, Direction::West, None).unwrap_err();
+        assert_eq!(
+            err,
+            PlayError::Revoke {
+                trick: 1,
+                seat: Direction::South,
+                card: Card::new(Suit::Hearts, Rank::Ace),
+                led: Suit::Spades,
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "trick 1: South revoked, playing HA on a S lead"
+        );
+    }
+
+    #[test]
+    fn test_card_not_held() {
+        let deal = small_deal("A.2..", "K.3..", "2.A..", "Q.4..");
+        let play = cards("SQ SA SK S2 SA");
+        assert_eq!(
+            validate_play(&deal, &play, Direction::West, None),
+            Err(PlayError::CardNotHeld {
+                trick: 2,
+                seat: Direction::North,
+                card: Card::new(Suit::Spades, Rank::Ace),
+            })
+        );
+    }
+
+    #[test]
+    fn test_dealer_vul_consistency() {
+        let board = Board::new()
+            .with_number(2)
+            .with_dealer(Direction::East)
+            .with_vulnerability(Vulnerability::NorthSouth);
+        assert_eq!(check_dealer_vul_consistency(&board), None);
+
+        let board = board.with_dealer(Direction::North);
+        assert_eq!(
+            check_dealer_vul_consistency(&board).as_deref(),
+            Some("unexpected dealer N (board 2 is dealt by E)")
+        );
+
+        let board = board.with_vulnerability(Vulnerability::None);
+        assert_eq!(
+            check_dealer_vul_consistency(&board).as_deref(),
+            Some(
+                "unexpected dealer N (board 2 is dealt by E) and vulnerability None (board 2 is NS)"
+            )
+        );
+
+        assert_eq!(check_dealer_vul_consistency(&Board::new()), None);
+    }
+
+    #[test]
+    fn test_detect_revokes() {
+        // South discards the heart ace on a spade lead that North wins;
+        // West wins the second trick
+        let deal = small_deal("A.2..", "K.3..", "2.A..", "Q.4..");
+        let play = cards("SQ SA SK HA H2 H3 S2 H4");
+        let revokes = detect_revokes(&deal, &play, Direction::West, None);
+        assert_eq!(
+            revokes,
+            vec![Revoke {
+                trick: 1,
+                seat: Direction::South,
+                card: Card::new(Suit::Hearts, Rank::Ace),
+                led: Suit::Spades,
+                won_revoke_trick: false,
+                side_tricks: 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_detect_revokes_legal_play() {
+        let deal = small_deal("A.2..", "K.3..", "2.A..", "Q.4..");
+        let play = cards("SQ SA SK S2 H2 H3 HA H4");
+        assert!(detect_revokes(&deal, &play, Direction::West, None).is_empty());
+    }
+}
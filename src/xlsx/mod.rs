@@ -1,6 +1,11 @@
 pub mod writer;
 
 pub use writer::write_boards_to_xlsx;
+pub use writer::write_boards_to_xlsx_buf;
 pub use writer::write_bws_to_xlsx;
+pub use writer::write_bws_to_xlsx_buf;
+pub use writer::write_bws_to_xlsx_streaming;
 pub use writer::write_bws_to_xlsx_with_masterpoints;
 pub use writer::write_combined_to_xlsx;
+pub use writer::write_combined_to_xlsx_buf;
+pub use writer::{ColorScheme, ImpMethod, XlsxOptions, SIT_OUT_MP_PCT};
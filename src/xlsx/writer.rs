@@ -1,21 +1,164 @@
+use crate::bws::ranking::{
+    average_mp_pct, dense_rank_pairs, rank_overall, PairMatchpointTotals, PairMatchpoints,
+};
+use crate::bws::tables::ReceivedDataRow;
 use crate::error::Result;
-use crate::{calculate_matchpoints, Board, Contract, Direction, Hand, Rank, Suit, Vulnerability};
+use crate::scoring::{
+    butler_datum, butler_imps, calculate_cross_imps, calculate_matchpoints_neuberg,
+    calculate_score_for_result, MatchpointPct,
+};
+use crate::{calculate_matchpoints, Board, Direction, Hand, Rank, Suit};
 use rust_xlsxwriter::{
-    ConditionalFormat3ColorScale, Format, FormatAlign, FormatBorder, Workbook, Worksheet,
+    Color, ConditionalFormat3ColorScale, Format, FormatAlign, FormatBorder, Workbook, Worksheet,
 };
-use std::collections::HashMap;
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
+/// Column headers of the Hand Records sheet
+pub(crate) const HAND_RECORD_HEADERS: [&str; 18] = [
+    "Board",
+    "Dealer",
+    "Vul",
+    "North",
+    "East",
+    "South",
+    "West",
+    "N HCP",
+    "E HCP",
+    "S HCP",
+    "W HCP",
+    "DD Tricks",
+    "Optimum",
+    "Par",
+    "Contract",
+    "Declarer",
+    "Result",
+    "Url",
+];
+
+/// Column headers of the combined Game Results sheet
+pub(crate) const COMBINED_RESULT_HEADERS: [&str; 22] = [
+    "Board", "Section", "Table", "Round", "NS Pair", "EW Pair", "N Name", "E Name", "S Name",
+    "W Name", "Declarer", "Contract", "Result", "Lead", "Score", "NS MP%", "EW MP%", "Vul",
+    "North", "East", "South", "West",
+];
+
+/// Color scale applied to matchpoint percentage columns
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorScheme {
+    /// Red (low) -> Yellow (mid) -> Green (high)
+    #[default]
+    RedYellowGreen,
+    /// Red (low) -> White (mid) -> Green (high), so average results stay uncolored;
+    /// better suited to IMP-style scoring where the midpoint is neutral
+    RedWhiteGreen,
+}
+
+impl ColorScheme {
+    /// Minimum, midpoint and maximum colors as RGB hex strings
+    pub fn colors(&self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            ColorScheme::RedYellowGreen => ("F8696B", "FFEB84", "63BE7B"),
+            ColorScheme::RedWhiteGreen => ("F8696B", "FFFFFF", "63BE7B"),
+        }
+    }
+}
+
+/// How IMP-scored sections compare the results on a board
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ImpMethod {
+    /// Against the board's Butler datum (the average with the extremes dropped)
+    #[default]
+    Butler,
+    /// Against every other result, averaged over the comparisons
+    CrossImps,
+}
+
+impl ImpMethod {
+    /// Column heading for IMP totals scored this way
+    pub fn label(&self) -> &'static str {
+        match self {
+            ImpMethod::Butler => "Butler IMPs",
+            ImpMethod::CrossImps => "Cross-IMPs",
+        }
+    }
+}
+
+/// Presentation options for the combined workbook
+#[derive(Debug, Clone, PartialEq)]
+pub struct XlsxOptions {
+    /// Color-scale the NS/EW MP% columns
+    pub conditional_formatting: bool,
+    /// Add an autofilter over the Game Results table
+    pub autofilter: bool,
+    /// Keep the header row visible when scrolling
+    pub freeze_header: bool,
+    /// Colors used when `conditional_formatting` is set
+    pub color_scheme: ColorScheme,
+    /// Add the per-pair Scorecards sheet
+    pub scorecards: bool,
+    /// Add the Frequencies sheet listing each board's distinct scores
+    pub frequencies: bool,
+    /// How pairs in IMP-scored sections are totalled and ranked
+    pub imp_method: ImpMethod,
+    /// MP% credited for each board a pair sits out against a phantom pair
+    pub sit_out_mp_pct: f64,
+    /// Minimum name similarity (0.0-1.0) for matching a player to the
+    /// masterpoint data when the exact number/name lookup misses
+    #[cfg(feature = "fuzzy")]
+    pub fuzzy_threshold: Option<f64>,
+}
+
+impl Default for XlsxOptions {
+    fn default() -> Self {
+        Self {
+            conditional_formatting: true,
+            autofilter: true,
+            freeze_header: false,
+            color_scheme: ColorScheme::default(),
+            scorecards: false,
+            frequencies: false,
+            imp_method: ImpMethod::default(),
+            sit_out_mp_pct: SIT_OUT_MP_PCT,
+            #[cfg(feature = "fuzzy")]
+            fuzzy_threshold: None,
+        }
+    }
+}
+
+impl XlsxOptions {
+    /// Options with autofilter and conditional formatting turned off, for
+    /// output that will be diffed or post-processed
+    pub fn plain() -> Self {
+        Self {
+            conditional_formatting: false,
+            autofilter: false,
+            ..Self::default()
+        }
+    }
+}
+
 /// Write boards to an Excel file
 pub fn write_boards_to_xlsx(boards: &[Board], path: &Path) -> Result<()> {
+    build_boards_workbook(boards)?.save(path)?;
+    Ok(())
+}
+
+/// Write boards to an in-memory Excel file
+pub fn write_boards_to_xlsx_buf(boards: &[Board]) -> Result<Vec<u8>> {
+    Ok(build_boards_workbook(boards)?.save_to_buffer()?)
+}
+
+/// Build the hand records workbook
+fn build_boards_workbook(boards: &[Board]) -> Result<Workbook> {
     let mut workbook = Workbook::new();
 
     // Add the hand records worksheet
     let worksheet = workbook.add_worksheet();
     write_hand_records_sheet(worksheet, boards)?;
 
-    workbook.save(path)?;
-    Ok(())
+    Ok(workbook)
 }
 
 /// Write hand records to a worksheet
@@ -35,6 +178,10 @@ fn write_hand_records_sheet(sheet: &mut Worksheet, boards: &[Board]) -> Result<(
     sheet.set_column_width(11, 24)?; // DD Tricks
     sheet.set_column_width(12, 12)?; // Optimum Score
     sheet.set_column_width(13, 14)?; // Par Contract
+    sheet.set_column_width(14, 10)?; // Contract
+    sheet.set_column_width(15, 8)?; // Declarer
+    sheet.set_column_width(16, 8)?; // Result
+    sheet.set_column_width(17, 12)?; // Handviewer link
 
     // Header format
     let header_format = Format::new()
@@ -43,24 +190,7 @@ fn write_hand_records_sheet(sheet: &mut Worksheet, boards: &[Board]) -> Result<(
         .set_border_bottom(FormatBorder::Thin);
 
     // Write headers
-    let headers = [
-        "Board",
-        "Dealer",
-        "Vul",
-        "North",
-        "East",
-        "South",
-        "West",
-        "N HCP",
-        "E HCP",
-        "S HCP",
-        "W HCP",
-        "DD Tricks",
-        "Optimum",
-        "Par",
-    ];
-
-    for (col, header) in headers.iter().enumerate() {
+    for (col, header) in HAND_RECORD_HEADERS.iter().enumerate() {
         sheet.write_string_with_format(0, col as u16, *header, &header_format)?;
     }
 
@@ -117,6 +247,27 @@ fn write_hand_records_sheet(sheet: &mut Worksheet, boards: &[Board]) -> Result<(
         if let Some(ref par) = board.par_contract {
             sheet.write_string_with_format(row, 13, par, &center_format)?;
         }
+
+        // Played contract, declarer and tricks taken (PBN result files)
+        if let Some(ref contract) = board.contract {
+            sheet.write_string_with_format(row, 14, contract, &center_format)?;
+        }
+        if let Some(declarer) = board.declarer {
+            sheet.write_string_with_format(
+                row,
+                15,
+                declarer.to_char().to_string(),
+                &center_format,
+            )?;
+        }
+        if let Some(result) = board.result {
+            sheet.write_number_with_format(row, 16, f64::from(result), &center_format)?;
+        }
+
+        // BBO handviewer link
+        if let Some(url) = crate::lin::board_to_handviewer_url(board) {
+            sheet.write_url_with_text(row, 17, url.as_str(), "Handviewer")?;
+        }
     }
 
     // Set worksheet name
@@ -126,7 +277,7 @@ fn write_hand_records_sheet(sheet: &mut Worksheet, boards: &[Board]) -> Result<(
 }
 
 /// Format a hand in compact notation (S:AKQ H:JT9 D:876 C:5432)
-fn format_hand_compact(hand: &Hand) -> String {
+pub(crate) fn format_hand_compact(hand: &Hand) -> String {
     let mut parts = Vec::new();
 
     for suit in Suit::ALL {
@@ -151,31 +302,113 @@ fn format_hand_compact(hand: &Hand) -> String {
     }
 }
 
-/// Pair matchpoint summary
-#[derive(Debug, Default, Clone)]
-struct PairMatchpoints {
-    boards_played: u32,
-    total_mp_pct: f64, // Sum of matchpoint percentages
-}
-
-/// Per-pair matchpoint totals keyed by (section, pair_number, is_ns)
-type PairMatchpointTotals = HashMap<(i32, i32, bool), PairMatchpoints>;
+/// Default MP% credited for a board sat out against a phantom pair
+pub const SIT_OUT_MP_PCT: f64 = 50.0;
 
-/// Calculate matchpoints for all results in BwsData
+/// Calculate matchpoints for all results in BwsData, crediting sit-outs
+/// with [`SIT_OUT_MP_PCT`]
+/// Results in IMP-scored sections aren't matchpointed.
 /// Returns: (per-result matchpoints, per-pair totals)
 /// Pair key is (section, pair_number, is_ns)
-fn calculate_all_matchpoints(
+pub(crate) fn calculate_all_matchpoints(
     data: &crate::bws::BwsData,
-) -> (Vec<Option<f64>>, PairMatchpointTotals) {
+) -> (Vec<Option<MatchpointPct>>, PairMatchpointTotals) {
+    calculate_all_matchpoints_with_sit_out(data, SIT_OUT_MP_PCT)
+}
+
+/// Calculate matchpoints for all results in BwsData, crediting each board a
+/// pair sat out against its section's phantom pair with `sit_out_mp_pct`
+pub(crate) fn calculate_all_matchpoints_with_sit_out(
+    data: &crate::bws::BwsData,
+    sit_out_mp_pct: f64,
+) -> (Vec<Option<MatchpointPct>>, PairMatchpointTotals) {
     let results = &data.received_data;
+    let matchpoints = calculate_row_matchpoints(results, &imp_section_ids(data));
 
-    // Calculate scores for all results
-    let scores: Vec<Option<i32>> = results.iter().map(calculate_score_for_result).collect();
+    // Aggregate matchpoints per pair
+    // In a Mitchell movement, pair_ns is the NS pair number and pair_ew is the EW pair number
+    let mut pair_totals: HashMap<(i32, i32, bool), PairMatchpoints> = HashMap::new();
 
-    // Group results by board for matchpoint calculation
-    let mut board_results: HashMap<i32, Vec<(usize, i32)>> = HashMap::new();
     for (idx, result) in results.iter().enumerate() {
-        if let Some(score) = scores[idx] {
+        if let Some(mp) = matchpoints[idx] {
+            // NS pair gets the NS matchpoints
+            let ns_key = (result.section, result.pair_ns, true);
+            let ns_entry = pair_totals.entry(ns_key).or_default();
+            ns_entry.boards_played += 1;
+            ns_entry.total_mp_pct += mp.ns;
+
+            // EW pair gets the EW matchpoints
+            let ew_key = (result.section, result.pair_ew, false);
+            let ew_entry = pair_totals.entry(ew_key).or_default();
+            ew_entry.boards_played += 1;
+            ew_entry.total_mp_pct += mp.ew;
+        }
+    }
+
+    add_sit_outs(data, &mut pair_totals, sit_out_mp_pct);
+
+    (matchpoints, pair_totals)
+}
+
+/// Credit pairs that sat out rounds against a section's phantom pair
+///
+/// In a section with a `missing_pair`, the pairs due to meet the phantom
+/// have no result for those boards. Each pair is taken to owe as many boards
+/// as the most boards played by any pair in its section; the shortfall is
+/// recorded as sit-out boards and credited with `sit_out_mp_pct` each, so
+/// the pair's average isn't based on fewer boards than the rest of the field.
+fn add_sit_outs(
+    data: &crate::bws::BwsData,
+    pair_totals: &mut PairMatchpointTotals,
+    sit_out_mp_pct: f64,
+) {
+    for section in data.sections.iter().filter(|s| s.missing_pair != 0) {
+        let full = pair_totals
+            .iter()
+            .filter(|(key, _)| key.0 == section.id)
+            .map(|(_, totals)| totals.boards_played)
+            .max()
+            .unwrap_or(0);
+        for (_, totals) in pair_totals
+            .iter_mut()
+            .filter(|(key, _)| key.0 == section.id)
+        {
+            let missed = full - totals.boards_played;
+            totals.sit_out_boards = missed;
+            totals.total_mp_pct += sit_out_mp_pct * f64::from(missed);
+        }
+    }
+}
+
+/// Calculate the NS and EW matchpoint percentages of each result, in
+/// iteration order
+///
+/// Only each row's board number and score are kept between the grouping pass
+/// and the matchpoint pass, so rows can be borrowed or produced on the fly.
+/// Rows in `imp_sections` are left unscored so they don't join the
+/// matchpoint pool of a section that played the same boards.
+pub(crate) fn calculate_row_matchpoints<I>(
+    rows: I,
+    imp_sections: &[i32],
+) -> Vec<Option<MatchpointPct>>
+where
+    I: IntoIterator,
+    I::Item: Borrow<ReceivedDataRow>,
+{
+    // Group results by board for matchpoint calculation. Director-assigned
+    // averages take their percentage directly and aren't compared.
+    let mut board_results: HashMap<i32, Vec<(usize, i32)>> = HashMap::new();
+    let mut adjusted: Vec<(usize, MatchpointPct)> = Vec::new();
+    let mut row_count = 0;
+    for (idx, result) in rows.into_iter().enumerate() {
+        let result: &ReceivedDataRow = result.borrow();
+        row_count += 1;
+        if imp_sections.contains(&result.section) {
+            continue;
+        }
+        if let Some(pct) = crate::scoring::adjusted_score_pct(result) {
+            adjusted.push((idx, pct));
+        } else if let Some(score) = calculate_score_for_result(result) {
             board_results
                 .entry(result.board)
                 .or_default()
@@ -183,62 +416,230 @@ fn calculate_all_matchpoints(
         }
     }
 
+    // Boards played a different number of times are Neuberg-adjusted to the
+    // top of the most-played board so their percentages are comparable
+    let max_played = board_results.values().map(Vec::len).max().unwrap_or(0);
+    let uneven = board_results.values().any(|b| b.len() != max_played);
+
     // Calculate matchpoints for each board
-    let mut matchpoints: Vec<Option<f64>> = vec![None; results.len()];
+    let mut matchpoints: Vec<Option<MatchpointPct>> = vec![None; row_count];
     for board_scores in board_results.values() {
         let ns_scores: Vec<i32> = board_scores.iter().map(|(_, s)| *s).collect();
-        let mps = calculate_matchpoints(&ns_scores);
+        let mps = if uneven && max_played > 1 {
+            let top = (max_played - 1) as f64;
+            calculate_matchpoints_neuberg(&ns_scores, top)
+                .iter()
+                .map(|mp| mp / top * 100.0)
+                .collect()
+        } else {
+            calculate_matchpoints(&ns_scores)
+        };
         for (i, (idx, _)) in board_scores.iter().enumerate() {
-            matchpoints[*idx] = Some(mps[i]);
+            matchpoints[*idx] = Some(MatchpointPct::from_ns(mps[i]));
         }
     }
+    for (idx, pct) in adjusted {
+        matchpoints[idx] = Some(pct);
+    }
 
-    // Aggregate matchpoints per pair
-    // In a Mitchell movement, pair_ns is the NS pair number and pair_ew is the EW pair number
-    let mut pair_totals: HashMap<(i32, i32, bool), PairMatchpoints> = HashMap::new();
+    matchpoints
+}
 
-    for (idx, result) in results.iter().enumerate() {
-        if let Some(mp) = matchpoints[idx] {
-            // NS pair gets the NS matchpoints
-            let ns_key = (result.section, result.pair_ns, true);
-            let ns_entry = pair_totals.entry(ns_key).or_default();
-            ns_entry.boards_played += 1;
-            ns_entry.total_mp_pct += mp;
+/// A pair's IMP total across the boards it played in an IMP-scored section
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct PairImps {
+    pub(crate) boards_played: u32,
+    pub(crate) total_imps: f64,
+}
 
-            // EW pair gets the EW matchpoints (100 - NS)
-            let ew_key = (result.section, result.pair_ew, false);
-            let ew_entry = pair_totals.entry(ew_key).or_default();
-            ew_entry.boards_played += 1;
-            ew_entry.total_mp_pct += 100.0 - mp;
+/// Per-pair IMP totals keyed by (section, pair_number, is_ns)
+pub(crate) type PairImpTotals = HashMap<(i32, i32, bool), PairImps>;
+
+/// Calculate IMP totals for results in IMP-scored sections
+///
+/// With [`ImpMethod::Butler`] each NS score is compared against its board's
+/// datum, and boards with fewer than 3 scores have no meaningful datum and are
+/// skipped. With [`ImpMethod::CrossImps`] each score is compared against every
+/// other score on the board, and boards played only once are skipped. The EW
+/// pair receives the negated IMPs.
+pub(crate) fn calculate_imp_totals(data: &crate::bws::BwsData, method: ImpMethod) -> PairImpTotals {
+    let imp_sections = imp_section_ids(data);
+
+    // Group IMP-section scores by board
+    let mut board_results: HashMap<i32, Vec<(usize, i32)>> = HashMap::new();
+    for (idx, result) in data.received_data.iter().enumerate() {
+        if !imp_sections.contains(&result.section) {
+            continue;
+        }
+        if let Some(score) = calculate_score_for_result(result) {
+            board_results
+                .entry(result.board)
+                .or_default()
+                .push((idx, score));
         }
     }
 
-    (matchpoints, pair_totals)
+    let mut pair_totals = PairImpTotals::new();
+    for board_scores in board_results.values() {
+        let ns_scores: Vec<i32> = board_scores.iter().map(|(_, s)| *s).collect();
+        let imps: Vec<f64> = match method {
+            ImpMethod::Butler if ns_scores.len() >= 3 => {
+                let datum = butler_datum(&ns_scores);
+                ns_scores
+                    .iter()
+                    .map(|&score| f64::from(butler_imps(score, datum)))
+                    .collect()
+            }
+            ImpMethod::CrossImps if ns_scores.len() >= 2 => calculate_cross_imps(&ns_scores),
+            _ => continue,
+        };
+
+        for (&(idx, _), imps) in board_scores.iter().zip(imps) {
+            let result = &data.received_data[idx];
+            let ns = pair_totals
+                .entry((result.section, result.pair_ns, true))
+                .or_default();
+            ns.boards_played += 1;
+            ns.total_imps += imps;
+            let ew = pair_totals
+                .entry((result.section, result.pair_ew, false))
+                .or_default();
+            ew.boards_played += 1;
+            ew.total_imps -= imps;
+        }
+    }
+
+    pair_totals
+}
+
+/// Ids of the IMP-scored sections
+fn imp_section_ids(data: &crate::bws::BwsData) -> Vec<i32> {
+    data.sections
+        .iter()
+        .filter(|s| s.scoring_type == Some(1))
+        .map(|s| s.id)
+        .collect()
+}
+
+/// Whether any section is IMP-scored
+fn has_imp_sections(data: &crate::bws::BwsData) -> bool {
+    data.sections.iter().any(|s| s.scoring_type == Some(1))
+}
+
+/// Whether any section is matchpointed (the default when there is no
+/// Section table)
+fn has_mp_sections(data: &crate::bws::BwsData) -> bool {
+    data.sections.is_empty() || data.sections.iter().any(|s| s.scoring_type != Some(1))
+}
+
+/// Number format for IMP totals: Butler IMPs are whole, cross-IMPs are averages
+fn imp_number_format(method: ImpMethod) -> Format {
+    let num_format = match method {
+        ImpMethod::Butler => "0",
+        ImpMethod::CrossImps => "0.00",
+    };
+    Format::new()
+        .set_align(FormatAlign::Center)
+        .set_num_format(num_format)
+}
+
+/// Dense rank of each pair by total IMPs within its section and direction
+fn rank_imp_pairs(imp_totals: &PairImpTotals) -> HashMap<(i32, i32, bool), u32> {
+    dense_rank_pairs(
+        imp_totals,
+        |key| (key.0, key.2),
+        |totals| Some(totals.total_imps),
+    )
 }
 
 /// Write BWS data to an Excel file
 pub fn write_bws_to_xlsx(data: &crate::bws::BwsData, path: &Path) -> Result<()> {
-    write_bws_to_xlsx_with_masterpoints(data, path, None)
+    write_bws_to_xlsx_with_masterpoints(data, path, None, &XlsxOptions::default())
 }
 
 /// Write BWS data to an Excel file with optional masterpoint data
+///
+/// `options` choose the optional sheets, the IMP method and the sit-out
+/// credit; the formatting options apply to the combined workbook only.
 pub fn write_bws_to_xlsx_with_masterpoints(
     data: &crate::bws::BwsData,
     path: &Path,
     member_data: Option<&HashMap<String, crate::acbl::MemberInfo>>,
+    options: &XlsxOptions,
 ) -> Result<()> {
+    build_bws_workbook(data, member_data, options)?.save(path)?;
+    Ok(())
+}
+
+/// Write BWS data to an in-memory Excel file with optional masterpoint data
+pub fn write_bws_to_xlsx_buf(
+    data: &crate::bws::BwsData,
+    member_data: Option<&HashMap<String, crate::acbl::MemberInfo>>,
+    options: &XlsxOptions,
+) -> Result<Vec<u8>> {
+    Ok(build_bws_workbook(data, member_data, options)?.save_to_buffer()?)
+}
+
+/// Write BWS results to an Excel file with only the Game Results sheet,
+/// without holding all the rows in memory
+///
+/// `rows` is called twice and must yield the same rows each time, e.g. by
+/// re-reading an export. The first pass keeps only each row's board and
+/// score to compute matchpoints; the second writes the rows as they arrive.
+/// Rows in `imp_sections` are written without matchpoints.
+pub fn write_bws_to_xlsx_streaming<F, I>(rows: F, imp_sections: &[i32], path: &Path) -> Result<()>
+where
+    F: Fn() -> I,
+    I: Iterator<Item = ReceivedDataRow>,
+{
+    let mut workbook = Workbook::new();
+
+    let matchpoints = calculate_row_matchpoints(rows(), imp_sections);
+
+    let results_sheet = workbook.add_worksheet();
+    write_game_results_sheet(results_sheet, rows(), &matchpoints)?;
+
+    workbook.save(path)?;
+    Ok(())
+}
+
+/// Build the BWS results workbook
+fn build_bws_workbook(
+    data: &crate::bws::BwsData,
+    member_data: Option<&HashMap<String, crate::acbl::MemberInfo>>,
+    options: &XlsxOptions,
+) -> Result<Workbook> {
     let mut workbook = Workbook::new();
 
     // Calculate matchpoints once for use in multiple sheets
-    let (matchpoints, pair_totals) = calculate_all_matchpoints(data);
+    let (matchpoints, pair_totals) =
+        calculate_all_matchpoints_with_sit_out(data, options.sit_out_mp_pct);
 
     // Add Game Results sheet
     let results_sheet = workbook.add_worksheet();
-    write_game_results_sheet(results_sheet, data, &matchpoints)?;
+    write_game_results_sheet(results_sheet, &data.received_data, &matchpoints)?;
 
     // Add Players sheet with matchpoint totals
     let players_sheet = workbook.add_worksheet();
-    write_players_sheet(players_sheet, data, &pair_totals, member_data)?;
+    write_players_sheet(players_sheet, data, &pair_totals, member_data, options)?;
+
+    // Add IMP Rankings sheet when any section is IMP-scored
+    if has_imp_sections(data) {
+        let imp_sheet = workbook.add_worksheet();
+        write_imp_rankings_sheet(imp_sheet, data, options.imp_method)?;
+    }
+
+    // Add Frequencies sheet if requested
+    if options.frequencies && !data.received_data.is_empty() {
+        let frequency_sheet = workbook.add_worksheet();
+        write_frequency_sheet(frequency_sheet, data, &matchpoints)?;
+    }
+
+    // Add Overall Rankings sheet when several sections share a field
+    if data.sections.len() > 1 {
+        let overall_sheet = workbook.add_worksheet();
+        write_overall_rankings_sheet(overall_sheet, data, &pair_totals)?;
+    }
 
     // Add Sections sheet if there are sections
     if !data.sections.is_empty() {
@@ -252,45 +653,21 @@ pub fn write_bws_to_xlsx_with_masterpoints(
         write_hand_records_sheet(hands_sheet, &data.boards)?;
     }
 
-    workbook.save(path)?;
-    Ok(())
-}
-
-/// Calculate score for a result row
-fn calculate_score_for_result(result: &crate::bws::tables::ReceivedDataRow) -> Option<i32> {
-    let contract = Contract::parse(&result.contract)?;
-    let tricks_relative = Contract::parse_result(&result.result)?;
-
-    // Determine vulnerability from board number
-    let board_num = result.board as u32;
-    let vul = Vulnerability::from_board_number(board_num);
-
-    // Check if declarer is vulnerable
-    let declarer_dir = match result.ns_ew.as_str() {
-        "N" => Direction::North,
-        "S" => Direction::South,
-        "E" => Direction::East,
-        "W" => Direction::West,
-        _ => return None,
-    };
-    let declarer_vul = vul.is_vulnerable(declarer_dir);
-
-    let score = contract.score(tricks_relative, declarer_vul);
-
-    // Return score from NS perspective
-    Some(match result.ns_ew.as_str() {
-        "N" | "S" => score,
-        "E" | "W" => -score,
-        _ => score,
-    })
+    Ok(workbook)
 }
 
 /// Write game results to a worksheet
-fn write_game_results_sheet(
+///
+/// Rows are written in iteration order, which must match `matchpoints`.
+fn write_game_results_sheet<I>(
     sheet: &mut Worksheet,
-    data: &crate::bws::BwsData,
-    matchpoints: &[Option<f64>],
-) -> Result<()> {
+    rows: I,
+    matchpoints: &[Option<MatchpointPct>],
+) -> Result<()>
+where
+    I: IntoIterator,
+    I::Item: Borrow<ReceivedDataRow>,
+{
     sheet.set_name("Game Results")?;
 
     // Set column widths
@@ -331,15 +708,9 @@ fn write_game_results_sheet(
         .set_align(FormatAlign::Right)
         .set_num_format("0.0");
 
-    // Calculate scores for all results
-    let scores: Vec<Option<i32>> = data
-        .received_data
-        .iter()
-        .map(calculate_score_for_result)
-        .collect();
-
     // Write result data (in original order to match matchpoints indices)
-    for (row_idx, result) in data.received_data.iter().enumerate() {
+    for (row_idx, result) in rows.into_iter().enumerate() {
+        let result: &ReceivedDataRow = result.borrow();
         let row = (row_idx + 1) as u32;
 
         sheet.write_number_with_format(row, 0, result.board as f64, &center_format)?;
@@ -367,14 +738,14 @@ fn write_game_results_sheet(
         }
 
         // Score (from NS perspective)
-        if let Some(score) = scores[row_idx] {
+        if let Some(score) = calculate_score_for_result(result) {
             sheet.write_number_with_format(row, 10, score as f64, &score_format)?;
         }
 
         // Matchpoints
-        if let Some(mp) = matchpoints[row_idx] {
-            sheet.write_number_with_format(row, 11, mp, &mp_format)?;
-            sheet.write_number_with_format(row, 12, 100.0 - mp, &mp_format)?;
+        if let Some(mp) = matchpoints.get(row_idx).copied().flatten() {
+            sheet.write_number_with_format(row, 11, mp.ns, &mp_format)?;
+            sheet.write_number_with_format(row, 12, mp.ew, &mp_format)?;
         }
     }
 
@@ -382,16 +753,34 @@ fn write_game_results_sheet(
 }
 
 /// Write players to a worksheet (from PlayerNumbers - actual game participants)
-/// Includes matchpoint totals and percentages per pair, plus ACBL masterpoints if available
+/// Includes matchpoint totals and percentages per pair, each pair's rank within
+/// its section and direction (by IMP total in IMP-scored sections, otherwise
+/// by average MP%), boards sat out against a phantom pair, IMP totals
+/// for IMP-scored sections (scored with `options.imp_method`), plus ACBL
+/// masterpoints if available. Players are listed best first within each
+/// section/direction group, with the group winners highlighted.
 fn write_players_sheet(
     sheet: &mut Worksheet,
     data: &crate::bws::BwsData,
     pair_totals: &HashMap<(i32, i32, bool), PairMatchpoints>,
     member_data: Option<&HashMap<String, crate::acbl::MemberInfo>>,
+    options: &XlsxOptions,
 ) -> Result<()> {
     sheet.set_name("Players")?;
 
     let has_masterpoints = member_data.is_some();
+    let has_sit_outs = pair_totals.values().any(|t| t.sit_out_boards > 0);
+    let has_imps = has_imp_sections(data);
+    let imp_totals = if has_imps {
+        calculate_imp_totals(data, options.imp_method)
+    } else {
+        PairImpTotals::new()
+    };
+
+    // Optional columns follow the fixed ones
+    let sit_out_col: u16 = 9;
+    let imp_col: u16 = sit_out_col + u16::from(has_sit_outs);
+    let acbl_col: u16 = imp_col + u16::from(has_imps);
 
     // Set column widths
     sheet.set_column_width(0, 10)?; // Section
@@ -402,10 +791,19 @@ fn write_players_sheet(
     sheet.set_column_width(5, 8)?; // Boards
     sheet.set_column_width(6, 10)?; // Total MP%
     sheet.set_column_width(7, 10)?; // Avg MP%
+    sheet.set_column_width(8, 6)?; // Rank
+
+    if has_sit_outs {
+        sheet.set_column_width(sit_out_col, 8)?; // Sat Out
+    }
+
+    if has_imps {
+        sheet.set_column_width(imp_col, 12)?; // Butler IMPs or Cross-IMPs
+    }
 
     if has_masterpoints {
-        sheet.set_column_width(8, 18)?; // ACBL Rank
-        sheet.set_column_width(9, 12)?; // ACBL Points
+        sheet.set_column_width(acbl_col, 18)?; // ACBL Rank
+        sheet.set_column_width(acbl_col + 1, 12)?; // ACBL Points
     }
 
     // Header format
@@ -422,6 +820,7 @@ fn write_players_sheet(
     let points_format = Format::new()
         .set_align(FormatAlign::Right)
         .set_num_format("#,##0.00");
+    let imp_format = imp_number_format(options.imp_method);
 
     // Write headers
     sheet.write_string_with_format(0, 0, "Section", &header_format)?;
@@ -432,17 +831,53 @@ fn write_players_sheet(
     sheet.write_string_with_format(0, 5, "Boards", &header_format)?;
     sheet.write_string_with_format(0, 6, "Total MP%", &header_format)?;
     sheet.write_string_with_format(0, 7, "Avg MP%", &header_format)?;
+    sheet.write_string_with_format(0, 8, "Rank", &header_format)?;
+
+    if has_sit_outs {
+        sheet.write_string_with_format(0, sit_out_col, "Sat Out", &header_format)?;
+    }
+
+    if has_imps {
+        sheet.write_string_with_format(0, imp_col, options.imp_method.label(), &header_format)?;
+    }
 
     if has_masterpoints {
-        sheet.write_string_with_format(0, 8, "ACBL Rank", &header_format)?;
-        sheet.write_string_with_format(0, 9, "ACBL Points", &header_format)?;
+        sheet.write_string_with_format(0, acbl_col, "ACBL Rank", &header_format)?;
+        sheet.write_string_with_format(0, acbl_col + 1, "ACBL Points", &header_format)?;
     }
 
-    // Sort players by section, table, direction order (N, E, S, W)
+    let winner_format = Format::new()
+        .set_bold()
+        .set_align(FormatAlign::Left)
+        .set_background_color(Color::RGB(0xC6EFCE));
+    let average_format = Format::new()
+        .set_bold()
+        .set_align(FormatAlign::Right)
+        .set_num_format("0.00");
+
+    // IMP-scored sections are ranked by IMP total, the rest by average MP%
+    let mp_ranks = rank_pairs(pair_totals);
+    let imp_ranks = rank_imp_pairs(&imp_totals);
+    let imp_sections = imp_section_ids(data);
+    let rank_of = |key: &(i32, i32, bool)| {
+        let ranks = if imp_sections.contains(&key.0) {
+            &imp_ranks
+        } else {
+            &mp_ranks
+        };
+        ranks.get(key).copied()
+    };
+
+    // Sort players by section, NS before EW, best rank first (unranked
+    // last), then table and direction order (N, E, S, W)
     let mut players: Vec<_> = data.player_numbers.iter().collect();
     players.sort_by(|a, b| {
+        let (key_a, key_b) = (player_pair_key(data, a), player_pair_key(data, b));
+        let (rank_a, rank_b) = (rank_of(&key_a), rank_of(&key_b));
         a.section
             .cmp(&b.section)
+            .then(key_b.2.cmp(&key_a.2))
+            .then(rank_a.unwrap_or(u32::MAX).cmp(&rank_b.unwrap_or(u32::MAX)))
             .then(a.table.cmp(&b.table))
             .then(direction_order(&a.direction).cmp(&direction_order(&b.direction)))
     });
@@ -450,46 +885,117 @@ fn write_players_sheet(
     // Write player data
     for (row_idx, player) in players.iter().enumerate() {
         let row = (row_idx + 1) as u32;
+        let pair_key = player_pair_key(data, player);
+        let rank = rank_of(&pair_key);
 
         sheet.write_number_with_format(row, 0, player.section as f64, &center_format)?;
         sheet.write_number_with_format(row, 1, player.table as f64, &center_format)?;
         sheet.write_string_with_format(row, 2, &player.direction, &center_format)?;
         sheet.write_string_with_format(row, 3, &player.number, &left_format)?;
         if let Some(ref name) = player.name {
-            sheet.write_string_with_format(row, 4, name, &left_format)?;
+            let name_format = if rank == Some(1) {
+                &winner_format
+            } else {
+                &left_format
+            };
+            sheet.write_string_with_format(row, 4, name, name_format)?;
         }
 
-        // Look up pair matchpoints
-        // Pair is identified by (section, table, is_ns)
-        // For the initial seating, table number = pair number
-        let is_ns = player.direction == "N" || player.direction == "S";
-        let pair_key = (player.section, player.table, is_ns);
-
+        // Look up pair matchpoints; IMP-section pairs have none, so their
+        // MP% columns stay empty and the board count comes from the IMPs
         if let Some(mp_data) = pair_totals.get(&pair_key) {
             sheet.write_number_with_format(row, 5, mp_data.boards_played as f64, &center_format)?;
             sheet.write_number_with_format(row, 6, mp_data.total_mp_pct, &mp_format)?;
 
             // Average matchpoint percentage
-            if mp_data.boards_played > 0 {
-                let avg = mp_data.total_mp_pct / mp_data.boards_played as f64;
+            if let Some(avg) = average_mp_pct(mp_data) {
                 sheet.write_number_with_format(row, 7, avg, &mp_format)?;
             }
+
+            if mp_data.sit_out_boards > 0 {
+                sheet.write_number_with_format(
+                    row,
+                    sit_out_col,
+                    f64::from(mp_data.sit_out_boards),
+                    &center_format,
+                )?;
+            }
+        }
+
+        if let Some(rank) = rank {
+            sheet.write_number_with_format(row, 8, f64::from(rank), &center_format)?;
+        }
+
+        if let Some(imps) = imp_totals.get(&pair_key) {
+            if !pair_totals.contains_key(&pair_key) {
+                sheet.write_number_with_format(
+                    row,
+                    5,
+                    f64::from(imps.boards_played),
+                    &center_format,
+                )?;
+            }
+            sheet.write_number_with_format(row, imp_col, imps.total_imps, &imp_format)?;
         }
 
         // Look up ACBL masterpoint data if available
         if let Some(members) = member_data {
-            if let Some(member_info) =
-                crate::acbl::lookup_member(members, &player.number, player.name.as_deref())
-            {
-                sheet.write_string_with_format(row, 8, &member_info.rank, &left_format)?;
-                sheet.write_number_with_format(row, 9, member_info.points, &points_format)?;
+            let acbl_number = data
+                .acbl_number_for(player.section, player.table, &player.direction)
+                .unwrap_or(&player.number);
+            let name = player.name.as_deref();
+            // The fuzzy lookup tries the exact match first
+            #[cfg(feature = "fuzzy")]
+            let member_info = match options.fuzzy_threshold {
+                Some(threshold) => {
+                    crate::acbl::lookup_member_fuzzy(members, acbl_number, name, threshold)
+                }
+                None => crate::acbl::lookup_member(members, acbl_number, name),
+            };
+            #[cfg(not(feature = "fuzzy"))]
+            let member_info = crate::acbl::lookup_member(members, acbl_number, name);
+            if let Some(member_info) = member_info {
+                sheet.write_string_with_format(row, acbl_col, &member_info.rank, &left_format)?;
+                sheet.write_number_with_format(
+                    row,
+                    acbl_col + 1,
+                    member_info.points,
+                    &points_format,
+                )?;
             }
         }
     }
 
+    // Field average reference, after a blank row. Only matchpointed fields
+    // average 50%; IMP totals have no fixed average to show.
+    if !players.is_empty() && has_mp_sections(data) {
+        let row = players.len() as u32 + 2;
+        sheet.write_string_with_format(row, 4, "Average", &average_format)?;
+        sheet.write_number_with_format(row, 7, 50.0, &average_format)?;
+    }
+
     Ok(())
 }
 
+/// Pair key (section, pair_number, is_ns) for a seated player
+///
+/// PlayerNumbers records the first-round seating, so the pair number is the
+/// one at that table in the first round, which in a Mitchell needn't match
+/// the table number for EW.
+fn player_pair_key(
+    data: &crate::bws::BwsData,
+    player: &crate::bws::PlayerNumberRow,
+) -> (i32, i32, bool) {
+    let is_ns = player.direction == "N" || player.direction == "S";
+    let pair = data.pair_at_start(player.section, player.table, is_ns);
+    (player.section, pair, is_ns)
+}
+
+/// Dense rank of each pair by average MP% within its section and direction
+fn rank_pairs(pair_totals: &PairMatchpointTotals) -> HashMap<(i32, i32, bool), u32> {
+    dense_rank_pairs(pair_totals, |key| (key.0, key.2), average_mp_pct)
+}
+
 /// Get sort order for direction (N=0, E=1, S=2, W=3)
 fn direction_order(dir: &str) -> i32 {
     match dir {
@@ -507,19 +1013,72 @@ pub fn write_combined_to_xlsx(
     bws_data: &crate::bws::BwsData,
     path: &Path,
     member_data: Option<&HashMap<String, crate::acbl::MemberInfo>>,
+    options: &XlsxOptions,
 ) -> Result<()> {
+    build_combined_workbook(boards, bws_data, member_data, options)?.save(path)?;
+    Ok(())
+}
+
+/// Write combined PBN (deals) and BWS (scores) data to an in-memory Excel file
+pub fn write_combined_to_xlsx_buf(
+    boards: &[Board],
+    bws_data: &crate::bws::BwsData,
+    member_data: Option<&HashMap<String, crate::acbl::MemberInfo>>,
+    options: &XlsxOptions,
+) -> Result<Vec<u8>> {
+    Ok(build_combined_workbook(boards, bws_data, member_data, options)?.save_to_buffer()?)
+}
+
+/// Build the combined deals and results workbook
+fn build_combined_workbook(
+    boards: &[Board],
+    bws_data: &crate::bws::BwsData,
+    member_data: Option<&HashMap<String, crate::acbl::MemberInfo>>,
+    options: &XlsxOptions,
+) -> Result<Workbook> {
     let mut workbook = Workbook::new();
 
     // Calculate matchpoints once for use in multiple sheets
-    let (matchpoints, pair_totals) = calculate_all_matchpoints(bws_data);
+    let (matchpoints, pair_totals) =
+        calculate_all_matchpoints_with_sit_out(bws_data, options.sit_out_mp_pct);
 
     // Add Game Results sheet (with deal info)
     let results_sheet = workbook.add_worksheet();
-    write_game_results_with_deals_sheet(results_sheet, bws_data, boards, &matchpoints)?;
+    write_game_results_with_deals_sheet(results_sheet, bws_data, boards, &matchpoints, options)?;
 
     // Add Players sheet with matchpoint totals
     let players_sheet = workbook.add_worksheet();
-    write_players_sheet(players_sheet, bws_data, &pair_totals, member_data)?;
+    write_players_sheet(players_sheet, bws_data, &pair_totals, member_data, options)?;
+
+    // Add IMP Rankings sheet when any section is IMP-scored
+    if has_imp_sections(bws_data) {
+        let imp_sheet = workbook.add_worksheet();
+        write_imp_rankings_sheet(imp_sheet, bws_data, options.imp_method)?;
+    }
+
+    // Add Travellers sheet (results grouped per board)
+    if !bws_data.received_data.is_empty() {
+        let travellers_sheet = workbook.add_worksheet();
+        write_travellers_sheet(travellers_sheet, bws_data, &matchpoints)?;
+    }
+
+    // Add per-pair Scorecards sheet if requested
+    if options.scorecards {
+        let scorecards_sheet = workbook.add_worksheet();
+        write_scorecards_sheet(scorecards_sheet, bws_data, &matchpoints, &pair_totals)?;
+    }
+
+    // Add Frequencies sheet if requested
+    if options.frequencies && !bws_data.received_data.is_empty() {
+        let frequency_sheet = workbook.add_worksheet();
+        write_frequency_sheet(frequency_sheet, bws_data, &matchpoints)?;
+    }
+
+    // Add Overall Rankings sheet when several sections share a field
+    if bws_data.sections.len() > 1 {
+        let overall_sheet = workbook.add_worksheet();
+        write_overall_rankings_sheet(overall_sheet, bws_data, &pair_totals)?;
+    }
 
     // Add Sections sheet if there are sections
     if !bws_data.sections.is_empty() {
@@ -533,35 +1092,200 @@ pub fn write_combined_to_xlsx(
         write_hand_records_sheet(hands_sheet, boards)?;
     }
 
-    workbook.save(path)?;
-    Ok(())
+    Ok(workbook)
 }
 
-/// Write game results with deal information to a worksheet
-fn write_game_results_with_deals_sheet(
-    sheet: &mut Worksheet,
+/// One board on a pair's personal scorecard, from that pair's perspective
+#[derive(Debug, Clone, PartialEq)]
+struct ScorecardEntry {
+    board: i32,
+    opponents: i32,
+    contract: String,
+    declarer: String,
+    result: String,
+    score: Option<i32>,
+    mp_pct: Option<f64>,
+}
+
+/// Per-pair scorecards keyed by (section, pair_number, is_ns), boards ascending
+type Scorecards = BTreeMap<(i32, i32, bool), Vec<ScorecardEntry>>;
+
+/// Build every pair's scorecard, using the same pair keys as the matchpoint totals
+///
+/// Scores and matchpoints are flipped for the EW pair so each card reads from
+/// the owner's side of the table.
+fn build_scorecards(
     data: &crate::bws::BwsData,
-    boards: &[Board],
-    matchpoints: &[Option<f64>],
-) -> Result<()> {
-    sheet.set_name("Game Results")?;
+    matchpoints: &[Option<MatchpointPct>],
+) -> Scorecards {
+    let mut cards = Scorecards::new();
+
+    for (idx, result) in data.received_data.iter().enumerate() {
+        let score = calculate_score_for_result(result);
+        let mp = matchpoints.get(idx).copied().flatten();
+
+        for is_ns in [true, false] {
+            let (pair, opponents) = if is_ns {
+                (result.pair_ns, result.pair_ew)
+            } else {
+                (result.pair_ew, result.pair_ns)
+            };
+            cards
+                .entry((result.section, pair, is_ns))
+                .or_default()
+                .push(ScorecardEntry {
+                    board: result.board,
+                    opponents,
+                    contract: result.contract.clone(),
+                    declarer: result.ns_ew.clone(),
+                    result: result.result.clone(),
+                    score: score.map(|s| if is_ns { s } else { -s }),
+                    mp_pct: mp.map(|m| m.side(is_ns)),
+                });
+        }
+    }
 
-    // Build a map of board number to board for quick lookup
-    let board_map: HashMap<u32, &Board> = boards
-        .iter()
-        .filter_map(|b| b.number.map(|n| (n, b)))
-        .collect();
+    for entries in cards.values_mut() {
+        entries.sort_by_key(|e| e.board);
+    }
 
-    // Calculate scores for all results
-    let scores: Vec<Option<i32>> = data
-        .received_data
+    cards
+}
+
+/// Average MP% over the scored boards so far, after each scorecard entry
+///
+/// `None` until the first board with a matchpoint percentage.
+fn running_mp_pcts(entries: &[ScorecardEntry]) -> Vec<Option<f64>> {
+    let mut total = 0.0;
+    let mut scored = 0u32;
+    entries
         .iter()
-        .map(calculate_score_for_result)
-        .collect();
+        .map(|entry| {
+            if let Some(mp) = entry.mp_pct {
+                total += mp;
+                scored += 1;
+            }
+            (scored > 0).then(|| total / f64::from(scored))
+        })
+        .collect()
+}
 
-    // Create sorted indices: by Board ascending, then Score descending
-    let mut sorted_indices: Vec<usize> = (0..data.received_data.len()).collect();
-    sorted_indices.sort_by(|&a, &b| {
+/// Write per-pair scorecards to a worksheet
+///
+/// Each pair gets a titled block listing the boards it played, with a running
+/// average MP% and the final percentage from the pair totals.
+fn write_scorecards_sheet(
+    sheet: &mut Worksheet,
+    data: &crate::bws::BwsData,
+    matchpoints: &[Option<MatchpointPct>],
+    pair_totals: &PairMatchpointTotals,
+) -> Result<()> {
+    sheet.set_name("Scorecards")?;
+
+    let headers = [
+        "Board",
+        "Opponents",
+        "Contract",
+        "Declarer",
+        "Result",
+        "Score",
+        "MP%",
+        "Running MP%",
+    ];
+    let col_widths = [8, 10, 10, 10, 8, 8, 8, 12];
+    for (col, width) in col_widths.iter().enumerate() {
+        sheet.set_column_width(col as u16, *width)?;
+    }
+
+    let title_format = Format::new().set_bold();
+    let header_format = Format::new()
+        .set_bold()
+        .set_align(FormatAlign::Center)
+        .set_border_bottom(FormatBorder::Thin);
+    let center_format = Format::new().set_align(FormatAlign::Center);
+    let score_format = Format::new().set_align(FormatAlign::Right);
+    let mp_format = Format::new()
+        .set_align(FormatAlign::Right)
+        .set_num_format("0.0");
+    let total_format = Format::new()
+        .set_bold()
+        .set_align(FormatAlign::Right)
+        .set_num_format("0.00")
+        .set_border_top(FormatBorder::Thin);
+
+    let mut row: u32 = 0;
+    for (&(section, pair, is_ns), entries) in &build_scorecards(data, matchpoints) {
+        // Title: section letter, pair, direction and player names
+        let section_letter = data
+            .sections
+            .iter()
+            .find(|s| s.id == section)
+            .map(|s| s.letter.trim().to_string())
+            .unwrap_or_else(|| section.to_string());
+        let (dir_a, dir_b, side) = if is_ns {
+            ("N", "S", "NS")
+        } else {
+            ("E", "W", "EW")
+        };
+        let names: Vec<&str> = [dir_a, dir_b]
+            .iter()
+            .filter_map(|d| data.get_pair_player(section, pair, d))
+            .collect();
+        let title = format!(
+            "Section {} Pair {} {}: {}",
+            section_letter,
+            pair,
+            side,
+            names.join(" & ")
+        );
+        sheet.write_string_with_format(row, 0, &title, &title_format)?;
+        row += 1;
+
+        for (col, header) in headers.iter().enumerate() {
+            sheet.write_string_with_format(row, col as u16, *header, &header_format)?;
+        }
+        row += 1;
+
+        for (entry, running) in entries.iter().zip(running_mp_pcts(entries)) {
+            sheet.write_number_with_format(row, 0, entry.board as f64, &center_format)?;
+            sheet.write_number_with_format(row, 1, entry.opponents as f64, &center_format)?;
+            sheet.write_string_with_format(row, 2, &entry.contract, &center_format)?;
+            sheet.write_string_with_format(row, 3, &entry.declarer, &center_format)?;
+            sheet.write_string_with_format(row, 4, &entry.result, &center_format)?;
+            if let Some(score) = entry.score {
+                sheet.write_number_with_format(row, 5, score as f64, &score_format)?;
+            }
+            if let Some(mp) = entry.mp_pct {
+                sheet.write_number_with_format(row, 6, mp, &mp_format)?;
+            }
+            if let Some(running) = running {
+                sheet.write_number_with_format(row, 7, running, &mp_format)?;
+            }
+            row += 1;
+        }
+
+        // Final percentage
+        if let Some(totals) = pair_totals.get(&(section, pair, is_ns)) {
+            if let Some(pct) = average_mp_pct(totals) {
+                sheet.write_string_with_format(row, 6, "Final %", &title_format)?;
+                sheet.write_number_with_format(row, 7, pct, &total_format)?;
+            }
+        }
+
+        // Blank row between pairs
+        row += 2;
+    }
+
+    Ok(())
+}
+
+/// Indices of the results sorted by board ascending, then NS score descending
+pub(crate) fn sort_results_by_board_and_score(
+    data: &crate::bws::BwsData,
+    scores: &[Option<i32>],
+) -> Vec<usize> {
+    let mut sorted_indices: Vec<usize> = (0..data.received_data.len()).collect();
+    sorted_indices.sort_by(|&a, &b| {
         let board_cmp = data.received_data[a]
             .board
             .cmp(&data.received_data[b].board);
@@ -573,6 +1297,34 @@ fn write_game_results_with_deals_sheet(
         let score_b = scores[b].unwrap_or(i32::MIN);
         score_b.cmp(&score_a)
     });
+    sorted_indices
+}
+
+/// Write game results with deal information to a worksheet
+fn write_game_results_with_deals_sheet(
+    sheet: &mut Worksheet,
+    data: &crate::bws::BwsData,
+    boards: &[Board],
+    matchpoints: &[Option<MatchpointPct>],
+    options: &XlsxOptions,
+) -> Result<()> {
+    sheet.set_name("Game Results")?;
+
+    // Build a map of board number to board for quick lookup
+    let board_map: HashMap<u32, &Board> = boards
+        .iter()
+        .filter_map(|b| b.number.map(|n| (n, b)))
+        .collect();
+
+    // Calculate scores for all results
+    let scores: Vec<Option<i32>> = data
+        .received_data
+        .iter()
+        .map(calculate_score_for_result)
+        .collect();
+
+    // Create sorted indices: by Board ascending, then Score descending
+    let sorted_indices = sort_results_by_board_and_score(data, &scores);
 
     // Set column widths - expanded to include player names
     let col_widths = [
@@ -610,13 +1362,7 @@ fn write_game_results_with_deals_sheet(
         .set_border_bottom(FormatBorder::Thin);
 
     // Write headers
-    let headers = [
-        "Board", "Section", "Table", "Round", "NS Pair", "EW Pair", "N Name", "E Name", "S Name",
-        "W Name", "Declarer", "Contract", "Result", "Lead", "Score", "NS MP%", "EW MP%", "Vul",
-        "North", "East", "South", "West",
-    ];
-
-    for (col, header) in headers.iter().enumerate() {
+    for (col, header) in COMBINED_RESULT_HEADERS.iter().enumerate() {
         sheet.write_string_with_format(0, col as u16, *header, &header_format)?;
     }
 
@@ -640,18 +1386,18 @@ fn write_game_results_with_deals_sheet(
         sheet.write_number_with_format(row, 4, result.pair_ns as f64, &center_format)?;
         sheet.write_number_with_format(row, 5, result.pair_ew as f64, &center_format)?;
 
-        // Player names - look up by pair number (starting table) and direction
-        // NS pair started at table = pair_ns, EW pair started at table = pair_ew
-        if let Some(n_name) = data.get_player_at(result.section, result.pair_ns, "N") {
+        // Player names - look up by pair number and direction, following each
+        // pair back to its starting table
+        if let Some(n_name) = data.get_pair_player(result.section, result.pair_ns, "N") {
             sheet.write_string_with_format(row, 6, n_name, &left_format)?;
         }
-        if let Some(e_name) = data.get_player_at(result.section, result.pair_ew, "E") {
+        if let Some(e_name) = data.get_pair_player(result.section, result.pair_ew, "E") {
             sheet.write_string_with_format(row, 7, e_name, &left_format)?;
         }
-        if let Some(s_name) = data.get_player_at(result.section, result.pair_ns, "S") {
+        if let Some(s_name) = data.get_pair_player(result.section, result.pair_ns, "S") {
             sheet.write_string_with_format(row, 8, s_name, &left_format)?;
         }
-        if let Some(w_name) = data.get_player_at(result.section, result.pair_ew, "W") {
+        if let Some(w_name) = data.get_pair_player(result.section, result.pair_ew, "W") {
             sheet.write_string_with_format(row, 9, w_name, &left_format)?;
         }
 
@@ -679,8 +1425,8 @@ fn write_game_results_with_deals_sheet(
 
         // Matchpoints
         if let Some(mp) = matchpoints[original_idx] {
-            sheet.write_number_with_format(row, 15, mp, &mp_format)?;
-            sheet.write_number_with_format(row, 16, 100.0 - mp, &mp_format)?;
+            sheet.write_number_with_format(row, 15, mp.ns, &mp_format)?;
+            sheet.write_number_with_format(row, 16, mp.ew, &mp_format)?;
         }
 
         // Add deal information if available
@@ -704,24 +1450,406 @@ fn write_game_results_with_deals_sheet(
         }
     }
 
-    // Add auto-filter to the table
     let last_row = data.received_data.len() as u32;
-    let last_col = (headers.len() - 1) as u16;
-    sheet.autofilter(0, 0, last_row, last_col)?;
+    apply_results_formatting(sheet, options, last_row)?;
+
+    Ok(())
+}
+
+/// Apply the optional header freeze, autofilter and MP% color scales to the
+/// combined Game Results sheet
+///
+/// Returns the number of conditional format rules added.
+fn apply_results_formatting(
+    sheet: &mut Worksheet,
+    options: &XlsxOptions,
+    last_row: u32,
+) -> Result<usize> {
+    if options.freeze_header {
+        sheet.set_freeze_panes(1, 0)?;
+    }
+
+    // Add auto-filter to the table
+    if options.autofilter {
+        let last_col = (COMBINED_RESULT_HEADERS.len() - 1) as u16;
+        sheet.autofilter(0, 0, last_row, last_col)?;
+    }
 
     // Add conditional formatting (3-color scale) to NS MP% and EW MP% columns
-    // Red (low) -> Yellow (mid) -> Green (high)
-    if !data.received_data.is_empty() {
+    let mut rules = 0;
+    if options.conditional_formatting && last_row > 0 {
+        let (min, mid, max) = options.color_scheme.colors();
         let mp_conditional_format = ConditionalFormat3ColorScale::new()
-            .set_minimum_color("F8696B") // Red
-            .set_midpoint_color("FFEB84") // Yellow
-            .set_maximum_color("63BE7B"); // Green
+            .set_minimum_color(min)
+            .set_midpoint_color(mid)
+            .set_maximum_color(max);
 
         // NS MP% column (column 15, 0-indexed)
         sheet.add_conditional_format(1, 15, last_row, 15, &mp_conditional_format)?;
 
         // EW MP% column (column 16, 0-indexed)
         sheet.add_conditional_format(1, 16, last_row, 16, &mp_conditional_format)?;
+        rules += 2;
+    }
+
+    Ok(rules)
+}
+
+/// Group result indices into travellers: one entry per board number (ascending),
+/// each listing that board's results by NS score descending
+fn group_travellers(data: &crate::bws::BwsData, scores: &[Option<i32>]) -> Vec<(i32, Vec<usize>)> {
+    let mut travellers: Vec<(i32, Vec<usize>)> = Vec::new();
+    for idx in sort_results_by_board_and_score(data, scores) {
+        let board = data.received_data[idx].board;
+        match travellers.last_mut() {
+            Some((last_board, indices)) if *last_board == board => indices.push(idx),
+            _ => travellers.push((board, vec![idx])),
+        }
+    }
+    travellers
+}
+
+/// Write travellers to a worksheet: every table's result for each board,
+/// ranked by NS score, with a border separating consecutive boards
+fn write_travellers_sheet(
+    sheet: &mut Worksheet,
+    data: &crate::bws::BwsData,
+    matchpoints: &[Option<MatchpointPct>],
+) -> Result<()> {
+    sheet.set_name("Travellers")?;
+
+    let scores: Vec<Option<i32>> = data
+        .received_data
+        .iter()
+        .map(calculate_score_for_result)
+        .collect();
+
+    let headers = [
+        "Board", "NS Pair", "EW Pair", "Contract", "Declarer", "Result", "Score", "NS MP%",
+    ];
+    let col_widths = [8, 8, 8, 10, 10, 8, 8, 8];
+    for (col, width) in col_widths.iter().enumerate() {
+        sheet.set_column_width(col as u16, *width)?;
+    }
+
+    // Header format
+    let header_format = Format::new()
+        .set_bold()
+        .set_align(FormatAlign::Center)
+        .set_border_bottom(FormatBorder::Thin);
+
+    for (col, header) in headers.iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, *header, &header_format)?;
+    }
+
+    // The first row of each board after the first gets a top border
+    let center_format = Format::new().set_align(FormatAlign::Center);
+    let score_format = Format::new().set_align(FormatAlign::Right);
+    let mp_format = Format::new()
+        .set_align(FormatAlign::Right)
+        .set_num_format("0.0");
+    let separated = |format: &Format| format.clone().set_border_top(FormatBorder::Medium);
+
+    let mut row: u32 = 1;
+    for (traveller_idx, (board, indices)) in group_travellers(data, &scores).iter().enumerate() {
+        for (i, &idx) in indices.iter().enumerate() {
+            let result = &data.received_data[idx];
+            let first_of_board = i == 0 && traveller_idx > 0;
+            let (center, score_fmt, mp_fmt) = if first_of_board {
+                (
+                    separated(&center_format),
+                    separated(&score_format),
+                    separated(&mp_format),
+                )
+            } else {
+                (
+                    center_format.clone(),
+                    score_format.clone(),
+                    mp_format.clone(),
+                )
+            };
+
+            sheet.write_number_with_format(row, 0, *board as f64, &center)?;
+            sheet.write_number_with_format(row, 1, result.pair_ns as f64, &center)?;
+            sheet.write_number_with_format(row, 2, result.pair_ew as f64, &center)?;
+            sheet.write_string_with_format(row, 3, &result.contract, &center)?;
+            sheet.write_string_with_format(row, 4, &result.ns_ew, &center)?;
+            sheet.write_string_with_format(row, 5, &result.result, &center)?;
+
+            // Unscored results still get the separator so the board boundary is unbroken
+            match scores[idx] {
+                Some(score) => sheet.write_number_with_format(row, 6, score as f64, &score_fmt)?,
+                None => sheet.write_blank(row, 6, &score_fmt)?,
+            };
+            match matchpoints.get(idx).copied().flatten() {
+                Some(mp) => sheet.write_number_with_format(row, 7, mp.ns, &mp_fmt)?,
+                None => sheet.write_blank(row, 7, &mp_fmt)?,
+            };
+
+            row += 1;
+        }
+    }
+
+    Ok(())
+}
+
+/// One line of a board's frequency listing
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ScoreFrequency {
+    /// NS score
+    pub(crate) score: i32,
+    /// Number of tables that recorded this score
+    pub(crate) count: usize,
+    /// NS MP% earned by this score
+    pub(crate) ns_mp_pct: Option<f64>,
+}
+
+/// Build each board's frequency listing: the distinct NS scores (descending),
+/// how often each occurred and the MP% it earned
+///
+/// Boards are ascending. Unscored results and director-assigned averages are
+/// left out, since they don't take part in the matchpoint comparison.
+pub(crate) fn build_frequencies(
+    data: &crate::bws::BwsData,
+    matchpoints: &[Option<MatchpointPct>],
+) -> Vec<(i32, Vec<ScoreFrequency>)> {
+    let scores: Vec<Option<i32>> = data
+        .received_data
+        .iter()
+        .map(|result| match crate::scoring::adjusted_score_pct(result) {
+            Some(_) => None,
+            None => calculate_score_for_result(result),
+        })
+        .collect();
+
+    let mut frequencies = Vec::new();
+    for (board, indices) in group_travellers(data, &scores) {
+        let mut lines: Vec<ScoreFrequency> = Vec::new();
+        for idx in indices {
+            let Some(score) = scores[idx] else {
+                continue;
+            };
+            match lines.last_mut() {
+                Some(line) if line.score == score => line.count += 1,
+                _ => lines.push(ScoreFrequency {
+                    score,
+                    count: 1,
+                    ns_mp_pct: matchpoints.get(idx).copied().flatten().map(|mp| mp.ns),
+                }),
+            }
+        }
+        if !lines.is_empty() {
+            frequencies.push((board, lines));
+        }
+    }
+    frequencies
+}
+
+/// Write the frequency listing to a worksheet: each distinct NS score per
+/// board with its count and the MP% it earned for each side
+fn write_frequency_sheet(
+    sheet: &mut Worksheet,
+    data: &crate::bws::BwsData,
+    matchpoints: &[Option<MatchpointPct>],
+) -> Result<()> {
+    sheet.set_name("Frequencies")?;
+
+    let headers = ["Board", "NS Score", "Count", "NS MP%", "EW MP%"];
+    let col_widths = [8, 10, 8, 8, 8];
+    for (col, width) in col_widths.iter().enumerate() {
+        sheet.set_column_width(col as u16, *width)?;
+    }
+
+    // Header format
+    let header_format = Format::new()
+        .set_bold()
+        .set_align(FormatAlign::Center)
+        .set_border_bottom(FormatBorder::Thin);
+
+    for (col, header) in headers.iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, *header, &header_format)?;
+    }
+
+    // The first line of each board after the first gets a top border
+    let center_format = Format::new().set_align(FormatAlign::Center);
+    let score_format = Format::new().set_align(FormatAlign::Right);
+    let mp_format = Format::new()
+        .set_align(FormatAlign::Right)
+        .set_num_format("0.0");
+    let separated = |format: &Format| format.clone().set_border_top(FormatBorder::Medium);
+
+    let mut row: u32 = 1;
+    for (board_idx, (board, lines)) in build_frequencies(data, matchpoints).iter().enumerate() {
+        for (i, line) in lines.iter().enumerate() {
+            let first_of_board = i == 0 && board_idx > 0;
+            let (center, score_fmt, mp_fmt) = if first_of_board {
+                (
+                    separated(&center_format),
+                    separated(&score_format),
+                    separated(&mp_format),
+                )
+            } else {
+                (
+                    center_format.clone(),
+                    score_format.clone(),
+                    mp_format.clone(),
+                )
+            };
+
+            sheet.write_number_with_format(row, 0, *board as f64, &center)?;
+            sheet.write_number_with_format(row, 1, line.score as f64, &score_fmt)?;
+            sheet.write_number_with_format(row, 2, line.count as f64, &center)?;
+            match line.ns_mp_pct {
+                Some(mp) => {
+                    sheet.write_number_with_format(row, 3, mp, &mp_fmt)?;
+                    sheet.write_number_with_format(row, 4, 100.0 - mp, &mp_fmt)?;
+                }
+                None => {
+                    sheet.write_blank(row, 3, &mp_fmt)?;
+                    sheet.write_blank(row, 4, &mp_fmt)?;
+                }
+            }
+
+            row += 1;
+        }
+    }
+
+    Ok(())
+}
+
+/// Write the overall rankings across sections to a worksheet
+fn write_overall_rankings_sheet(
+    sheet: &mut Worksheet,
+    data: &crate::bws::BwsData,
+    pair_totals: &PairMatchpointTotals,
+) -> Result<()> {
+    sheet.set_name("Overall Rankings")?;
+
+    sheet.set_column_width(0, 6)?; // Rank
+    sheet.set_column_width(1, 8)?; // Field
+    sheet.set_column_width(2, 10)?; // Section
+    sheet.set_column_width(3, 6)?; // Pair
+    sheet.set_column_width(4, 40)?; // Names
+    sheet.set_column_width(5, 10)?; // Avg MP%
+
+    let header_format = Format::new()
+        .set_bold()
+        .set_align(FormatAlign::Center)
+        .set_border_bottom(FormatBorder::Thin);
+    let center_format = Format::new().set_align(FormatAlign::Center);
+    let left_format = Format::new().set_align(FormatAlign::Left);
+    let mp_format = Format::new()
+        .set_align(FormatAlign::Right)
+        .set_num_format("0.00");
+
+    let headers = ["Rank", "Field", "Section", "Pair", "Names", "Avg MP%"];
+    for (col, header) in headers.iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, *header, &header_format)?;
+    }
+
+    for (row_idx, entry) in rank_overall(data, pair_totals).iter().enumerate() {
+        let row = (row_idx + 1) as u32;
+        let (field, seats): (&str, &[&str]) = match entry.direction {
+            Some(true) => ("NS", &["N", "S"][..]),
+            Some(false) => ("EW", &["E", "W"][..]),
+            None => ("All", &["N", "S", "E", "W"][..]),
+        };
+        let section_letter = data
+            .sections
+            .iter()
+            .find(|s| s.id == entry.section)
+            .map(|s| s.letter.trim().to_string())
+            .unwrap_or_else(|| entry.section.to_string());
+        let names: Vec<&str> = seats
+            .iter()
+            .filter_map(|d| data.get_pair_player(entry.section, entry.pair, d))
+            .take(2)
+            .collect();
+
+        sheet.write_number_with_format(row, 0, f64::from(entry.rank), &center_format)?;
+        sheet.write_string_with_format(row, 1, field, &center_format)?;
+        sheet.write_string_with_format(row, 2, &section_letter, &center_format)?;
+        sheet.write_number_with_format(row, 3, f64::from(entry.pair), &center_format)?;
+        sheet.write_string_with_format(row, 4, names.join(" - "), &left_format)?;
+        sheet.write_number_with_format(row, 5, entry.avg_mp_pct, &mp_format)?;
+    }
+
+    Ok(())
+}
+
+/// Write the IMP pairs ranking to a worksheet: each pair in an IMP-scored
+/// section with its total IMPs, ranked within its section and direction
+fn write_imp_rankings_sheet(
+    sheet: &mut Worksheet,
+    data: &crate::bws::BwsData,
+    imp_method: ImpMethod,
+) -> Result<()> {
+    sheet.set_name("IMP Rankings")?;
+
+    sheet.set_column_width(0, 6)?; // Rank
+    sheet.set_column_width(1, 8)?; // Field
+    sheet.set_column_width(2, 10)?; // Section
+    sheet.set_column_width(3, 6)?; // Pair
+    sheet.set_column_width(4, 40)?; // Names
+    sheet.set_column_width(5, 8)?; // Boards
+    sheet.set_column_width(6, 12)?; // Butler IMPs or Cross-IMPs
+
+    let header_format = Format::new()
+        .set_bold()
+        .set_align(FormatAlign::Center)
+        .set_border_bottom(FormatBorder::Thin);
+    let center_format = Format::new().set_align(FormatAlign::Center);
+    let left_format = Format::new().set_align(FormatAlign::Left);
+    let imp_format = imp_number_format(imp_method);
+
+    let headers = [
+        "Rank",
+        "Field",
+        "Section",
+        "Pair",
+        "Names",
+        "Boards",
+        imp_method.label(),
+    ];
+    for (col, header) in headers.iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, *header, &header_format)?;
+    }
+
+    let imp_totals = calculate_imp_totals(data, imp_method);
+    let ranks = rank_imp_pairs(&imp_totals);
+
+    // Section, NS before EW, then best first
+    let mut pairs: Vec<_> = imp_totals.iter().collect();
+    pairs.sort_by_key(|(key, _)| (key.0, !key.2, ranks.get(*key).copied(), key.1));
+
+    for (row_idx, (key, totals)) in pairs.iter().enumerate() {
+        let row = (row_idx + 1) as u32;
+        let &(section, pair, is_ns) = *key;
+        let (field, seats) = if is_ns {
+            ("NS", ["N", "S"])
+        } else {
+            ("EW", ["E", "W"])
+        };
+        let section_letter = data
+            .sections
+            .iter()
+            .find(|s| s.id == section)
+            .map(|s| s.letter.trim().to_string())
+            .unwrap_or_else(|| section.to_string());
+        let names: Vec<&str> = seats
+            .iter()
+            .filter_map(|d| data.get_pair_player(section, pair, d))
+            .collect();
+
+        if let Some(&rank) = ranks.get(*key) {
+            sheet.write_number_with_format(row, 0, f64::from(rank), &center_format)?;
+        }
+        sheet.write_string_with_format(row, 1, field, &center_format)?;
+        sheet.write_string_with_format(row, 2, &section_letter, &center_format)?;
+        sheet.write_number_with_format(row, 3, f64::from(pair), &center_format)?;
+        sheet.write_string_with_format(row, 4, names.join(" - "), &left_format)?;
+        sheet.write_number_with_format(row, 5, f64::from(totals.boards_played), &center_format)?;
+        sheet.write_number_with_format(row, 6, totals.total_imps, &imp_format)?;
     }
 
     Ok(())
@@ -763,11 +1891,7 @@ fn write_sections_sheet(sheet: &mut Worksheet, data: &crate::bws::BwsData) -> Re
         }
 
         if let Some(scoring) = section.scoring_type {
-            let scoring_str = match scoring {
-                0 => "Matchpoints",
-                1 => "IMPs",
-                _ => "Unknown",
-            };
+            let scoring_str = crate::bws::reader::scoring_type_name(scoring);
             sheet.write_string_with_format(row, 3, scoring_str, &center_format)?;
         }
     }
@@ -786,4 +1910,470 @@ mod tests {
         assert!(formatted.contains("SAKQ"));
         assert!(formatted.contains("HJT9"));
     }
+
+    fn imp_test_data() -> crate::bws::BwsData {
+        use crate::bws::tables::{ReceivedDataRow, SectionRow};
+
+        let row = |table: i32, contract: &str, result: &str| ReceivedDataRow {
+            id: table,
+            section: 1,
+            table,
+            round: 1,
+            board: 1,
+            pair_ns: table,
+            pair_ew: table,
+            declarer: 0,
+            ns_ew: "N".to_string(),
+            contract: contract.to_string(),
+            result: result.to_string(),
+            lead_card: None,
+            remarks: None,
+        };
+
+        crate::bws::BwsData {
+            sections: vec![SectionRow {
+                id: 1,
+                letter: "A".to_string(),
+                tables: 4,
+                missing_pair: 0,
+                ew_move_before_play: None,
+                session: Some(1),
+                scoring_type: Some(1),
+                winners: Some(2),
+            }],
+            received_data: vec![
+                row(1, "4S", "="),
+                row(2, "4S", "="),
+                row(3, "4S", "+1"),
+                row(4, "2S", "+2"),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_butler_totals() {
+        let data = imp_test_data();
+        let totals = calculate_imp_totals(&data, ImpMethod::Butler);
+        let imps = |key| totals.get(&key).map(|t| t.total_imps);
+
+        // Scores 420, 420, 450, 170: datum is mean(420, 420) = 420
+        assert_eq!(imps((1, 1, true)), Some(0.0));
+        assert_eq!(imps((1, 3, true)), Some(1.0));
+        assert_eq!(imps((1, 4, true)), Some(-6.0));
+        assert_eq!(imps((1, 4, false)), Some(6.0));
+        assert_eq!(totals[&(1, 4, true)].boards_played, 1);
+
+        // Every IMP won by NS is lost by EW
+        let sum: f64 = totals.values().map(|t| t.total_imps).sum();
+        assert_eq!(sum, 0.0);
+    }
+
+    #[test]
+    fn test_cross_imp_totals() {
+        let data = imp_test_data();
+        let totals = calculate_imp_totals(&data, ImpMethod::CrossImps);
+
+        // 450 against 420, 420, 170: 1 + 1 + 7 IMPs over 3 comparisons
+        assert_eq!(totals[&(1, 3, true)].total_imps, 3.0);
+        assert_eq!(totals[&(1, 3, false)].total_imps, -3.0);
+
+        let sum: f64 = totals.values().map(|t| t.total_imps).sum();
+        assert!(sum.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_imp_ranking_ignores_matchpoints() {
+        let mut data = imp_test_data();
+        // Board 2 at the same tables: the 170 pair now beats the field by a lot
+        let board_two: Vec<_> = data
+            .received_data
+            .iter()
+            .map(|r| ReceivedDataRow {
+                board: 2,
+                contract: if r.table == 4 { "6S" } else { "4S" }.to_string(),
+                result: "=".to_string(),
+                ..r.clone()
+            })
+            .collect();
+        data.received_data.extend(board_two);
+
+        // On IMPs pair 4's slam swing outweighs the board-1 loss
+        let totals = calculate_imp_totals(&data, ImpMethod::Butler);
+        let ranks = rank_imp_pairs(&totals);
+        assert_eq!(totals[&(1, 4, true)].boards_played, 2);
+        assert_eq!(ranks[&(1, 4, true)], 1);
+        assert_eq!(ranks[&(1, 4, false)], 3);
+
+        // An IMP-only section isn't matchpointed at all
+        let (matchpoints, mp_totals) = calculate_all_matchpoints(&data);
+        assert!(matchpoints.iter().all(Option::is_none));
+        assert!(mp_totals.is_empty());
+    }
+
+    #[test]
+    fn test_imp_section_not_in_mp_pool() {
+        let mut data = imp_test_data();
+        // Section 2 is matchpointed and plays board 1 at two tables
+        let mut mp_section = data.sections[0].clone();
+        mp_section.id = 2;
+        mp_section.scoring_type = Some(0);
+        data.sections.push(mp_section);
+        for (table, contract) in [(1, "4S"), (2, "3S")] {
+            let mut row = data.received_data[0].clone();
+            row.section = 2;
+            row.table = table;
+            row.pair_ns = table;
+            row.pair_ew = table;
+            row.contract = contract.to_string();
+            row.result = "=".to_string();
+            data.received_data.push(row);
+        }
+
+        // 420 against 140 in section 2 only: section 1's scores don't count
+        let (matchpoints, totals) = calculate_all_matchpoints(&data);
+        assert!(matchpoints[..4].iter().all(Option::is_none));
+        let ns: Vec<Option<f64>> = matchpoints[4..].iter().map(|mp| mp.map(|m| m.ns)).collect();
+        assert_eq!(ns, vec![Some(100.0), Some(0.0)]);
+        assert!(totals.keys().all(|key| key.0 == 2));
+    }
+
+    #[test]
+    fn test_mp_sections() {
+        let mut data = imp_test_data();
+        assert!(!has_mp_sections(&data));
+
+        let mut mp_section = data.sections[0].clone();
+        mp_section.id = 2;
+        mp_section.scoring_type = Some(0);
+        data.sections.push(mp_section);
+        assert!(has_mp_sections(&data));
+
+        assert!(has_mp_sections(&crate::bws::BwsData::default()));
+    }
+
+    #[test]
+    fn test_group_travellers() {
+        let mut data = imp_test_data();
+        data.received_data[1].board = 2;
+        data.received_data[3].board = 2;
+        let scores: Vec<Option<i32>> = data
+            .received_data
+            .iter()
+            .map(calculate_score_for_result)
+            .collect();
+
+        // Board 1: 420 (table 1), 450 (table 3); board 2: 420 (table 2), 170 (table 4)
+        let travellers = group_travellers(&data, &scores);
+        assert_eq!(travellers, vec![(1, vec![2, 0]), (2, vec![1, 3])]);
+    }
+
+    #[test]
+    fn test_group_travellers_empty() {
+        let data = crate::bws::BwsData::default();
+        assert!(group_travellers(&data, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_frequencies_tied_top() {
+        let mut data = imp_test_data();
+        data.sections[0].scoring_type = Some(0);
+        // Scores 450, 420, 450, 170: two tables share the top
+        data.received_data[0].result = "+1".to_string();
+
+        let (matchpoints, _) = calculate_all_matchpoints(&data);
+        let frequencies = build_frequencies(&data, &matchpoints);
+        assert_eq!(frequencies.len(), 1);
+
+        let (board, lines) = &frequencies[0];
+        assert_eq!(*board, 1);
+        let listing: Vec<(i32, usize)> = lines.iter().map(|l| (l.score, l.count)).collect();
+        assert_eq!(listing, vec![(450, 2), (420, 1), (170, 1)]);
+
+        // 450 beats two results and ties one: 2.5 of 3
+        let top = lines[0].ns_mp_pct.unwrap();
+        assert!((top - 250.0 / 3.0).abs() < 1e-9);
+        assert_eq!(lines[2].ns_mp_pct, Some(0.0));
+    }
+
+    #[test]
+    fn test_frequencies_skip_unscored() {
+        let mut data = imp_test_data();
+        data.received_data[1].board = 2;
+        data.received_data[1].contract = String::new();
+        data.received_data[1].result = String::new();
+        data.received_data[1].remarks = Some("Ave+".to_string());
+
+        let (matchpoints, _) = calculate_all_matchpoints(&data);
+        let frequencies = build_frequencies(&data, &matchpoints);
+        assert_eq!(frequencies.len(), 1);
+        assert_eq!(frequencies[0].0, 1);
+        assert_eq!(frequencies[0].1.len(), 3);
+    }
+
+    #[test]
+    fn test_scorecards_two_boards() {
+        let mut data = imp_test_data();
+        data.sections[0].scoring_type = Some(0);
+        // NS pair 1 plays board 1 against EW 1, then board 2 against EW 2
+        data.received_data[1].board = 2;
+        data.received_data[1].pair_ns = 1;
+        data.received_data[3].board = 2;
+
+        let (matchpoints, pair_totals) = calculate_all_matchpoints(&data);
+        let cards = build_scorecards(&data, &matchpoints);
+
+        let ns1 = &cards[&(1, 1, true)];
+        assert_eq!(ns1.len(), 2);
+        assert_eq!(ns1[0].board, 1);
+        assert_eq!(ns1[0].opponents, 1);
+        assert_eq!(ns1[0].score, Some(420));
+        assert_eq!(ns1[1].board, 2);
+        assert_eq!(ns1[1].opponents, 2);
+
+        // Board 1: 420 vs 450 -> 0%; board 2 (NS vul): 620 vs 170 -> 100%
+        let total: f64 = ns1.iter().filter_map(|e| e.mp_pct).sum();
+        assert_eq!(ns1[0].mp_pct, Some(0.0));
+        assert_eq!(ns1[1].mp_pct, Some(100.0));
+        assert_eq!(total, pair_totals[&(1, 1, true)].total_mp_pct);
+
+        // Running MP% averages the boards so far: 0%, then (0 + 100) / 2
+        assert_eq!(running_mp_pcts(ns1), vec![Some(0.0), Some(50.0)]);
+
+        // EW side of the same result is flipped
+        let ew2 = &cards[&(1, 2, false)];
+        assert_eq!(ew2.len(), 1);
+        assert_eq!(ew2[0].score, Some(-620));
+        assert_eq!(ew2[0].mp_pct, Some(0.0));
+    }
+
+    #[test]
+    fn test_results_formatting_default() {
+        let mut sheet = Worksheet::new();
+        let rules = apply_results_formatting(&mut sheet, &XlsxOptions::default(), 10).unwrap();
+        assert_eq!(rules, 2);
+    }
+
+    #[test]
+    fn test_results_formatting_disabled() {
+        let mut sheet = Worksheet::new();
+        let options = XlsxOptions {
+            freeze_header: true,
+            ..XlsxOptions::plain()
+        };
+        let rules = apply_results_formatting(&mut sheet, &options, 10).unwrap();
+        assert_eq!(rules, 0);
+    }
+
+    #[test]
+    fn test_results_formatting_no_rows() {
+        let mut sheet = Worksheet::new();
+        let rules = apply_results_formatting(&mut sheet, &XlsxOptions::default(), 0).unwrap();
+        assert_eq!(rules, 0);
+    }
+
+    /// Check the buffer is a complete ZIP archive: local file header at the
+    /// start and an end-of-central-directory record at the end
+    fn assert_zip(buf: &[u8]) {
+        assert!(buf.starts_with(b"PK\x03\x04"));
+        let eocd = buf.windows(4).rposition(|w| w == b"PK\x05\x06");
+        assert!(matches!(eocd, Some(pos) if pos + 22 <= buf.len()));
+    }
+
+    #[test]
+    fn test_write_boards_to_xlsx_buf() {
+        let deal = crate::Deal::from_pbn(
+            "N:K843.T542.J6.863 AQJ7.K.Q75.AT942 962.AJ7.KT82.J75 T5.Q9863.A943.KQ",
+        )
+        .unwrap();
+        let boards = vec![Board::new().with_number(1).with_deal(deal)];
+
+        let buf = write_boards_to_xlsx_buf(&boards).unwrap();
+        assert_zip(&buf);
+    }
+
+    #[test]
+    fn test_write_combined_to_xlsx_buf() {
+        let data = imp_test_data();
+        let options = XlsxOptions {
+            scorecards: true,
+            frequencies: true,
+            imp_method: ImpMethod::CrossImps,
+            ..XlsxOptions::default()
+        };
+
+        let buf = write_combined_to_xlsx_buf(&[], &data, None, &options).unwrap();
+        assert_zip(&buf);
+
+        let buf = write_bws_to_xlsx_buf(&data, None, &XlsxOptions::default()).unwrap();
+        assert_zip(&buf);
+    }
+
+    /// Synthetic result `i`: 12 tables playing 26 boards per round, repeated
+    fn synthetic_result(i: usize) -> ReceivedDataRow {
+        let contracts = ["4S", "3NT", "2H", "4S", "5DX", "PASS"];
+        let results = ["=", "+1", "-1", "=", "-2", ""];
+        let table = (i % 12) as i32 + 1;
+        let k = i % contracts.len();
+        ReceivedDataRow {
+            id: i as i32,
+            section: 1,
+            table,
+            round: (i / 12 % 8) as i32 + 1,
+            board: (i / 12 % 26) as i32 + 1,
+            pair_ns: table,
+            pair_ew: table,
+            declarer: 0,
+            ns_ew: if i % 2 == 0 { "N" } else { "E" }.to_string(),
+            contract: contracts[k].to_string(),
+            result: results[k].to_string(),
+            lead_card: None,
+            remarks: None,
+        }
+    }
+
+    fn synthetic_results(count: usize) -> Vec<ReceivedDataRow> {
+        (0..count).map(synthetic_result).collect()
+    }
+
+    #[test]
+    fn test_row_matchpoints_match_all_matchpoints() {
+        let data = crate::bws::BwsData {
+            received_data: synthetic_results(600),
+            ..Default::default()
+        };
+        let (all, _) = calculate_all_matchpoints(&data);
+        let streamed = calculate_row_matchpoints(&data.received_data, &[]);
+        assert_eq!(all, streamed);
+    }
+
+    #[test]
+    fn test_write_bws_to_xlsx_streaming_large() {
+        // Rows are generated on each pass and never collected
+        let rows = || (0..50_000).map(synthetic_result);
+        let matchpoints = calculate_row_matchpoints(rows(), &[]);
+        assert_eq!(matchpoints.len(), 50_000);
+        assert!(matchpoints.iter().all(|mp| mp.is_some()));
+        assert_eq!(
+            matchpoints,
+            calculate_row_matchpoints(&synthetic_results(50_000), &[])
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("large.xlsx");
+        write_bws_to_xlsx_streaming(rows, &[], &path).unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        assert_zip(&bytes);
+    }
+
+    #[test]
+    fn test_butler_skips_small_boards() {
+        let mut data = imp_test_data();
+        data.received_data.truncate(2);
+        assert!(calculate_imp_totals(&data, ImpMethod::Butler).is_empty());
+    }
+
+    #[test]
+    fn test_rank_pairs_with_tie() {
+        let totals = |boards: u32, pct: f64| PairMatchpoints {
+            boards_played: boards,
+            total_mp_pct: pct,
+            ..Default::default()
+        };
+        let mut pair_totals = PairMatchpointTotals::new();
+        pair_totals.insert((1, 1, true), totals(4, 240.0)); // 60%
+        pair_totals.insert((1, 2, true), totals(4, 180.0)); // 45%
+        pair_totals.insert((1, 3, true), totals(4, 240.0)); // 60%
+        pair_totals.insert((1, 1, false), totals(4, 160.0)); // 40%, EW ranked separately
+        pair_totals.insert((1, 4, true), totals(0, 0.0)); // sat out
+
+        let ranks = rank_pairs(&pair_totals);
+        assert_eq!(ranks.get(&(1, 1, true)), Some(&1));
+        assert_eq!(ranks.get(&(1, 3, true)), Some(&1));
+        assert_eq!(ranks.get(&(1, 2, true)), Some(&2));
+        assert_eq!(ranks.get(&(1, 1, false)), Some(&1));
+        assert_eq!(ranks.get(&(1, 4, true)), None);
+    }
+
+    #[test]
+    fn test_phantom_pair_sit_out() {
+        let mut data = imp_test_data();
+        data.sections[0].scoring_type = Some(0);
+        data.sections[0].missing_pair = 4;
+        // NS pair 4 plays board 1 only; the others also play board 2
+        for table in 1..=3 {
+            let mut row = data.received_data[0].clone();
+            row.table = table;
+            row.pair_ns = table;
+            row.pair_ew = table;
+            row.board = 2;
+            data.received_data.push(row);
+        }
+
+        // Board 1: NS 4 scored 170 against 420, 420, 450 -> 0%
+        let (_, totals) = calculate_all_matchpoints_with_sit_out(&data, 60.0);
+        let ns4 = &totals[&(1, 4, true)];
+        assert_eq!(ns4.boards_played, 1);
+        assert_eq!(ns4.sit_out_boards, 1);
+        assert_eq!(average_mp_pct(ns4), Some(30.0));
+
+        // Without a phantom pair nothing is credited
+        data.sections[0].missing_pair = 0;
+        let (_, totals) = calculate_all_matchpoints(&data);
+        assert_eq!(totals[&(1, 4, true)].sit_out_boards, 0);
+        assert_eq!(average_mp_pct(&totals[&(1, 4, true)]), Some(0.0));
+    }
+
+    #[test]
+    fn test_average_plus_row() {
+        let mut data = imp_test_data();
+        data.sections[0].scoring_type = Some(0);
+        // Table 4 was given Ave+ instead of playing the board
+        data.received_data[3].contract = "Ave+".to_string();
+        data.received_data[3].result = String::new();
+
+        let matchpoints = calculate_row_matchpoints(&data.received_data, &[]);
+        assert_eq!(matchpoints[3], Some(MatchpointPct::from_ns(60.0)));
+
+        // The other tables are matchpointed as if table 4 hadn't played
+        let played = calculate_row_matchpoints(&data.received_data[..3], &[]);
+        assert_eq!(&matchpoints[..3], &played[..]);
+        let ns: Vec<Option<f64>> = played.iter().map(|mp| mp.map(|m| m.ns)).collect();
+        assert_eq!(ns, vec![Some(25.0), Some(25.0), Some(100.0)]);
+    }
+
+    #[test]
+    fn test_remarks_average_row() {
+        let mut data = imp_test_data();
+        data.sections[0].scoring_type = Some(0);
+        // BWS leaves the contract blank and records the average in Remarks
+        data.received_data[3].contract = String::new();
+        data.received_data[3].result = String::new();
+        data.received_data[3].remarks = Some("Ave+".to_string());
+
+        let matchpoints = calculate_row_matchpoints(&data.received_data, &[]);
+        assert_eq!(matchpoints[3], Some(MatchpointPct::from_ns(60.0)));
+
+        // Not matchpointed as a 0 pass-out against the other tables
+        let played = calculate_row_matchpoints(&data.received_data[..3], &[]);
+        assert_eq!(&matchpoints[..3], &played[..]);
+    }
+
+    #[test]
+    fn test_split_average_row() {
+        let mut data = imp_test_data();
+        data.sections[0].scoring_type = Some(0);
+        // Neither side at fault at table 4: both get Ave+
+        data.received_data[3].contract = "Ave+/Ave+".to_string();
+        data.received_data[3].result = String::new();
+
+        let (matchpoints, totals) = calculate_all_matchpoints(&data);
+        assert_eq!(matchpoints[3], Some(MatchpointPct { ns: 60.0, ew: 60.0 }));
+        assert_eq!(totals[&(1, 4, true)].total_mp_pct, 60.0);
+        assert_eq!(totals[&(1, 4, false)].total_mp_pct, 60.0);
+
+        let cards = build_scorecards(&data, &matchpoints);
+        assert_eq!(cards[&(1, 4, false)][0].mp_pct, Some(60.0));
+    }
 }
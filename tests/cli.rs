@@ -0,0 +1,232 @@
+use std::process::Command;
+
+const HAND_RECORD: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/input/1768880456-bJwUQdVmhY@260119HandRecord.pbn"
+);
+
+fn run(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_bridge-parsers"))
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_info_json_pbn() {
+    let stdout = run(&["info", HAND_RECORD, "--format", "json"]);
+    let boards: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(boards.as_array().unwrap().len(), 36);
+
+    let board = &boards[1];
+    assert_eq!(board["number"], 2);
+    assert_eq!(board["dealer"], "E");
+    assert_eq!(board["vulnerability"], "NS");
+
+    let east = &board["hands"][1];
+    assert_eq!(east["direction"], "E");
+    assert_eq!(east["pbn"], "Q7.AKT9.JT3.JT96");
+    assert_eq!(east["shape"], "2-4-3-4");
+    assert_eq!(east["hcp"], 11);
+}
+
+#[test]
+fn test_info_json_with_board_filter() {
+    let stdout = run(&["info", HAND_RECORD, "--format", "json", "--boards", "2-3"]);
+    let boards: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let numbers: Vec<u64> = boards
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|b| b["number"].as_u64().unwrap())
+        .collect();
+    assert_eq!(numbers, vec![2, 3]);
+}
+
+#[test]
+fn test_convert_lin_to_pbn() {
+    let dir = tempfile::tempdir().unwrap();
+    let lin = dir.path().join("game.lin");
+    let pbn = dir.path().join("game.pbn");
+    std::fs::write(
+        &lin,
+        concat!(
+            "qx|o1|pn|South,West,North,East|md|3S48JH25TQD235C679,S36TH468KAD4TC45Q,",
+            "S257QAH9DJQKC8JKA,|sv|o|ah|Board 1|mb|1N|mb|p|mb|3N|mb|p|mb|p|mb|p|\n",
+            "qx|o2|md|4S48JH25TQD235C679,S36TH468KAD4TC45Q,S257QAH9DJQKC8JKA,|sv|n|ah|Board 2",
+            "|mb|p|mb|p|mb|p|mb|p|\n",
+        ),
+    )
+    .unwrap();
+
+    run(&[
+        "convert",
+        lin.to_str().unwrap(),
+        "-o",
+        pbn.to_str().unwrap(),
+    ]);
+
+    let stdout = run(&["info", pbn.to_str().unwrap(), "--format", "json"]);
+    let boards: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(boards.as_array().unwrap().len(), 2);
+    assert_eq!(boards[0]["number"], 1);
+    assert_eq!(boards[0]["dealer"], "N");
+    assert_eq!(boards[1]["number"], 2);
+    assert_eq!(boards[1]["vulnerability"], "NS");
+    assert_eq!(boards[0]["hands"][0]["pbn"], "AQ752.9.KQJ.AKJ8");
+}
+
+#[test]
+fn test_validate_lin_revokes_in_closed_room() {
+    // Open room: North declares 3NT. Closed room: East declares 1D, and West
+    // discards a heart on South's spade lead while holding spades.
+    let dir = tempfile::tempdir().unwrap();
+    let lin = dir.path().join("match.lin");
+    std::fs::write(
+        &lin,
+        concat!(
+            "qx|o1|pn|South,West,North,East|md|3S48JH25TQD235C679,S36TH468KAD4TC45Q,",
+            "S257QAH9DJQKC8JKA,|sv|o|ah|Board 1|mb|1N|mb|p|mb|3N|mb|p|mb|p|mb|p|\n",
+            "qx|c1|md|3S48JH25TQD235C679,S36TH468KAD4TC45Q,S257QAH9DJQKC8JKA,|sv|o|",
+            "mb|p|mb|1D|mb|p|mb|p|mb|p|pc|SJ|pc|H4|pc|SA|pc|S9|\n",
+        ),
+    )
+    .unwrap();
+
+    let stdout = run(&["validate", lin.to_str().unwrap()]);
+    assert!(stdout.contains("2 boards"), "{}", stdout);
+    assert!(
+        stdout.contains("Board 1: trick 1:") && stdout.contains("playing H4 on a S lead"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn test_split_pbn() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = dir.path().join("three.pbn");
+    let out = dir.path().join("boards");
+    let deal = "N:K843.T542.J6.863 AQJ7.K.Q75.AT942 962.AJ7.KT82.J75 T5.Q9863.A943.KQ";
+    let content: String = (1..=3)
+        .map(|n| format!("[Board \"{}\"]\n[Deal \"{}\"]\n\n", n, deal))
+        .collect();
+    std::fs::write(&input, content).unwrap();
+
+    run(&[
+        "split",
+        input.to_str().unwrap(),
+        "-o",
+        out.to_str().unwrap(),
+    ]);
+
+    let mut names: Vec<String> = std::fs::read_dir(&out)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().into_string().unwrap())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["board-001.pbn", "board-002.pbn", "board-003.pbn"]);
+
+    let stdout = run(&[
+        "info",
+        out.join("board-002.pbn").to_str().unwrap(),
+        "--format",
+        "json",
+    ]);
+    let boards: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(boards.as_array().unwrap().len(), 1);
+    assert_eq!(boards[0]["number"], 2);
+    assert_eq!(boards[0]["hands"][0]["pbn"], "K843.T542.J6.863");
+
+    // A second run refuses to overwrite
+    let output = Command::new(env!("CARGO_BIN_EXE_bridge-parsers"))
+        .args([
+            "split",
+            input.to_str().unwrap(),
+            "-o",
+            out.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_split_rejects_duplicate_board_numbers() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = dir.path().join("twice.pbn");
+    let out = dir.path().join("boards");
+    let deal = "N:K843.T542.J6.863 AQJ7.K.Q75.AT942 962.AJ7.KT82.J75 T5.Q9863.A943.KQ";
+    let content: String = [1, 1]
+        .iter()
+        .map(|n| format!("[Board \"{}\"]\n[Deal \"{}\"]\n\n", n, deal))
+        .collect();
+    std::fs::write(&input, content).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_bridge-parsers"))
+        .args([
+            "split",
+            input.to_str().unwrap(),
+            "-o",
+            out.to_str().unwrap(),
+            "--force",
+        ])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("board-001.pbn"));
+    assert!(!out.join("board-001.pbn").exists());
+}
+
+#[test]
+fn test_convert_vulnerability_override() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("white.pbn");
+    run(&[
+        "convert",
+        HAND_RECORD,
+        "-o",
+        output.to_str().unwrap(),
+        "--vulnerability",
+        "none",
+    ]);
+
+    let stdout = run(&["info", output.to_str().unwrap(), "--format", "json"]);
+    let boards: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let boards = boards.as_array().unwrap();
+    assert_eq!(boards.len(), 36);
+    assert!(boards.iter().all(|b| b["vulnerability"] == "None"));
+    // Dealer is untouched without --dealer
+    assert_eq!(boards[1]["dealer"], "E");
+}
+
+#[test]
+fn test_convert_pbn_keeps_scoring_tags() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = dir.path().join("played.pbn");
+    std::fs::write(
+        &input,
+        "[Board \"1\"]\n[Dealer \"N\"]\n[Vulnerable \"None\"]\n\
+         [Deal \"N:K843.T542.J6.863 AQJ7.K.Q75.AT942 962.AJ7.KT82.J75 T5.Q9863.A943.KQ\"]\n\
+         [Scoring \"IMP\"]\n[Declarer \"S\"]\n[Contract \"2H\"]\n[Result \"8\"]\n\
+         [Score \"NS 110\"]\n",
+    )
+    .unwrap();
+    let output = dir.path().join("copy.pbn");
+    run(&[
+        "convert",
+        input.to_str().unwrap(),
+        "-o",
+        output.to_str().unwrap(),
+    ]);
+
+    let written = std::fs::read_to_string(&output).unwrap();
+    assert!(written.contains("[Scoring \"IMP\"]"));
+    assert!(written.contains("[Score \"NS 110\"]"));
+}